@@ -0,0 +1,361 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::Path;
+
+use itertools::Itertools;
+
+use crate::{io, Arguments};
+
+const CACHE_FILE: &str = ".mamba_cache";
+const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Build cache stored in the output directory.
+///
+/// For each source file, identified by its path relative to the output directory, it records
+/// the hash of the source, of the signatures it exports into the context, and of the generated
+/// output.
+/// Anything which affects all output, such as the version and arguments, is stored in the header.
+/// If the header differs, the entire cache is invalid.
+#[derive(Debug, Default, PartialEq)]
+pub struct Cache {
+    entries: HashMap<String, Entry>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    pub source: u64,
+    pub signature: u64,
+    pub output: u64,
+    pub defines: HashSet<String>,
+}
+
+/// State of a source file in the current build.
+pub struct Fingerprint {
+    pub source: u64,
+    pub signature: u64,
+    /// Hash of output currently in output directory, if any.
+    pub output: Option<u64>,
+    pub defines: HashSet<String>,
+    pub uses: HashSet<String>,
+}
+
+impl Cache {
+    /// Load cache from output directory.
+    ///
+    /// If there is no cache, or it was written by another version or with other arguments, the
+    /// cache is empty.
+    pub fn load(out_dir: &Path, arguments: &Arguments) -> Cache {
+        let path = out_dir.join(CACHE_FILE);
+        match io::read_source(&path) {
+            Ok(source) => Cache::from_source(&source, arguments).unwrap_or_else(|| {
+                debug!("Ignoring outdated or malformed cache '{}'", path.display());
+                Cache::default()
+            }),
+            Err(_) => Cache::default(),
+        }
+    }
+
+    /// Remove cache from output directory, if present.
+    pub fn clean(out_dir: &Path) -> Result<(), String> {
+        let path = out_dir.join(CACHE_FILE);
+        if path.exists() {
+            fs::remove_file(&path).map_err(|e| format!("{}: {}", e, path.display()))?;
+            info!("Removed cache '{}'", path.display());
+        }
+        Ok(())
+    }
+
+    pub fn save(&self, out_dir: &Path, arguments: &Arguments) -> Result<(), String> {
+        io::write_source(&self.to_source(arguments), &out_dir.join(CACHE_FILE)).map(|_| ())
+    }
+
+    pub fn insert(&mut self, file: &str, entry: Entry) {
+        self.entries.insert(String::from(file), entry);
+    }
+
+    pub fn get(&self, file: &str) -> Option<&Entry> {
+        self.entries.get(file)
+    }
+
+    /// For each file, whether it must be rebuilt.
+    ///
+    /// A file must be rebuilt if its source or output changed, or if it uses a name defined by a
+    /// file whose signatures changed.
+    /// Files which were added or removed are considered to have changed signatures.
+    pub fn stale(&self, files: &[(String, Fingerprint)]) -> Vec<bool> {
+        let mut names: HashSet<&String> = HashSet::new();
+        for (file, entry) in &self.entries {
+            match files.iter().find(|(other, _)| other == file) {
+                Some((_, current)) if current.signature == entry.signature => {}
+                Some((_, current)) => names.extend(entry.defines.union(&current.defines)),
+                None => names.extend(&entry.defines),
+            }
+        }
+        for (file, current) in files {
+            if !self.entries.contains_key(file) {
+                names.extend(&current.defines);
+            }
+        }
+
+        (files.iter())
+            .map(|(file, current)| match self.entries.get(file) {
+                Some(entry) => {
+                    entry.source != current.source
+                        || current.output != Some(entry.output)
+                        || current.uses.iter().any(|name| names.contains(name))
+                }
+                None => true,
+            })
+            .collect()
+    }
+
+    fn header(arguments: &Arguments) -> String {
+        let python = arguments.python.map(|python| python.to_string());
+        let stub_paths = arguments.stub_paths.iter().map(|p| p.display()).join(",");
+        format!(
+            "mamba {VERSION} annotate={} stubs={} python={} stub-paths={stub_paths} {} source-map={} emit={}:{}",
+            arguments.annotate,
+            arguments.stubs,
+            python.unwrap_or_default(),
+            arguments.lints,
+            arguments.source_map,
+            arguments.emit.iter().join(","),
+            arguments.emit_format
+        )
+    }
+
+    fn to_source(&self, arguments: &Arguments) -> String {
+        let mut lines = vec![Cache::header(arguments)];
+        let entries = self.entries.iter().collect::<Vec<_>>();
+        for (file, entry) in entries.into_iter().sorted_by_key(|(file, _)| *file) {
+            let defines = entry.defines.iter().sorted().join(",");
+            lines.push(format!(
+                "{:016x} {:016x} {:016x} {defines}\t{file}",
+                entry.source, entry.signature, entry.output
+            ));
+        }
+        format!("{}\n", lines.join("\n"))
+    }
+
+    fn from_source(source: &str, arguments: &Arguments) -> Option<Cache> {
+        let mut lines = source.lines();
+        if lines.next()? != Cache::header(arguments) {
+            return None;
+        }
+
+        let mut cache = Cache::default();
+        for line in lines {
+            let (hashes, file) = line.split_once('\t')?;
+            let mut hashes = hashes.split(' ');
+            let mut hash = || u64::from_str_radix(hashes.next()?, 16).ok();
+            let (source, signature, output) = (hash()?, hash()?, hash()?);
+            let defines = hashes.next()?.split(',').filter(|name| !name.is_empty());
+
+            cache.insert(
+                file,
+                Entry {
+                    source,
+                    signature,
+                    output,
+                    defines: defines.map(String::from).collect(),
+                },
+            );
+        }
+        Some(cache)
+    }
+}
+
+/// Hash of given content.
+///
+/// Line endings are normalized, as they are when output is written.
+pub fn hash(content: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    content.replace("\r\n", "\n").hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Hash of the output of a file, which consists of the Python source and optionally a stub.
+pub fn output_hash(python: &str, stub: Option<&str>) -> u64 {
+    match stub {
+        Some(stub) => hash(&format!("{python}\0{stub}")),
+        None => hash(python),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+    use std::fs;
+    use std::fs::File;
+    use std::path::Path;
+    use std::time::UNIX_EPOCH;
+
+    use tempfile::tempdir;
+
+    use crate::cache::{Cache, Entry, Fingerprint};
+    use crate::{transpile_dir, Arguments};
+
+    fn names(names: &[&str]) -> HashSet<String> {
+        names.iter().map(|name| String::from(*name)).collect()
+    }
+
+    fn entry(source: u64, signature: u64, defines: &[&str]) -> Entry {
+        Entry {
+            source,
+            signature,
+            output: 0,
+            defines: names(defines),
+        }
+    }
+
+    fn fingerprint(source: u64, signature: u64, defines: &[&str], uses: &[&str]) -> Fingerprint {
+        Fingerprint {
+            source,
+            signature,
+            output: Some(0),
+            defines: names(defines),
+            uses: names(uses),
+        }
+    }
+
+    fn cache() -> Cache {
+        let mut cache = Cache::default();
+        cache.insert("a.mamba", entry(1, 1, &["f"]));
+        cache.insert("b.mamba", entry(2, 2, &["y"]));
+        cache.insert("c.mamba", entry(3, 3, &["z"]));
+        cache
+    }
+
+    #[test]
+    fn round_trip() {
+        let args = Arguments::default();
+        let cache = cache();
+        assert_eq!(
+            Cache::from_source(&cache.to_source(&args), &args),
+            Some(cache)
+        );
+    }
+
+    #[test]
+    fn other_arguments_invalidate() {
+        let args = Arguments {
+            annotate: true,
+            ..Default::default()
+        };
+        let source = cache().to_source(&Arguments::default());
+        assert_eq!(Cache::from_source(&source, &args), None);
+    }
+
+    #[test]
+    fn unchanged_not_stale() {
+        let files = vec![
+            (
+                String::from("a.mamba"),
+                fingerprint(1, 1, &["f"], &["f", "x"]),
+            ),
+            (
+                String::from("b.mamba"),
+                fingerprint(2, 2, &["y"], &["y", "f"]),
+            ),
+            (String::from("c.mamba"), fingerprint(3, 3, &["z"], &["z"])),
+        ];
+        assert_eq!(cache().stale(&files), vec![false, false, false]);
+    }
+
+    #[test]
+    fn body_change_only_rebuilds_file() {
+        let files = vec![
+            (
+                String::from("a.mamba"),
+                fingerprint(10, 1, &["f"], &["f", "x"]),
+            ),
+            (
+                String::from("b.mamba"),
+                fingerprint(2, 2, &["y"], &["y", "f"]),
+            ),
+            (String::from("c.mamba"), fingerprint(3, 3, &["z"], &["z"])),
+        ];
+        assert_eq!(cache().stale(&files), vec![true, false, false]);
+    }
+
+    #[test]
+    fn signature_change_rebuilds_dependents() {
+        let files = vec![
+            (
+                String::from("a.mamba"),
+                fingerprint(10, 10, &["f"], &["f", "x"]),
+            ),
+            (
+                String::from("b.mamba"),
+                fingerprint(2, 2, &["y"], &["y", "f"]),
+            ),
+            (String::from("c.mamba"), fingerprint(3, 3, &["z"], &["z"])),
+        ];
+        assert_eq!(cache().stale(&files), vec![true, true, false]);
+    }
+
+    #[test]
+    fn removed_file_rebuilds_dependents_and_missing_output_rebuilds() {
+        let mut c = fingerprint(3, 3, &["z"], &["z"]);
+        c.output = None;
+        let files = vec![
+            (
+                String::from("b.mamba"),
+                fingerprint(2, 2, &["y"], &["y", "f"]),
+            ),
+            (String::from("c.mamba"), c),
+        ];
+        assert_eq!(cache().stale(&files), vec![true, true]);
+    }
+
+    /// Set modification time of output to epoch, which is kept if the output is not written.
+    fn reset_modified(dir: &Path, file: &str) {
+        let file = File::options()
+            .write(true)
+            .open(dir.join("target").join(file));
+        file.unwrap().set_modified(UNIX_EPOCH).unwrap();
+    }
+
+    fn written(dir: &Path, file: &str) -> bool {
+        let metadata = fs::metadata(dir.join("target").join(file)).unwrap();
+        metadata.modified().unwrap() != UNIX_EPOCH
+    }
+
+    #[test]
+    fn transpile_skips_unchanged_files() {
+        let dir = tempdir().expect("temp dir");
+        fs::create_dir(dir.path().join("src")).unwrap();
+        let write = |file: &str, source: &str| fs::write(dir.path().join("src").join(file), source);
+        write("a.mamba", "def f(x: Int) -> Int => x + 1\n").unwrap();
+        write("b.mamba", "def y := f(10)\n").unwrap();
+        write("c.mamba", "def z := 20\n").unwrap();
+
+        let args = Arguments::default();
+        transpile_dir(dir.path(), None, None, &args).unwrap();
+        ["a.py", "b.py", "c.py"]
+            .iter()
+            .for_each(|f| reset_modified(dir.path(), f));
+
+        write("a.mamba", "def f(x: Int) -> Int => x + 2\n").unwrap();
+        transpile_dir(dir.path(), None, None, &args).unwrap();
+        assert!(written(dir.path(), "a.py"));
+        assert!(!written(dir.path(), "b.py"));
+        assert!(!written(dir.path(), "c.py"));
+
+        write("a.mamba", "def f(x: Int, y: Int) -> Int => x + y\n").unwrap();
+        write("b.mamba", "def y := f(10, 20)\n").unwrap();
+        reset_modified(dir.path(), "a.py");
+        transpile_dir(dir.path(), None, None, &args).unwrap();
+        assert!(written(dir.path(), "a.py") && written(dir.path(), "b.py"));
+        assert!(!written(dir.path(), "c.py"));
+
+        let clean = Arguments {
+            clean: true,
+            ..Default::default()
+        };
+        transpile_dir(dir.path(), None, None, &clean).unwrap();
+        assert!(written(dir.path(), "c.py"));
+    }
+}
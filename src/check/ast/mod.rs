@@ -1,6 +1,9 @@
 use std::ops::Deref;
 
+use serde::Serialize;
+
 use crate::check::constrain::unify::finished::Finished;
+use crate::check::context::parameter::generic::GenericParameter;
 use crate::check::name::string_name::StringName;
 use crate::check::name::Name;
 use crate::common::position::Position;
@@ -9,7 +12,7 @@ use crate::parse::ast::AST;
 
 pub mod node;
 
-#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+#[derive(PartialEq, Eq, Hash, Clone, Debug, Serialize)]
 pub struct ASTTy {
     pub pos: Position,
     pub node: NodeTy,
@@ -74,7 +77,7 @@ impl ASTTy {
 type OptASTTy = Option<Box<ASTTy>>;
 type OptName = Option<Name>;
 
-#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+#[derive(PartialEq, Eq, Hash, Clone, Debug, Serialize)]
 pub enum NodeTy {
     Import {
         from: Option<Box<ASTTy>>,
@@ -82,10 +85,12 @@ pub enum NodeTy {
         alias: Vec<ASTTy>,
     },
     Class {
+        decorators: Vec<ASTTy>,
         ty: StringName,
         args: Vec<ASTTy>,
         parents: Vec<ASTTy>,
         body: OptASTTy,
+        data: Option<bool>,
     },
     Parent {
         ty: StringName,
@@ -105,12 +110,21 @@ pub enum NodeTy {
     },
     FunDef {
         pure: bool,
+        is_async: bool,
+        is_generator: bool,
+        decorators: Vec<ASTTy>,
         id: Box<ASTTy>,
+        generics: Vec<GenericParameter>,
         args: Vec<ASTTy>,
         ret: OptName,
         raises: Vec<ASTTy>,
         body: OptASTTy,
     },
+    Property {
+        id: Box<ASTTy>,
+        getter: Box<ASTTy>,
+        setter: OptASTTy,
+    },
     AnonFun {
         args: Vec<ASTTy>,
         body: Box<ASTTy>,
@@ -127,10 +141,14 @@ pub enum NodeTy {
         cases: Vec<ASTTy>,
     },
     With {
+        is_async: bool,
         resource: Box<ASTTy>,
         alias: Option<(Box<ASTTy>, bool, OptName)>,
         expr: Box<ASTTy>,
     },
+    Await {
+        expr: Box<ASTTy>,
+    },
     FunctionCall {
         name: StringName,
         args: Vec<ASTTy>,
@@ -139,6 +157,10 @@ pub enum NodeTy {
         instance: Box<ASTTy>,
         property: Box<ASTTy>,
     },
+    SafeCall {
+        instance: Box<ASTTy>,
+        property: Box<ASTTy>,
+    },
     Id {
         lit: String,
     },
@@ -218,9 +240,8 @@ pub enum NodeTy {
     Int {
         lit: String,
     },
-    ENum {
-        num: String,
-        exp: String,
+    Imaginary {
+        lit: String,
     },
     Str {
         lit: String,
@@ -354,9 +375,25 @@ pub enum NodeTy {
     },
     Case {
         cond: Box<ASTTy>,
+        guard: OptASTTy,
         body: Box<ASTTy>,
     },
+    PatternOr {
+        patterns: Vec<ASTTy>,
+    },
+    PatternClass {
+        ty: Box<ASTTy>,
+        fields: Vec<ASTTy>,
+    },
+    PatternField {
+        id: Box<ASTTy>,
+        pattern: Box<ASTTy>,
+    },
+    PatternRest {
+        id: Box<ASTTy>,
+    },
     For {
+        is_async: bool,
         expr: Box<ASTTy>,
         col: Box<ASTTy>,
         body: Box<ASTTy>,
@@ -375,6 +412,12 @@ pub enum NodeTy {
         expr: Box<ASTTy>,
     },
     ReturnEmpty,
+    Yield {
+        expr: Box<ASTTy>,
+    },
+    YieldFrom {
+        expr: Box<ASTTy>,
+    },
     Underscore,
     Undefined,
     Pass,
@@ -3,6 +3,7 @@ use std::ops::Deref;
 
 use crate::check::ast::NodeTy;
 use crate::check::constrain::unify::finished::Finished;
+use crate::check::context::parameter::generic::GenericParameter;
 use crate::check::name::string_name::StringName;
 use crate::check::name::{Empty, Name};
 use crate::parse::ast::Node;
@@ -50,11 +51,16 @@ impl From<(&Node, &Finished)> for NodeTy {
                     .collect(),
             },
             Node::Class {
+                decorators,
                 ty,
                 args,
                 parents,
                 body,
             } => NodeTy::Class {
+                decorators: decorators
+                    .iter()
+                    .map(|ast| ASTTy::from((ast, finished)))
+                    .collect(),
                 ty: StringName::try_from(ty).unwrap_or_else(|_| StringName::empty()),
                 args: args
                     .iter()
@@ -68,6 +74,7 @@ impl From<(&Node, &Finished)> for NodeTy {
                     .clone()
                     .map(|ast| ASTTy::from((ast, finished)))
                     .map(Box::from),
+                data: node.data_class(),
             },
             Node::Parent { ty, args } => NodeTy::Parent {
                 ty: StringName::try_from(ty).unwrap_or_else(|_| StringName::empty()),
@@ -107,14 +114,27 @@ impl From<(&Node, &Finished)> for NodeTy {
             },
             Node::FunDef {
                 pure,
+                is_async,
+                decorators,
                 id,
+                generics,
                 args,
                 ret,
                 raises,
                 body,
             } => NodeTy::FunDef {
                 pure: *pure,
+                is_async: *is_async,
+                is_generator: body.as_ref().is_some_and(|body| body.node.contains_yield()),
+                decorators: decorators
+                    .iter()
+                    .map(|ast| ASTTy::from((ast, finished)))
+                    .collect(),
                 id: Box::from(ASTTy::from((id, finished))),
+                generics: generics
+                    .iter()
+                    .flat_map(GenericParameter::try_from)
+                    .collect(),
                 args: args
                     .iter()
                     .map(|ast| ASTTy::from((ast, finished)))
@@ -129,6 +149,16 @@ impl From<(&Node, &Finished)> for NodeTy {
                     .map(|ast| ASTTy::from((ast, finished)))
                     .map(Box::from),
             },
+            Node::Property {
+                id, getter, setter, ..
+            } => NodeTy::Property {
+                id: Box::from(ASTTy::from((id, finished))),
+                getter: Box::from(ASTTy::from((getter, finished))),
+                setter: setter
+                    .clone()
+                    .map(|ast| ASTTy::from((ast, finished)))
+                    .map(Box::from),
+            },
             Node::AnonFun { args, body } => NodeTy::AnonFun {
                 args: args
                     .iter()
@@ -150,10 +180,12 @@ impl From<(&Node, &Finished)> for NodeTy {
                     .collect(),
             },
             Node::With {
+                is_async,
                 resource,
                 alias,
                 expr,
             } => NodeTy::With {
+                is_async: *is_async,
                 resource: Box::from(ASTTy::from((resource, finished))),
                 alias: alias.clone().map(|(resource, alias, expr)| {
                     let resource = Box::from(ASTTy::from((resource, finished)));
@@ -162,6 +194,9 @@ impl From<(&Node, &Finished)> for NodeTy {
                 }),
                 expr: Box::from(ASTTy::from((expr, finished))),
             },
+            Node::Await { expr } => NodeTy::Await {
+                expr: Box::from(ASTTy::from((expr, finished))),
+            },
             Node::FunctionCall { name, args } => NodeTy::FunctionCall {
                 name: StringName::try_from(name).unwrap_or_else(|_| StringName::empty()),
                 args: args
@@ -173,6 +208,10 @@ impl From<(&Node, &Finished)> for NodeTy {
                 instance: Box::from(ASTTy::from((instance, finished))),
                 property: Box::from(ASTTy::from((property, finished))),
             },
+            Node::SafeCall { instance, property } => NodeTy::SafeCall {
+                instance: Box::from(ASTTy::from((instance, finished))),
+                property: Box::from(ASTTy::from((property, finished))),
+            },
             Node::ExpressionType { expr, mutable, ty } => NodeTy::ExpressionType {
                 expr: Box::from(ASTTy::from((expr, finished))),
                 mutable: *mutable,
@@ -420,11 +459,41 @@ impl From<(&Node, &Finished)> for NodeTy {
                     .map(|ast| ASTTy::from((ast, finished)))
                     .collect(),
             },
-            Node::Case { cond, body } => NodeTy::Case {
+            Node::Case { cond, guard, body } => NodeTy::Case {
                 cond: Box::from(ASTTy::from((cond, finished))),
+                guard: guard
+                    .clone()
+                    .map(|ast| ASTTy::from((ast, finished)))
+                    .map(Box::from),
                 body: Box::from(ASTTy::from((body, finished))),
             },
-            Node::For { expr, col, body } => NodeTy::For {
+            Node::PatternOr { patterns } => NodeTy::PatternOr {
+                patterns: patterns
+                    .iter()
+                    .map(|ast| ASTTy::from((ast, finished)))
+                    .collect(),
+            },
+            Node::PatternClass { ty, fields } => NodeTy::PatternClass {
+                ty: Box::from(ASTTy::from((ty, finished))),
+                fields: fields
+                    .iter()
+                    .map(|ast| ASTTy::from((ast, finished)))
+                    .collect(),
+            },
+            Node::PatternField { id, pattern } => NodeTy::PatternField {
+                id: Box::from(ASTTy::from((id, finished))),
+                pattern: Box::from(ASTTy::from((pattern, finished))),
+            },
+            Node::PatternRest { id } => NodeTy::PatternRest {
+                id: Box::from(ASTTy::from((id, finished))),
+            },
+            Node::For {
+                is_async,
+                expr,
+                col,
+                body,
+            } => NodeTy::For {
+                is_async: *is_async,
                 expr: Box::from(ASTTy::from((expr, finished))),
                 col: Box::from(ASTTy::from((col, finished))),
                 body: Box::from(ASTTy::from((body, finished))),
@@ -440,6 +509,12 @@ impl From<(&Node, &Finished)> for NodeTy {
             Node::Return { expr } => NodeTy::Return {
                 expr: Box::from(ASTTy::from((expr, finished))),
             },
+            Node::Yield { expr } => NodeTy::Yield {
+                expr: Box::from(ASTTy::from((expr, finished))),
+            },
+            Node::YieldFrom { expr } => NodeTy::YieldFrom {
+                expr: Box::from(ASTTy::from((expr, finished))),
+            },
             Node::Question { left, right } => NodeTy::Question {
                 left: Box::from(ASTTy::from((left, finished))),
                 right: Box::from(ASTTy::from((right, finished))),
@@ -471,10 +546,7 @@ impl From<(&Node, &Finished)> for NodeTy {
             },
             Node::Real { lit } => NodeTy::Real { lit: lit.clone() },
             Node::Int { lit } => NodeTy::Int { lit: lit.clone() },
-            Node::ENum { num, exp } => NodeTy::ENum {
-                num: num.clone(),
-                exp: exp.clone(),
-            },
+            Node::Imaginary { lit } => NodeTy::Imaginary { lit: lit.clone() },
             Node::Str { lit, expressions } => NodeTy::Str {
                 lit: lit.clone(),
                 expressions: expressions
@@ -4,6 +4,7 @@ use std::hash::Hash;
 use std::ops::Deref;
 
 use itertools::{EitherOrBoth, Itertools};
+use serde::Serialize;
 
 use crate::check::constrain::constraint::builder::{format_var_map, VarMapping};
 use crate::check::constrain::constraint::expected::Expect::*;
@@ -17,10 +18,11 @@ use crate::common::position::Position;
 use crate::common::result::an_or_a;
 use crate::parse::ast::{Node, AST};
 
-#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize)]
 pub struct Expected {
     pub pos: Position,
     pub expect: Expect,
+    #[serde(skip)]
     an_or_a: bool,
 }
 
@@ -69,6 +71,12 @@ impl MapExp for Expected {
                     entity: Box::from(entity.map_exp(var_mapping, global_var_mapping)),
                     name: Box::from(name.map_exp(var_mapping, global_var_mapping)),
                 },
+                NonNull { expected } => NonNull {
+                    expected: Box::from(expected.map_exp(var_mapping, global_var_mapping)),
+                },
+                OrNone { expected } => OrNone {
+                    expected: Box::from(expected.map_exp(var_mapping, global_var_mapping)),
+                },
                 other => other.clone(),
             },
         )
@@ -133,7 +141,7 @@ impl From<&AST> for Expected {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize)]
 pub enum Expect {
     Expression {
         ast: AST,
@@ -152,6 +160,14 @@ pub enum Expect {
     Type {
         name: Name,
     },
+    /// Type of expected without None.
+    NonNull {
+        expected: Box<Expected>,
+    },
+    /// Type of expected or None.
+    OrNone {
+        expected: Box<Expected>,
+    },
 }
 
 impl Display for Expected {
@@ -178,6 +194,8 @@ impl Display for Expect {
             }
             Field { name } => write!(f, "{name}"),
             Type { name } => write!(f, "{name}"),
+            NonNull { expected } => write!(f, "{} without None", expected.and_or_a(false)),
+            OrNone { expected } => write!(f, "{} or None", expected.and_or_a(false)),
         }
     }
 }
@@ -201,6 +219,8 @@ impl Expect {
                 },
             ) => le == re && ln == rn,
             (Type { name: l }, Type { name: r }) => l == r,
+            (NonNull { expected: l }, NonNull { expected: r }) => l.expect.same_value(&r.expect),
+            (OrNone { expected: l }, OrNone { expected: r }) => l.expect.same_value(&r.expect),
 
             (Function { name: l, args: la }, Function { name: r, args: ra }) => {
                 l == r
@@ -219,6 +239,26 @@ impl Expect {
         }
     }
 
+    /// Remove None from, or add None to, expected if it is a type.
+    ///
+    /// Otherwise, we must wait until we know the type of the inner expected.
+    pub fn resolve_nullable(&self) -> Self {
+        let inner = match self {
+            NonNull { expected } | OrNone { expected } => expected.expect.resolve_nullable(),
+            _ => return self.clone(),
+        };
+
+        match (self, inner) {
+            (NonNull { .. }, Type { name }) => Type {
+                name: name.as_non_nullable(),
+            },
+            (OrNone { .. }, Type { name }) => Type {
+                name: name.as_nullable(),
+            },
+            _ => self.clone(),
+        }
+    }
+
     pub fn is_none(&self) -> bool {
         match &self {
             Type { name } => name.is_null(),
@@ -230,10 +270,52 @@ impl Expect {
 #[cfg(test)]
 mod tests {
     use crate::check::constrain::constraint::expected::{Expect, Expected};
+    use crate::check::name::{Name, Nullable};
     use crate::common::position::{CaretPos, Position};
     use crate::parse::ast::{Node, AST};
     use crate::parse::parse_direct;
 
+    #[test]
+    fn test_non_null_of_nullable_type() {
+        let pos = Position::invisible();
+        let ty = Expect::Type {
+            name: Name::from("Int").as_nullable(),
+        };
+        let non_null = Expect::NonNull {
+            expected: Box::from(Expected::new(pos, &ty)),
+        };
+
+        let expected = Expect::Type {
+            name: Name::from("Int"),
+        };
+        assert_eq!(non_null.resolve_nullable(), expected);
+    }
+
+    #[test]
+    fn test_non_null_of_expression_unchanged() {
+        let ast = parse_direct("a").unwrap();
+        let non_null = Expect::NonNull {
+            expected: Box::from(Expected::from(&ast[0])),
+        };
+        assert_eq!(non_null.resolve_nullable(), non_null);
+    }
+
+    #[test]
+    fn test_nullable_of_type() {
+        let pos = Position::invisible();
+        let ty = Expect::Type {
+            name: Name::from("Int"),
+        };
+        let nullable = Expect::OrNone {
+            expected: Box::from(Expected::new(pos, &ty)),
+        };
+
+        let expected = Expect::Type {
+            name: Name::from("Int").as_nullable(),
+        };
+        assert_eq!(nullable.resolve_nullable(), expected);
+    }
+
     #[test]
     fn test_expected_from_int_constructor_call() {
         let ast = parse_direct("Int(10)").unwrap();
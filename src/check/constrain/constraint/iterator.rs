@@ -1,11 +1,14 @@
 use std::collections::VecDeque;
+use std::fmt::{Display, Error, Formatter};
+
+use serde::Serialize;
 
 use crate::check::constrain::constraint::expected::Expected;
 use crate::check::constrain::constraint::Constraint;
 use crate::check::result::{TypeErr, TypeResult};
 use crate::common::position::Position;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub struct Constraints {
     pub pos: Position,
     pub msg: String,
@@ -23,6 +26,16 @@ impl From<(Position, String, Vec<Constraint>)> for Constraints {
     }
 }
 
+impl Display for Constraints {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        writeln!(f, "{} {}", self.pos, self.msg)?;
+        for constraint in &self.constraints {
+            writeln!(f, "    {}: {constraint}", constraint.msg)?;
+        }
+        Ok(())
+    }
+}
+
 impl Constraints {
     pub fn len(&self) -> usize {
         self.constraints.len()
@@ -1,5 +1,7 @@
 use std::fmt::{Display, Error, Formatter};
 
+use serde::Serialize;
+
 use crate::check::constrain::constraint::builder::VarMapping;
 use crate::check::constrain::constraint::expected::Expect::{Access, Function, Type};
 use crate::check::constrain::constraint::expected::Expected;
@@ -12,7 +14,12 @@ pub mod builder;
 pub mod expected;
 pub mod iterator;
 
-#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+/// Message of the constraint between the left and right side of a reassignment.
+pub const REASSIGN: &str = "reassign";
+/// Message of the constraint of a function called on a variable which is not mutable.
+pub const IMMUTABLE_CALL: &str = "call on immutable";
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize)]
 pub struct Constraint {
     pub is_flag: bool,
     pub is_sub: bool,
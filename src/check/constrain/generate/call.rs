@@ -8,23 +8,27 @@ use itertools::Itertools;
 use crate::check::constrain::constraint::builder::ConstrBuilder;
 use crate::check::constrain::constraint::expected::Expect::*;
 use crate::check::constrain::constraint::expected::{Expect, Expected};
-use crate::check::constrain::constraint::Constraint;
+use crate::check::constrain::constraint::{Constraint, MapExp, IMMUTABLE_CALL, REASSIGN};
 use crate::check::constrain::generate::env::Environment;
+use crate::check::constrain::generate::expression::gen_anon_fun;
 use crate::check::constrain::generate::operation::gen_magic;
 use crate::check::constrain::generate::statement::check_raises_caught;
 use crate::check::constrain::generate::{gen_vec, generate, Constrained};
+use crate::check::constrain::unify::function::instantiate;
 use crate::check::context::arg::python::SELF;
 use crate::check::context::arg::FunctionArg;
 use crate::check::context::function::python::GET_ITEM;
 use crate::check::context::{arg, function, Context, LookupClass, LookupFunction};
 use crate::check::ident::{IdentiCall, Identifier};
 use crate::check::name::string_name::StringName;
-use crate::check::name::{Empty, Name};
+use crate::check::name::{ContainsTemp, Empty, Name, TupleCallable};
 use crate::check::result::{TypeErr, TypeResult};
 use crate::common::position::Position;
 use crate::parse::ast::node_op::NodeOp;
 use crate::parse::ast::{Node, AST};
 
+const SAFE_CALL_VAR: &str = "_safe_call";
+
 pub fn gen_call(
     ast: &AST,
     env: &Environment,
@@ -47,13 +51,32 @@ pub fn gen_call(
                     })
                     .fold(env.clone(), |env, self_var| env.assigned_to(&self_var));
 
-                constr.add(
-                    "reassign",
-                    &Expected::from(left),
-                    &Expected::from(right),
-                    env,
-                );
                 generate(right, &env_assigned_to, ctx, constr)?;
+                let env_assigned_to = assigned_vars(left)
+                    .iter()
+                    .fold(env_assigned_to, |env, var| {
+                        env.assigned_to_var(var).widen_var(var)
+                    });
+
+                // Right may still use narrowed variables, left no longer narrowed
+                let global = &constr.var_mapping;
+                let left_exp = Expected::from(left).map_exp(&env_assigned_to.var_mapping, global);
+                let right_exp = Expected::from(right).map_exp(&env.var_mapping, global);
+                let reassign = Constraint::new(REASSIGN, &left_exp, &right_exp);
+                constr.add_constr_map(&reassign, &env.var_mapping, true);
+                if let Some((entity, name)) = reassigned_field(left) {
+                    let global = &constr.var_mapping;
+                    let entity = Expected::from(&entity).map_exp(&env.var_mapping, global);
+                    let field = Expected::new(
+                        left.pos,
+                        &Access {
+                            entity: Box::from(entity),
+                            name: Box::from(Expected::new(left.pos, &Field { name })),
+                        },
+                    );
+                    let reassign = Constraint::new(REASSIGN, &field, &right_exp);
+                    constr.add_constr_map(&reassign, &env.var_mapping, true);
+                }
                 generate(left, &env_assigned_to, ctx, constr)?;
                 Ok(env_assigned_to)
             } else {
@@ -62,7 +85,13 @@ pub fn gen_call(
         }
         Node::FunctionCall { name, args } => {
             let f_name = StringName::try_from(name)?;
-            gen_vec(args, env, false, ctx, constr)?;
+            let params = match env.get_var(&f_name.name, &constr.var_mapping) {
+                Some(_) => vec![],
+                None => ctx
+                    .function(&f_name, ast.pos)
+                    .map_or_else(|_| vec![], |fun| fun_params(&fun)),
+            };
+            gen_args(args, &params, env, ctx, constr)?;
 
             Ok(if f_name == StringName::from(function::PRINT) {
                 args.iter()
@@ -99,15 +128,51 @@ pub fn gen_call(
             } else {
                 // Resort to looking up in Context
                 let fun = ctx.function(&f_name, ast.pos)?;
-                call_parameters(ast, &fun.arguments, &None, args, ctx, env, constr)?;
-                let fun_ret_exp = Expected::new(ast.pos, &Type { name: fun.ret_ty });
-                // entire AST is either fun ret ty or statement
-                constr.add("function call", &Expected::from(ast), &fun_ret_exp, env);
-
-                check_raises_caught(&fun.raises.names, env, ctx, ast.pos)?;
-                env.clone()
+                context_call(ast, &fun, args, env, ctx, constr)?
             })
         }
+        Node::PropertyCall { instance, property }
+            if is_class_call(instance, property, env, ctx, constr) =>
+        {
+            match (&instance.node, &property.node) {
+                (Node::Id { lit }, Node::FunctionCall { name, args }) => {
+                    let class = StringName::from(lit.as_str());
+                    let f_name = StringName::try_from(name)?;
+                    let fun = ctx.function((&class, &f_name), property.pos)?;
+                    gen_args(args, &fun_params(&fun), env, ctx, constr)?;
+                    context_call(ast, &fun, args, env, ctx, constr)
+                }
+                (
+                    _,
+                    Node::PropertyCall {
+                        instance: call,
+                        property,
+                    },
+                ) => {
+                    // Call on class first, rest of chain on its result
+                    let node = Node::PropertyCall {
+                        instance: instance.clone(),
+                        property: call.clone(),
+                    };
+                    let class_call = AST::new(instance.pos.union(call.pos), node);
+                    let node = Node::PropertyCall {
+                        instance: Box::from(class_call),
+                        property: property.clone(),
+                    };
+                    let chain = AST::new(ast.pos, node);
+
+                    let env = generate(&chain, env, ctx, constr)?;
+                    constr.add(
+                        "class call",
+                        &Expected::from(ast),
+                        &Expected::from(&chain),
+                        &env,
+                    );
+                    Ok(env)
+                }
+                _ => Err(vec![TypeErr::new(ast.pos, "Expected call on class")]),
+            }
+        }
         Node::PropertyCall { instance, property } => property_call(
             &mut vec![instance.deref().clone()],
             property,
@@ -115,12 +180,155 @@ pub fn gen_call(
             ctx,
             constr,
         ),
+        Node::SafeCall { instance, property } => {
+            // Property call on receiver, which is the instance narrowed to not None
+            let env = generate(instance, env, ctx, constr)?;
+            let instance_exp =
+                Expected::from(instance).map_exp(&env.var_mapping, &constr.var_mapping);
+            let non_null = NonNull {
+                expected: Box::from(instance_exp),
+            };
+            let non_null = Expected::new(instance.pos, &non_null);
+
+            // Fresh variable, so receiver is only narrowed within the call
+            let mutable = match &instance.node {
+                Node::Id { lit } => env
+                    .get_var(lit, &constr.var_mapping)
+                    .map_or(true, |expected| {
+                        expected.iter().any(|(mutable, _)| *mutable)
+                    }),
+                _ => true,
+            };
+            constr.insert_var(SAFE_CALL_VAR);
+            let offset = *constr
+                .var_mapping
+                .get(SAFE_CALL_VAR)
+                .expect("just inserted");
+            let receiver_env = env.narrow_var(mutable, SAFE_CALL_VAR, offset, &non_null);
+
+            let lit = String::from(SAFE_CALL_VAR);
+            let receiver = AST::new(instance.pos, Node::Id { lit });
+            let global = &constr.var_mapping;
+            let receiver_exp = Expected::from(&receiver).map_exp(&receiver_env.var_mapping, global);
+            let narrowed = Constraint::new("safe call receiver", &receiver_exp, &non_null);
+            constr.add_constr_map(&narrowed, &receiver_env.var_mapping, true);
+
+            let (instance, property) = (Box::from(receiver), property.clone());
+            let call = AST::new(ast.pos, Node::PropertyCall { instance, property });
+            generate(&call, &receiver_env, ctx, constr)?;
+
+            // Safe call itself outside, call on narrowed receiver inside
+            let global = &constr.var_mapping;
+            let call_exp = Expected::from(&call).map_exp(&receiver_env.var_mapping, global);
+            let nullable = Expected::new(
+                ast.pos,
+                &OrNone {
+                    expected: Box::from(call_exp),
+                },
+            );
+            let safe_call_exp = Expected::from(ast).map_exp(&env.var_mapping, global);
+            let safe_call = Constraint::new("safe call", &safe_call_exp, &nullable);
+            constr.add_constr_map(&safe_call, &env.var_mapping, true);
+            Ok(env)
+        }
         Node::Index { item, range } => gen_magic(GET_ITEM, ast, item, range, env, ctx, constr),
 
         _ => Err(vec![TypeErr::new(ast.pos, "Was expecting call")]),
     }
 }
 
+/// Whether a property call is a call to a function on a class, instead of on an instance.
+fn is_class_call(
+    instance: &AST,
+    property: &AST,
+    env: &Environment,
+    ctx: &Context,
+    constr: &ConstrBuilder,
+) -> bool {
+    let is_call = match &property.node {
+        Node::FunctionCall { .. } => true,
+        Node::PropertyCall { instance, .. } => matches!(instance.node, Node::FunctionCall { .. }),
+        _ => false,
+    };
+
+    match &instance.node {
+        Node::Id { lit } if is_call && env.get_var(lit, &constr.var_mapping).is_none() => {
+            let class = StringName::from(lit.as_str());
+            ctx.class(&class, instance.pos).is_ok()
+        }
+        _ => false,
+    }
+}
+
+/// Call a function from the context, the result of which is the result of the entire call.
+/// Generate arguments of a call.
+///
+/// An anonymous function takes the argument types of the callable parameter it is passed to.
+fn gen_args(
+    args: &[AST],
+    params: &[FunctionArg],
+    env: &Environment,
+    ctx: &Context,
+    constr: &mut ConstrBuilder,
+) -> Constrained<()> {
+    for either_or_both in args.iter().zip_longest(params) {
+        let (arg, param) = match either_or_both {
+            Both(arg, param) => (arg, Some(param)),
+            Left(arg) => (arg, None),
+            Right(_) => break,
+        };
+
+        let expected = param
+            .and_then(|param| param.ty.clone())
+            .and_then(|ty| ty.args(arg.pos).ok())
+            .filter(|arg_names| arg_names.len() == 1)
+            .and_then(|arg_names| arg_names.into_iter().next());
+        match (&arg.node, expected) {
+            (Node::AnonFun { args, body }, Some(expected)) => {
+                gen_anon_fun(args, body, &expected, env, ctx, constr)?
+            }
+            _ => generate(arg, env, ctx, constr)?,
+        };
+    }
+    Ok(())
+}
+
+/// Parameters of function, unless generic, in which case they are only known once instantiated.
+fn fun_params(fun: &function::Function) -> Vec<FunctionArg> {
+    if fun.generics.is_empty() {
+        fun.arguments.clone()
+    } else {
+        vec![]
+    }
+}
+
+fn context_call(
+    ast: &AST,
+    fun: &function::Function,
+    args: &[AST],
+    env: &Environment,
+    ctx: &Context,
+    constr: &mut ConstrBuilder,
+) -> Constrained {
+    let (fun, bounds) = instantiate(fun, ast.pos)?;
+    call_parameters(ast, &fun.arguments, &None, args, ctx, env, constr)?;
+    for (bound, name) in bounds {
+        let bound = Expected::new(ast.pos, &Type { name: bound });
+        constr.add(
+            "generic bound",
+            &bound,
+            &Expected::new(ast.pos, &Type { name }),
+            env,
+        );
+    }
+    let fun_ret_exp = Expected::new(ast.pos, &Type { name: fun.ret_ty });
+    // entire AST is either fun ret ty or statement
+    constr.add("function call", &Expected::from(ast), &fun_ret_exp, env);
+
+    check_raises_caught(&fun.raises.names, env, ctx, ast.pos)?;
+    Ok(env.clone())
+}
+
 fn check_iden_mut(
     id: &Identifier,
     env: &Environment,
@@ -181,7 +389,11 @@ fn call_parameters(
                 })?;
 
                 let arg_exp = Expected::new(*pos, arg);
-                let name = Name::from(&ctx.class(ty, *pos)?);
+                let name = if ty.contains_temp() {
+                    ty.clone() // generic inferred during unification
+                } else {
+                    Name::from(&ctx.class(ty, *pos)?)
+                };
                 constr.add(
                     "call parameters",
                     &Expected::new(*pos, &Type { name }),
@@ -270,6 +482,21 @@ fn property_call(
         }
     };
 
+    // Field narrowed by a condition, such as self.x in if self.x != None then self.x
+    if let (Node::Id { lit: field }, Node::Id { lit: var }) =
+        (&property.node, &ast_without_access.node)
+    {
+        if let Some(narrowed) = env.get_narrowed_field(var, field, &constr.var_mapping) {
+            // Narrowed field already mapped, so map access separately
+            let global = &constr.var_mapping;
+            let entire_call = entire_call_as_ast.map_exp(&env.var_mapping, global);
+            let narrowed = Constraint::new("narrowed field", narrowed, &entire_call);
+            constr.add_constr_map(&narrowed, &env.var_mapping, true);
+            generate(&ast_without_access, env, ctx, constr)?;
+            return Ok(env.clone());
+        }
+    }
+
     let entity = Box::new(Expected::from(&ast_without_access));
     let msg = format!("access property of {entity}");
     let access = Expected::new(
@@ -281,10 +508,37 @@ fn property_call(
     );
     constr.add(&msg, &access, &entire_call_as_ast, env);
 
+    // Function called on a variable which is not mutable may not mutate it
+    if let (Node::FunctionCall { .. }, Node::Id { lit }) =
+        (&property.node, &ast_without_access.node)
+    {
+        let var = env.get_var(lit, &constr.var_mapping);
+        if var.is_some_and(|expected| expected.iter().all(|(mutable, _)| !*mutable)) {
+            constr.add(IMMUTABLE_CALL, &access, &entire_call_as_ast, env);
+        }
+    }
+
     generate(&ast_without_access, env, ctx, constr)?;
     Ok(env.clone())
 }
 
+/// Get the entity and name of the field which is reassigned to, if any.
+fn reassigned_field(ast: &AST) -> Option<(AST, String)> {
+    match &ast.node {
+        Node::PropertyCall { instance, property } => match &property.node {
+            Node::Id { lit } => Some((*instance.clone(), lit.clone())),
+            _ => reassigned_field(property).map(|(entity, name)| {
+                let node = Node::PropertyCall {
+                    instance: instance.clone(),
+                    property: Box::from(entity.clone()),
+                };
+                (AST::new(instance.pos.union(entity.pos), node), name)
+            }),
+        },
+        _ => None,
+    }
+}
+
 /// Check if AST is something was can be re-assigned to.
 ///
 /// This is true if it is a valid identifier, or a property call which is a identifier.
@@ -380,3 +634,12 @@ fn reassign_op(
 
     Ok(env.clone())
 }
+
+/// Local variables which are assigned to if we assign to given AST.
+fn assigned_vars(ast: &AST) -> Vec<String> {
+    match &ast.node {
+        Node::Id { lit } => vec![lit.clone()],
+        Node::Tuple { elements } => elements.iter().flat_map(assigned_vars).collect(),
+        _ => vec![],
+    }
+}
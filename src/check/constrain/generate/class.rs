@@ -1,11 +1,11 @@
 use std::convert::TryFrom;
 
 use crate::check::constrain::constraint::builder::ConstrBuilder;
-use crate::check::constrain::generate::definition::id_from_var;
+use crate::check::constrain::generate::definition::{constrain_decorators, id_from_var};
 use crate::check::constrain::generate::env::Environment;
 use crate::check::constrain::generate::{gen_vec, generate, Constrained};
 use crate::check::context::arg::python::SELF;
-use crate::check::context::Context;
+use crate::check::context::{Context, LookupFunction};
 use crate::check::name::string_name::StringName;
 use crate::check::name::Name;
 use crate::check::result::TypeErr;
@@ -18,6 +18,13 @@ pub fn gen_class(
     ctx: &Context,
     constr: &mut ConstrBuilder,
 ) -> Constrained {
+    if let Node::Class { decorators, ty, .. } = &ast.node {
+        if !decorators.is_empty() {
+            let constructor = ctx.function(&StringName::try_from(ty)?, ty.pos)?;
+            constrain_decorators(decorators, &constructor.callable(), env, ctx, constr)?;
+        }
+    }
+
     match &ast.node {
         Node::Class {
             body: Some(body),
@@ -1,16 +1,15 @@
-use std::convert::TryFrom;
 use std::ops::Deref;
 
 use crate::check::constrain::constraint::builder::ConstrBuilder;
 use crate::check::constrain::constraint::expected::Expect::*;
 use crate::check::constrain::constraint::expected::Expected;
 use crate::check::constrain::constraint::Constraint;
+use crate::check::constrain::generate::definition::fresh_vars;
 use crate::check::constrain::generate::env::Environment;
 use crate::check::constrain::generate::{gen_vec, generate, Constrained};
 use crate::check::context::clss::{COLLECTION, DICT, LIST, SET, TUPLE};
-use crate::check::context::function::python::{ITER, NEXT};
+use crate::check::context::function::python::{AITER, ANEXT, AWAIT, ITER, NEXT};
 use crate::check::context::Context;
-use crate::check::ident::Identifier;
 use crate::check::name::string_name::StringName;
 use crate::check::name::{Any, Empty, Name, Union};
 use crate::check::result::TypeErr;
@@ -112,7 +111,7 @@ fn gen_builder(
 
         generate(right, env, ctx, constr)?;
         let conds_env = generate(left, &env.is_def_mode(true), ctx, constr)?.is_def_mode(false);
-        let conds_env = constr_col_lookup(left, right, &conds_env, constr)?;
+        let conds_env = constr_col_lookup(left, right, false, &conds_env, constr)?;
 
         generate(item, &conds_env, ctx, constr)?;
         if let Some(pair) = pair {
@@ -230,18 +229,47 @@ fn gen_col_items(
 /// Constrain lookup an collection.
 ///
 /// Adds constraint of collection of type lookup, and the given collection.
+/// If async, the collection is iterated over using its async iterator, and each item awaited.
 pub fn constr_col_lookup(
     lookup: &AST,
     col: &AST,
+    is_async: bool,
     env: &Environment,
     constr: &mut ConstrBuilder,
 ) -> Constrained {
-    let mut env = env.clone();
+    let exp_lookup_temp = constr_col_items(col, is_async, env, constr);
+
+    let (name, env) = fresh_vars(lookup, None, true, constr, env)?;
+
+    let exp_lookup_temp = Expected::new(lookup.pos, &exp_lookup_temp.expect);
+    constr.add(
+        "lookup type",
+        &exp_lookup_temp,
+        &Expected::new(lookup.pos, &Type { name }),
+        &env,
+    );
+    Ok(env)
+}
+
+/// Constrain the items of a collection, returning the expected type of each item.
+///
+/// Items are obtained by iterating over the collection, so this works for any iterable.
+pub fn constr_col_items(
+    col: &AST,
+    is_async: bool,
+    env: &Environment,
+    constr: &mut ConstrBuilder,
+) -> Expected {
     let (col_ty, iter_ty) = (constr.temp_name(), constr.temp_name());
     let exp_col = Expected::from(col);
+    let (iter, next) = if is_async {
+        (AITER, ANEXT)
+    } else {
+        (ITER, NEXT)
+    };
 
     let fun = Function {
-        name: StringName::from(ITER),
+        name: StringName::from(iter),
         args: vec![exp_col.clone()],
     };
     let col_iterator = Expected::new(
@@ -254,48 +282,50 @@ pub fn constr_col_lookup(
 
     let iter_ty = Expected::new(Position::invisible(), &Type { name: iter_ty });
     let iter_constr = Constraint::new("iterable", &iter_ty, &col_iterator);
-    constr.add_constr(&iter_constr, &env);
+    constr.add_constr(&iter_constr, env);
 
     let fun = Function {
-        name: StringName::from(NEXT),
+        name: StringName::from(next),
         args: vec![iter_ty.clone()],
     };
     let next_access = Access {
         entity: Box::from(iter_ty),
         name: Box::new(Expected::new(exp_col.pos, &fun)),
     };
+    let next_access = if is_async {
+        let next_ty = Expected::new(
+            Position::invisible(),
+            &Type {
+                name: constr.temp_name(),
+            },
+        );
+        let next_constr = Constraint::new(
+            "async iterable",
+            &next_ty,
+            &Expected::new(exp_col.pos, &next_access),
+        );
+        constr.add_constr(&next_constr, env);
+
+        let fun = Function {
+            name: StringName::from(AWAIT),
+            args: vec![next_ty.clone()],
+        };
+        Access {
+            entity: Box::from(next_ty),
+            name: Box::new(Expected::new(exp_col.pos, &fun)),
+        }
+    } else {
+        next_access
+    };
 
-    let next_ty = Expected::new(
-        exp_col.pos,
-        &Type {
-            name: col_ty.clone(),
-        },
-    );
+    let next_ty = Expected::new(exp_col.pos, &Type { name: col_ty });
     let next_constr = Constraint::new(
         "iterable",
         &next_ty,
         &Expected::new(exp_col.pos, &next_access),
     );
-    constr.add_constr(&next_constr, &env);
-
-    for (mutable, var) in Identifier::try_from(lookup)?.fields(lookup.pos)? {
-        constr.insert_var(&var);
-        env = env.insert_var(
-            mutable,
-            &var,
-            &Expected::any(lookup.pos),
-            &constr.var_mapping,
-        );
-    }
-
-    let exp_lookup_temp = Expected::new(lookup.pos, &Type { name: col_ty });
-    constr.add(
-        "lookup type",
-        &exp_lookup_temp,
-        &Expected::from(lookup),
-        &env,
-    );
-    Ok(env)
+    constr.add_constr(&next_constr, env);
+    next_ty
 }
 
 #[cfg(test)]
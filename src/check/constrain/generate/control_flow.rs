@@ -1,14 +1,22 @@
+use std::collections::HashSet;
 use std::convert::TryFrom;
 
 use crate::check::constrain::constraint::builder::ConstrBuilder;
+use crate::check::constrain::constraint::expected::Expect::{NonNull, Type};
 use crate::check::constrain::constraint::expected::Expected;
-use crate::check::constrain::constraint::Constraint;
+use crate::check::constrain::constraint::{Constraint, MapExp};
 use crate::check::constrain::generate::collection::constr_col_lookup;
+use crate::check::constrain::generate::definition::id_from_var;
 use crate::check::constrain::generate::env::Environment;
 use crate::check::constrain::generate::{generate, Constrained};
-use crate::check::context::Context;
+use crate::check::context::clss::{GetField, LIST, NONE};
+use crate::check::context::{Context, LookupClass};
+use crate::check::name::string_name::StringName;
 use crate::check::name::true_name::TrueName;
-use crate::check::result::TypeErr;
+use crate::check::name::{Empty, Name, Nullable, TupleCallable};
+use crate::check::result::{TypeErr, TypeResult};
+use crate::common::position::Position;
+use crate::common::result::Cause;
 use crate::parse::ast::{Node, AST};
 
 pub fn gen_flow(
@@ -46,7 +54,11 @@ pub fn gen_flow(
             let outer_env = generate(expr_or_stmt, &env.raises_caught(&raises), ctx, constr)?
                 .raises_caught(&raises_before);
 
-            constrain_cases(ast, &None, cases, &outer_env, ctx, constr)
+            // Arms may be entered before anything in the expression was assigned to
+            let arm_env = outer_env.with_unassigned_vars(&env.unassigned_vars);
+            let no_raise = Cause::new("when no exception is raised", expr_or_stmt.pos);
+            let fallthrough = Some((outer_env, no_raise));
+            constrain_cases(ast, &None, cases, fallthrough, &arm_env, ctx, constr)
         }
 
         Node::IfElse {
@@ -62,29 +74,49 @@ pub fn gen_flow(
             let if_expr_exp = Expected::from(ast);
 
             constr.branch_point();
-            let then_env = generate(then, env, ctx, constr)?;
+            let narrowed_env = narrow(&narrowings(cond, true)?, env, constr);
+            let then_env = generate(then, &narrowed_env, ctx, constr)?;
             if env.is_expr {
                 constr.add(
                     "then branch equal to if",
                     &if_expr_exp,
                     &Expected::from(then),
-                    env,
+                    &narrowed_env,
                 );
             }
 
             constr.branch("if else branch", el.pos);
-            let else_env = generate(el, env, ctx, constr)?;
+            let narrowed_env = narrow(&narrowings(cond, false)?, env, constr);
+            let else_env = generate(el, &narrowed_env, ctx, constr)?;
             if env.is_expr {
                 constr.add(
                     "else branch equal to if",
                     &if_expr_exp,
                     &Expected::from(el),
-                    env,
+                    &narrowed_env,
                 );
             }
 
             constr.reset_branches();
-            Ok(env.intersection(&then_env.union(&else_env)))
+            let branches = [
+                (then, then_env.clone(), "through then branch"),
+                (el, else_env.clone(), "through else branch"),
+            ];
+            let branches: Vec<(Environment, Cause)> = branches
+                .into_iter()
+                .filter(|(branch, ..)| !diverges(branch))
+                .map(|(branch, env, msg)| (env, Cause::new(msg, branch.pos)))
+                .collect();
+            let env = env
+                .intersection(&then_env.union(&else_env))
+                .join_branches(&branches);
+
+            // If one branch exits early, what we learned in the other still holds after
+            Ok(match (diverges(then), diverges(el)) {
+                (true, false) => narrow(&narrowings(cond, false)?, &env, constr),
+                (false, true) => narrow(&narrowings(cond, true)?, &env, constr),
+                _ => env,
+            })
         }
         Node::IfElse { cond, then, .. } => {
             constr.add_constr(
@@ -93,24 +125,62 @@ pub fn gen_flow(
             );
 
             generate(cond, env, ctx, constr)?;
-            generate(then, env, ctx, constr)?;
-            Ok(env.clone())
+            let narrowed_env = narrow(&narrowings(cond, true)?, env, constr);
+            let then_env = generate(then, &narrowed_env, ctx, constr)?;
+
+            let mut branches = vec![(
+                env.clone(),
+                Cause::new("when if condition is false", cond.pos),
+            )];
+            if !diverges(then) {
+                branches.push((then_env, Cause::new("through then branch", then.pos)));
+                Ok(env.join_branches(&branches))
+            } else {
+                // Early exit guard, so condition is false after
+                let env = env.join_branches(&branches);
+                Ok(narrow(&narrowings(cond, false)?, &env, constr))
+            }
         }
 
         Node::Case { .. } => Err(vec![TypeErr::new(ast.pos, "Case cannot be top level")]),
+        Node::PatternOr { .. }
+        | Node::PatternClass { .. }
+        | Node::PatternField { .. }
+        | Node::PatternRest { .. } => {
+            let msg = "Pattern can only be used in match arm";
+            Err(vec![TypeErr::new(ast.pos, msg)])
+        }
         Node::Match { cond, cases } => {
             let outer_env = generate(cond, env, ctx, constr)?;
-            constrain_cases(ast, &Some(*cond.clone()), cases, &outer_env, ctx, constr)
+            let fallthrough = if cases.iter().any(is_irrefutable) {
+                None
+            } else {
+                let no_match = Cause::new("when no match arm matches", ast.pos);
+                Some((outer_env.clone(), no_match))
+            };
+
+            let expr = Some(*cond.clone());
+            constrain_cases(ast, &expr, cases, fallthrough, &outer_env, ctx, constr)
         }
 
-        Node::For { expr, col, body } => {
+        Node::For { is_async: true, .. } if !env.in_async => Err(vec![TypeErr::new(
+            ast.pos,
+            "Cannot have async for outside async function",
+        )]),
+        Node::For {
+            is_async,
+            expr,
+            col,
+            body,
+        } => {
             let col_env = generate(col, env, ctx, constr)?;
-            let lookup_env = constr_col_lookup(expr, col, &col_env.is_def_mode(true), constr)?
-                .is_def_mode(false);
+            let def_env = col_env.is_def_mode(true);
+            let lookup_env =
+                constr_col_lookup(expr, col, *is_async, &def_env, constr)?.is_def_mode(false);
             let lookup_env = generate(expr, &lookup_env, ctx, constr)?;
 
-            generate(body, &lookup_env.in_loop(), ctx, constr)?;
-            Ok(env.clone())
+            let body_env = generate(body, &lookup_env.in_loop(), ctx, constr)?;
+            Ok(join_loop(env, body, &body_env))
         }
         Node::While { cond, body } => {
             constr.add_constr(
@@ -119,8 +189,8 @@ pub fn gen_flow(
             );
 
             generate(cond, env, ctx, constr)?;
-            generate(body, &env.in_loop(), ctx, constr)?;
-            Ok(env.clone())
+            let body_env = generate(body, &env.in_loop(), ctx, constr)?;
+            Ok(join_loop(env, body, &body_env))
         }
 
         Node::Break | Node::Continue if env.in_loop => Ok(env.clone()),
@@ -130,36 +200,65 @@ pub fn gen_flow(
     }
 }
 
+/// Join environment before a loop with the environment after its body.
+///
+/// The body of a loop may never be executed.
+fn join_loop(env: &Environment, body: &AST, body_env: &Environment) -> Environment {
+    let mut branches = vec![(
+        env.clone(),
+        Cause::new("when loop body is not executed", body.pos),
+    )];
+    if !diverges(body) {
+        branches.push((body_env.clone(), Cause::new("through loop body", body.pos)));
+    }
+    env.join_branches(&branches)
+}
+
+/// Constrain the arms of a match or handle.
+///
+/// The fallthrough is a path which does not pass through any arm, if such a path exists.
 fn constrain_cases(
     ast: &AST,
     expr: &Option<AST>,
     cases: &Vec<AST>,
+    fallthrough: Option<(Environment, Cause)>,
     env: &Environment,
     ctx: &Context,
     constr: &mut ConstrBuilder,
 ) -> Constrained {
     let is_define_mode = env.is_def_mode;
+    let subject = expr
+        .as_ref()
+        .map(|expr| Expected::from(expr).map_exp(&env.var_mapping, &constr.var_mapping));
     constr.branch_point();
 
     let mut envs = vec![];
+    let mut branches: Vec<(Environment, Cause)> = fallthrough.into_iter().collect();
     for case in cases {
         match &case.node {
-            Node::Case { cond, body } => {
+            Node::Case { cond, guard, body } => {
                 constr.branch("match arm", case.pos);
-                let cond_env = generate(cond, &env.is_def_mode(true), ctx, constr)?;
-
-                if let Node::ExpressionType { expr: ref cond, .. } = cond.node {
-                    if let Some(expr) = &expr {
-                        constr.add(
-                            "arm body",
-                            &Expected::from(expr),
-                            &Expected::from(cond),
-                            env,
-                        );
-                    }
+                let cond_env = if let Some(subject) = &subject {
+                    constrain_pattern(cond, subject, &env.is_def_mode(true), ctx, constr)?
+                } else if let Some(guard) = guard {
+                    let msg = "Handle arm cannot have a guard";
+                    return Err(vec![TypeErr::new(guard.pos, msg)]);
+                } else {
+                    generate(cond, &env.is_def_mode(true), ctx, constr)?
+                };
+
+                if let Some(guard) = guard {
+                    let guard_env = cond_env.is_def_mode(false);
+                    generate(guard, &guard_env, ctx, constr)?;
+                    let guard_exp = Expected::from(guard);
+                    constr.add_constr(&Constraint::truthy("arm guard", &guard_exp), &guard_env);
                 }
 
                 let body_env = generate(body, &cond_env.is_def_mode(is_define_mode), ctx, constr)?;
+                if !diverges(body) {
+                    let cause = Cause::new("through match arm", case.pos);
+                    branches.push((body_env.clone(), cause));
+                }
                 envs.push(body_env);
                 let exp_body = Expected::from(body);
                 constr.add("arm body", &exp_body, &Expected::from(ast), env);
@@ -174,9 +273,421 @@ fn constrain_cases(
 
     constr.reset_branches();
     let env_union = envs.into_iter().reduce(|e1, e2| e1.union(&e2));
-    if let Some(env_union) = env_union {
-        Ok(env.intersection(&env_union))
+    let env = if let Some(env_union) = env_union {
+        env.intersection(&env_union)
     } else {
-        Ok(env.clone())
+        env.clone()
+    };
+    Ok(env.join_branches(&branches))
+}
+
+/// Constrain the pattern of a match arm, where subject is what the pattern is matched against.
+///
+/// Any identifier within the pattern is defined in the returned environment.
+/// The subject should already be mapped using the environment outside the match arm, as
+/// identifiers in the pattern may shadow those in the subject.
+fn constrain_pattern(
+    pattern: &AST,
+    subject: &Expected,
+    env: &Environment,
+    ctx: &Context,
+    constr: &mut ConstrBuilder,
+) -> Constrained {
+    match &pattern.node {
+        Node::Underscore => Ok(env.clone()),
+        Node::ExpressionType { expr, ty, .. } => {
+            let pattern_env = generate(pattern, env, ctx, constr)?;
+            if ty.is_none() {
+                let capture =
+                    Expected::from(expr).map_exp(&pattern_env.var_mapping, &constr.var_mapping);
+                let constraint = Constraint::new("capture pattern", subject, &capture);
+                constr.add_constr_map(&constraint, &pattern_env.var_mapping, true);
+            }
+            Ok(pattern_env)
+        }
+        Node::Tuple { elements } => {
+            let names: Vec<Name> = elements.iter().map(|e| pattern_name(e, constr)).collect();
+            let tuple = Expected::new(
+                pattern.pos,
+                &Type {
+                    name: Name::tuple(&names),
+                },
+            );
+            let constraint = Constraint::new("tuple pattern", subject, &tuple);
+            constr.add_constr_map(&constraint, &env.var_mapping, true);
+
+            elements
+                .iter()
+                .zip(names)
+                .try_fold(env.clone(), |env, (element, name)| {
+                    let subject = Expected::new(element.pos, &Type { name });
+                    constrain_pattern(element, &subject, &env, ctx, constr)
+                })
+        }
+        Node::List { elements } => {
+            let name = constr.temp_name();
+            let list_name = Name::from(&StringName::new(LIST, std::slice::from_ref(&name)));
+            let list = Expected::new(
+                pattern.pos,
+                &Type {
+                    name: list_name.clone(),
+                },
+            );
+            let constraint = Constraint::new("list pattern", subject, &list);
+            constr.add_constr_map(&constraint, &env.var_mapping, true);
+
+            elements
+                .iter()
+                .try_fold(env.clone(), |env, element| match &element.node {
+                    Node::PatternRest { id } if id.node == Node::Underscore => Ok(env),
+                    Node::PatternRest { id } => {
+                        id_from_var(id, &Some(list_name.clone()), &None, true, ctx, constr, &env)
+                    }
+                    _ => {
+                        let subject = Expected::new(element.pos, &Type { name: name.clone() });
+                        constrain_pattern(element, &subject, &env, ctx, constr)
+                    }
+                })
+        }
+        Node::PatternClass { ty, fields } => {
+            let class_name = StringName::try_from(ty)?;
+            let class = ctx.class(&class_name, ty.pos)?;
+            let class_ty = Expected::new(
+                ty.pos,
+                &Type {
+                    name: Name::from(&class_name),
+                },
+            );
+            let constraint = Constraint::new("class pattern", subject, &class_ty);
+            constr.add_constr_map(&constraint, &env.var_mapping, true);
+
+            fields
+                .iter()
+                .try_fold(env.clone(), |env, field| match &field.node {
+                    Node::PatternField { id, pattern } => {
+                        let Node::Id { lit } = &id.node else {
+                            let msg = format!("Expected field identifier, was {}", id.node);
+                            return Err(vec![TypeErr::new(id.pos, &msg)]);
+                        };
+
+                        let name = class.field(lit, id.pos)?.ty;
+                        let subject = Expected::new(pattern.pos, &Type { name });
+                        constrain_pattern(pattern, &subject, &env, ctx, constr)
+                    }
+                    other => {
+                        let msg = format!("Expected field pattern, was {other}");
+                        Err(vec![TypeErr::new(field.pos, &msg)])
+                    }
+                })
+        }
+        Node::PatternOr { patterns } => {
+            let vars = pattern_vars(pattern);
+            if let Some(other) = patterns.iter().find(|p| pattern_vars(p) != vars) {
+                let msg = "Alternatives of pattern must all bind the same variables";
+                return Err(vec![TypeErr::new(other.pos, msg)]);
+            }
+
+            patterns.iter().try_fold(env.clone(), |env, pattern| {
+                constrain_pattern(pattern, subject, &env, ctx, constr)
+            })
+        }
+        Node::Int { .. } | Node::Real { .. } | Node::Imaginary { .. } | Node::Str { .. } => {
+            constrain_literal_pattern(pattern, subject, env, ctx, constr)
+        }
+        Node::SubU { .. } | Node::PropertyCall { .. } => {
+            constrain_literal_pattern(pattern, subject, env, ctx, constr)
+        }
+        Node::Id { lit } if ["True", "False", "None"].contains(&lit.as_str()) => {
+            constrain_literal_pattern(pattern, subject, env, ctx, constr)
+        }
+        other => {
+            let msg = format!("Expected pattern, was {other}");
+            Err(vec![TypeErr::new(pattern.pos, &msg)])
+        }
+    }
+}
+
+fn constrain_literal_pattern(
+    pattern: &AST,
+    subject: &Expected,
+    env: &Environment,
+    ctx: &Context,
+    constr: &mut ConstrBuilder,
+) -> Constrained {
+    generate(pattern, &env.is_def_mode(false), ctx, constr)?;
+    let constraint = Constraint::new("literal pattern", subject, &Expected::from(pattern));
+    constr.add_constr_map(&constraint, &env.var_mapping, true);
+    Ok(env.clone())
+}
+
+/// Name of the value a pattern is matched against.
+///
+/// A tuple pattern gives a tuple of the names of its elements, so that nested tuple patterns are
+/// matched against the elements of nested tuples.
+fn pattern_name(pattern: &AST, constr: &mut ConstrBuilder) -> Name {
+    match &pattern.node {
+        Node::Tuple { elements } => {
+            let names: Vec<Name> = elements.iter().map(|e| pattern_name(e, constr)).collect();
+            Name::tuple(&names)
+        }
+        _ => constr.temp_name(),
+    }
+}
+
+/// Variables which are defined by a pattern.
+fn pattern_vars(pattern: &AST) -> HashSet<String> {
+    match &pattern.node {
+        Node::ExpressionType { expr, .. } | Node::PatternRest { id: expr } => match &expr.node {
+            Node::Id { lit } => HashSet::from([lit.clone()]),
+            _ => HashSet::new(),
+        },
+        Node::Tuple { elements } | Node::List { elements } => {
+            elements.iter().flat_map(pattern_vars).collect()
+        }
+        Node::PatternClass { fields, .. } => fields.iter().flat_map(pattern_vars).collect(),
+        Node::PatternField { pattern, .. } => pattern_vars(pattern),
+        Node::PatternOr { patterns } => patterns.first().map(pattern_vars).unwrap_or_default(),
+        _ => HashSet::new(),
+    }
+}
+
+/// True if an arm matches anything, meaning that later arms are never reached.
+fn is_irrefutable(case: &AST) -> bool {
+    match &case.node {
+        Node::Case {
+            cond, guard: None, ..
+        } => matches!(
+            cond.node,
+            Node::Underscore | Node::ExpressionType { ty: None, .. }
+        ),
+        _ => false,
     }
 }
+
+/// True if execution never continues after given AST, as it always returns, raises, or jumps.
+fn diverges(ast: &AST) -> bool {
+    match &ast.node {
+        Node::Return { .. } | Node::ReturnEmpty | Node::Raise { .. } => true,
+        Node::Break | Node::Continue => true,
+        Node::Block { statements } => statements.iter().any(diverges),
+        Node::IfElse {
+            then, el: Some(el), ..
+        } => diverges(then) && diverges(el),
+        Node::Match { cases, .. } => {
+            cases.iter().any(is_irrefutable)
+                && cases.iter().all(|case| match &case.node {
+                    Node::Case { body, .. } => diverges(body),
+                    _ => false,
+                })
+        }
+        _ => false,
+    }
+}
+
+/// What we learn about a variable, or a field of a variable, from a condition.
+enum Narrowing {
+    /// Variable is not None.
+    NotNone { var: String, pos: Position },
+    /// Variable is an instance of given type.
+    IsA {
+        var: String,
+        ty: Name,
+        pos: Position,
+    },
+    /// Field of variable is not None, where access is the access of the field in the condition.
+    FieldNotNone {
+        var: String,
+        field: String,
+        access: AST,
+    },
+    /// Field of variable is an instance of given type.
+    FieldIsA {
+        var: String,
+        field: String,
+        ty: Name,
+        pos: Position,
+    },
+}
+
+/// What we learn about variables if condition is true, or if it is false.
+///
+/// We learn something from comparisons with None and from instance checks of a variable, or of a
+/// field of a variable.
+/// These may be combined using `and` if true, `or` if false, and `not`.
+fn narrowings(cond: &AST, holds: bool) -> TypeResult<Vec<Narrowing>> {
+    Ok(match &cond.node {
+        Node::Neq { left, right } | Node::IsN { left, right } if holds => not_none(left, right),
+        Node::Eq { left, right } | Node::Is { left, right } if !holds => not_none(left, right),
+        Node::IsA { left, right } if holds => is_a(left, right)?,
+        Node::IsNA { left, right } if !holds => is_a(left, right)?,
+        Node::And { left, right } if holds => {
+            let mut narrowings = narrowings(left, holds)?;
+            narrowings.append(&mut self::narrowings(right, holds)?);
+            narrowings
+        }
+        Node::Or { left, right } if !holds => {
+            let mut narrowings = narrowings(left, holds)?;
+            narrowings.append(&mut self::narrowings(right, holds)?);
+            narrowings
+        }
+        Node::Not { expr } => narrowings(expr, !holds)?,
+        _ => vec![],
+    })
+}
+
+fn not_none(left: &AST, right: &AST) -> Vec<Narrowing> {
+    let is_none = |ast: &AST| matches!(&ast.node, Node::Id { lit } if lit == NONE);
+    let subject = match (is_none(left), is_none(right)) {
+        (false, true) => left,
+        (true, false) => right,
+        _ => return vec![],
+    };
+
+    match (&subject.node, field_access(subject)) {
+        (Node::Id { lit }, _) => vec![Narrowing::NotNone {
+            var: lit.clone(),
+            pos: left.pos.union(right.pos),
+        }],
+        (_, Some((var, field))) => vec![Narrowing::FieldNotNone {
+            var,
+            field,
+            access: subject.clone(),
+        }],
+        _ => vec![],
+    }
+}
+
+fn is_a(left: &AST, right: &AST) -> TypeResult<Vec<Narrowing>> {
+    Ok(match (&left.node, field_access(left)) {
+        (Node::Id { lit }, _) => vec![Narrowing::IsA {
+            var: lit.clone(),
+            ty: Name::try_from(right)?,
+            pos: left.pos.union(right.pos),
+        }],
+        (_, Some((var, field))) => vec![Narrowing::FieldIsA {
+            var,
+            field,
+            ty: Name::try_from(right)?,
+            pos: left.pos.union(right.pos),
+        }],
+        _ => vec![],
+    })
+}
+
+/// Variable and field if AST is an access of a field of a variable, such as `self.x`.
+fn field_access(ast: &AST) -> Option<(String, String)> {
+    let Node::PropertyCall { instance, property } = &ast.node else {
+        return None;
+    };
+    match (&instance.node, &property.node) {
+        (Node::Id { lit: var }, Node::Id { lit: field }) => Some((var.clone(), field.clone())),
+        _ => None,
+    }
+}
+
+/// Narrow the types of variables in environment.
+///
+/// A narrowed variable gets a fresh mapping, with the narrowed type.
+/// Only variables whose type is known during generation can be narrowed to a non-null type.
+fn narrow(narrowings: &[Narrowing], env: &Environment, constr: &mut ConstrBuilder) -> Environment {
+    let mut env = env.clone();
+    for narrowing in narrowings {
+        let (var, mutable, name, pos) = match narrowing {
+            Narrowing::FieldNotNone { var, field, access } => {
+                // Same as the access of the field in the property call of the condition
+                let Node::PropertyCall { instance, property } = &access.node else {
+                    continue;
+                };
+                let access = AST::new(
+                    instance.pos,
+                    Node::PropertyCall {
+                        instance: instance.clone(),
+                        property: property.clone(),
+                    },
+                );
+                let access = Expected::from(&access).map_exp(&env.var_mapping, &constr.var_mapping);
+                let non_null = NonNull {
+                    expected: Box::from(access),
+                };
+                let non_null = Expected::new(property.pos, &non_null);
+                env = narrow_field(var, field, &non_null, &env, constr);
+                continue;
+            }
+            Narrowing::FieldIsA {
+                var,
+                field,
+                ty,
+                pos,
+            } => {
+                let ty = Expected::new(*pos, &Type { name: ty.clone() });
+                env = narrow_field(var, field, &ty, &env, constr);
+                continue;
+            }
+            Narrowing::NotNone { var, pos } => match env.get_var_ty(var, &constr.var_mapping) {
+                Some((mutable, name)) if name.as_non_nullable() != name => {
+                    (var, mutable, name.as_non_nullable(), *pos)
+                }
+                _ => continue,
+            },
+            Narrowing::IsA { var, ty, pos } => match env.get_var(var, &constr.var_mapping) {
+                Some(expected) => {
+                    let mutable = expected.iter().any(|(mutable, _)| *mutable);
+                    (var, mutable, ty.clone(), *pos)
+                }
+                None => continue,
+            },
+        };
+        if name.is_empty() {
+            continue;
+        }
+
+        constr.insert_var(var);
+        let offset = *constr.var_mapping.get(var).expect("just inserted");
+        let ty = Expected::new(pos, &Type { name });
+        env = env.narrow_var(mutable, var, offset, &ty);
+
+        let var = AST::new(pos, Node::Id { lit: var.clone() });
+        constr.add("narrowed variable", &ty, &Expected::from(&var), &env);
+    }
+    env
+}
+
+/// Narrow the type of a field of a variable.
+///
+/// Only a field of a variable which is not mutable is narrowed, as it cannot be reassigned.
+fn narrow_field(
+    var: &str,
+    field: &str,
+    ty: &Expected,
+    env: &Environment,
+    constr: &mut ConstrBuilder,
+) -> Environment {
+    let var_exp = match env.get_var(var, &constr.var_mapping) {
+        Some(expected) if expected.iter().all(|(mutable, _)| !*mutable) => {
+            expected.into_iter().next()
+        }
+        _ => None,
+    };
+    let Some((_, var_exp)) = var_exp else {
+        return env.clone();
+    };
+
+    let var_ast = AST::new(
+        ty.pos,
+        Node::Id {
+            lit: String::from(var),
+        },
+    );
+    let outer = Expected::from(&var_ast).map_exp(&env.var_mapping, &constr.var_mapping);
+    let global = constr.var_mapping.clone();
+
+    constr.insert_var(var);
+    let offset = *constr.var_mapping.get(var).expect("just inserted");
+    let env = env.narrow_field((var, &var_exp), (field, ty), offset, &global);
+
+    // Outer variable already mapped, so map narrowed variable separately
+    let inner = Expected::from(&var_ast).map_exp(&env.var_mapping, &constr.var_mapping);
+    let narrowed = Constraint::new("narrowed variable", &outer, &inner);
+    constr.add_constr_map(&narrowed, &env.var_mapping, true);
+    env
+}
@@ -1,21 +1,24 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::convert::TryFrom;
-
-use itertools::enumerate;
+use std::ops::Deref;
 
 use crate::check::constrain::constraint::builder::ConstrBuilder;
 use crate::check::constrain::constraint::expected::Expect::*;
 use crate::check::constrain::constraint::expected::Expected;
 use crate::check::constrain::generate::env::Environment;
 use crate::check::constrain::generate::{generate, Constrained};
+use crate::check::context::arg::python::CLS;
 use crate::check::context::arg::SELF;
 use crate::check::context::clss::{Class, HasParent};
 use crate::check::context::field::Field;
+use crate::check::context::function;
+use crate::check::context::function::generic::{scope_generics, GenericFunction};
 use crate::check::context::function::python::INIT;
-use crate::check::context::{clss, Context, LookupClass};
+use crate::check::context::{clss, Context, LookupClass, LookupFunction};
 use crate::check::ident::Identifier;
+use crate::check::name::string_name::StringName;
 use crate::check::name::true_name::TrueName;
-use crate::check::name::{match_name, Name, Nullable, TupleCallable};
+use crate::check::name::{match_name, Any, Empty, Name, Nullable, TupleCallable};
 use crate::check::result::{TypeErr, TypeResult};
 use crate::common::position::Position;
 use crate::parse::ast::Node::Id;
@@ -27,6 +30,10 @@ pub fn gen_def(
     ctx: &Context,
     constr: &mut ConstrBuilder,
 ) -> Constrained {
+    if let Some(scoped) = scope_generics(ast) {
+        return gen_def(&scoped, env, ctx, constr);
+    }
+
     match &ast.node {
         Node::FunDef {
             args: fun_args,
@@ -34,8 +41,20 @@ pub fn gen_def(
             body,
             raises,
             id,
+            is_async,
+            decorators,
             ..
         } => {
+            if !decorators.is_empty() {
+                let fun = GenericFunction::try_from(ast)?;
+                let fun = match &env.class {
+                    Some(class) => fun.in_class(Some(class), false, ast.pos)?,
+                    None => fun,
+                };
+                let decorated = function::Function::try_from((&fun, &HashMap::new(), ast.pos))?;
+                constrain_decorators(decorators, &decorated.callable(), env, ctx, constr)?;
+            }
+
             let (class, non_nullable_class_vars) = match &id.node {
                 Id { lit } if *lit == INIT => {
                     if let Some(class) = &env.class {
@@ -66,7 +85,9 @@ pub fn gen_def(
 
             let body_env = constrain_args(fun_args, env, ctx, constr)?
                 .with_unassigned(non_nullable_class_vars)
-                .in_fun(true);
+                .with_unassigned_vars(&HashMap::new())
+                .in_fun(true)
+                .in_async(*is_async);
 
             let (raises, errs): (Vec<(Position, _)>, Vec<_>) = raises
                 .iter()
@@ -92,8 +113,27 @@ pub fn gen_def(
             let raises = raises.into_iter().map(|(_, r)| r.unwrap()).collect();
             let body_env = body_env.raises_caught(&raises);
 
+            let body_env = body_env.yield_type(&None);
+            let class_body;
+            let body = match (&env.class, body) {
+                (Some(class), Some(body)) if is_class_method(fun_args) => {
+                    class_body = class_method_body(body, class);
+                    Some(&class_body)
+                }
+                (_, body) => body.as_deref(),
+            };
             let body_env = if let Some(body) = body {
-                if let Some(ret_ty) = ret_ty {
+                let is_generator = body.node.contains_yield();
+                if is_generator && *is_async {
+                    let msg = "Cannot yield in async function";
+                    return Err(vec![TypeErr::new(id.pos, msg)]);
+                }
+
+                if let (Some(ret_ty), true) = (ret_ty, is_generator) {
+                    let name = function::yielded(&Name::try_from(ret_ty)?);
+                    let yield_ty_exp = Some(Expected::new(ret_ty.pos, &Type { name }));
+                    generate(body, &body_env.yield_type(&yield_ty_exp), ctx, constr)?
+                } else if let Some(ret_ty) = ret_ty {
                     let name = Name::try_from(ret_ty)?;
                     let ret_ty_raises_exp = Expected::new(body.pos, &Type { name: name.clone() });
                     constr.add(
@@ -129,6 +169,17 @@ pub fn gen_def(
 
             Ok(env.clone())
         }
+        Node::Property { getter, setter, .. } => {
+            if env.class.is_none() {
+                return Err(vec![TypeErr::new(ast.pos, "Property must be in class")]);
+            }
+
+            gen_def(getter, env, ctx, constr)?;
+            if let Some(setter) = setter {
+                gen_def(setter, env, ctx, constr)?;
+            }
+            Ok(env.clone())
+        }
         Node::FunArg { .. } => Err(vec![TypeErr::new(
             ast.pos,
             "Function argument cannot be top level",
@@ -141,18 +192,118 @@ pub fn gen_def(
             expr: expression,
             ..
         } => {
-            if let Some(ty) = ty {
-                let name = Name::try_from(ty)?;
-                id_from_var(var, &Some(name), expression, *mutable, ctx, constr, env)
+            let name = if let Some(ty) = ty {
+                Some(Name::try_from(ty)?)
             } else {
-                id_from_var(var, &None, expression, *mutable, ctx, constr, env)
-            }
+                None
+            };
+            let def_env = id_from_var(var, &name, expression, *mutable, ctx, constr, env)?;
+
+            // Class fields are checked separately, see unassigned
+            let is_local = env.in_fun || env.class.is_none();
+            Ok(match (&var.node, expression) {
+                (Id { lit }, None) if is_local && !name.is_some_and(|n| n.is_nullable()) => {
+                    def_env.declare_unassigned(lit, var.pos)
+                }
+                (Id { lit }, _) => def_env.assigned_to_var(lit),
+                _ => def_env,
+            })
         }
 
         _ => Err(vec![TypeErr::new(ast.pos, "Expected definition")]),
     }
 }
 
+/// Whether the first argument of a function is `cls`.
+fn is_class_method(args: &[AST]) -> bool {
+    args.first().is_some_and(|arg| match &arg.node {
+        Node::FunArg { var, .. } => matches!(&var.node, Id { lit } if lit == CLS),
+        _ => false,
+    })
+}
+
+/// Body of a class method, in which `cls` is the class itself.
+fn class_method_body(body: &AST, class: &StringName) -> AST {
+    body.map(&|node| match node {
+        Id { lit } if lit == CLS => Id {
+            lit: class.name.clone(),
+        },
+        other => other.clone(),
+    })
+}
+
+/// Constrain decorators of a definition, whose type when used as a value is given.
+///
+/// Each decorator must be a callable which takes the decorated definition and returns something
+/// of the same type.
+/// An untyped argument or return type of a decorator is treated as [Any](clss::ANY), as is an
+/// imported decorator, of which the signature is unknown.
+pub fn constrain_decorators(
+    decorators: &[AST],
+    decorated: &Name,
+    env: &Environment,
+    ctx: &Context,
+    constr: &mut ConstrBuilder,
+) -> Constrained<()> {
+    for decorator in decorators {
+        match &decorator.node {
+            Id { lit } if env.get_var(lit, &constr.var_mapping).is_none() => {
+                let name = StringName::from(lit.as_str());
+                let fun = ctx.function(&name, decorator.pos)?;
+                let Some(arg) = fun.arguments.first() else {
+                    let is_import = !ctx.functions.iter().any(|f| f.name == name)
+                        && ctx.class(&name, decorator.pos)?.is_opaque();
+                    if is_import {
+                        continue;
+                    }
+                    let msg = format!("Decorator {lit} must take the decorated definition");
+                    return Err(vec![TypeErr::new(decorator.pos, &msg)]);
+                };
+                if fun.arguments.iter().skip(1).any(|a| !a.has_default) {
+                    let msg = format!("Decorator {lit} must take only the decorated definition");
+                    return Err(vec![TypeErr::new(decorator.pos, &msg)]);
+                }
+
+                let decorated = Expected::new(
+                    decorator.pos,
+                    &Type {
+                        name: decorated.clone(),
+                    },
+                );
+                if let Some(arg_ty) = &arg.ty {
+                    let arg_ty = Expected::new(
+                        decorator.pos,
+                        &Type {
+                            name: arg_ty.clone(),
+                        },
+                    );
+                    constr.add("decorator argument", &arg_ty, &decorated, env);
+                }
+                if !fun.ret_ty.is_empty() && fun.ret_ty != Name::any() {
+                    let ret_ty = Expected::new(decorator.pos, &Type { name: fun.ret_ty });
+                    constr.add("decorator", &decorated, &ret_ty, env);
+                }
+            }
+            _ => {
+                generate(decorator, env, ctx, constr)?;
+                let arg = Expected::new(
+                    decorator.pos,
+                    &Type {
+                        name: decorated.clone(),
+                    },
+                );
+                let call = Function {
+                    name: StringName::try_from(decorator).unwrap_or_else(|_| StringName::empty()),
+                    args: vec![arg],
+                };
+                let call = Expected::new(decorator.pos, &call);
+                constr.add("decorator", &call, &Expected::from(decorator), env);
+            }
+        }
+    }
+    Ok(())
+}
+
 pub fn constrain_args(
     args: &[AST],
     env: &Environment,
@@ -171,12 +322,17 @@ pub fn constrain_args(
                 default,
                 ..
             } => {
-                if var.node == Node::new_self() {
+                // Outside a class, cls may be given a type like any other argument
+                let special = match &var.node {
+                    Id { lit } if lit == SELF || (lit == CLS && ty.is_none()) => Some(lit),
+                    _ => None,
+                };
+                if let Some(lit) = special {
                     let class_name = &env.class.clone().ok_or_else(|| {
-                        TypeErr::new(var.pos, &format!("{SELF} cannot be outside class"))
+                        TypeErr::new(var.pos, &format!("{lit} cannot be outside class"))
                     })?;
                     if default.is_some() {
-                        let msg = format!("{SELF} cannot have default argument");
+                        let msg = format!("{lit} cannot have default argument");
                         return Err(vec![TypeErr::new(arg.pos, &msg)]);
                     }
 
@@ -231,6 +387,15 @@ pub fn id_from_var(
                 constr.insert_var(&f_name);
                 let ty = Expected::new(var.pos, &Type { name: name.clone() });
                 env = env.insert_var(mutable && f_mut, &f_name, &ty, &constr.var_mapping);
+                if identifier.is_tuple() {
+                    let element = AST::new(var.pos, Id { lit: f_name });
+                    constr.add(
+                        "tuple element with type",
+                        &Expected::from(&element),
+                        &ty,
+                        &env,
+                    );
+                }
             }
 
             let ty_exp = Expected::new(var.pos, &Type { name: ty.clone() });
@@ -252,6 +417,15 @@ pub fn id_from_var(
                 constr.insert_var(&f_name);
                 let ty = Expected::new(var.pos, &Type { name: name.clone() });
                 env = env.insert_var(mutable && f_mut, &f_name, &ty, &constr.var_mapping);
+                if identifier.is_tuple() {
+                    let element = AST::new(var.pos, Id { lit: f_name });
+                    constr.add(
+                        "tuple element with type",
+                        &Expected::from(&element),
+                        &ty,
+                        &env,
+                    );
+                }
             }
 
             let ty_exp = Expected::new(var.pos, &Type { name: ty.clone() });
@@ -263,68 +437,16 @@ pub fn id_from_var(
             );
         }
         (None, Some(expr)) => {
-            let mut temp_names = vec![];
-            let fields = identifier.fields(var.pos)?;
-            for (f_mut, name) in &fields {
-                let temp_name = constr.temp_name();
-                temp_names.push(temp_name.clone());
-
-                constr.insert_var(name);
-                let ty = Expected::new(
-                    var.pos,
-                    &Type {
-                        name: temp_name.clone(),
-                    },
-                );
-                env = env.insert_var(mutable && *f_mut, name, &ty, &constr.var_mapping);
-
-                let var = AST::new(var.pos, Id { lit: name.clone() });
-                constr.add(
-                    "variable with only expression",
-                    &Expected::from(&var),
-                    &ty,
-                    &env,
-                );
-            }
-
-            let exp_expr = if temp_names.len() > 1 {
-                // if tuple literal, deconstruct elements in generate stage
-                if let Node::Tuple { elements } = &expr.node {
-                    if elements.len() == temp_names.len() {
-                        for (i, (expr, ty)) in enumerate(elements.iter().zip(&temp_names)) {
-                            let expr_exp = Expected::from(expr);
-                            let expr_ty = Expected::new(expr.pos, &Type { name: ty.clone() });
-
-                            let msg = format!("tuple literal element {i}");
-                            constr.add(&msg, &expr_ty, &expr_exp, &env);
-                        }
-                    } else {
-                        let msg = format!(
-                            "Expected tuple of {} elements, was {}",
-                            temp_names.len(),
-                            elements.len()
-                        );
-                        return Err(vec![TypeErr::new(expr.pos, &msg)]);
-                    }
-                }
-
-                Expected::new(
-                    expr.pos,
-                    &Type {
-                        name: Name::tuple(&temp_names),
-                    },
-                )
-            } else if let Some(first) = temp_names.first() {
-                Expected::new(
-                    expr.pos,
-                    &Type {
-                        name: first.clone(),
-                    },
-                )
+            // if tuple literal, deconstruct elements in generate stage
+            let element = if identifier.is_tuple() {
+                Some(expr.deref())
             } else {
-                panic!("cannot have empty identifier")
+                None
             };
+            let (name, var_env) = fresh_vars(var, element, mutable, constr, &env)?;
+            env = var_env;
 
+            let exp_expr = Expected::new(expr.pos, &Type { name });
             constr.add(
                 "variable with only expression",
                 &Expected::from(var),
@@ -349,3 +471,75 @@ pub fn id_from_var(
 
     Ok(env)
 }
+
+/// Insert each variable of a definition or loop target, which may be a tuple, with a fresh type.
+///
+/// Returns the type of the variable as a whole, which is a tuple if the variable is a tuple.
+/// If given, the expression is the tuple literal, or element thereof, which is assigned to the
+/// variable, which is deconstructed so that each element is constrained separately.
+pub fn fresh_vars(
+    var: &AST,
+    expr: Option<&AST>,
+    mutable: bool,
+    constr: &mut ConstrBuilder,
+    env: &Environment,
+) -> TypeResult<(Name, Environment)> {
+    match &var.node {
+        Node::ExpressionType {
+            expr: var,
+            mutable: var_mut,
+            ..
+        } => fresh_vars(var, expr, mutable && *var_mut, constr, env),
+        Node::Id { lit } => {
+            let temp_name = constr.temp_name();
+            constr.insert_var(lit);
+            let ty = Expected::new(
+                var.pos,
+                &Type {
+                    name: temp_name.clone(),
+                },
+            );
+            let env = env.insert_var(mutable, lit, &ty, &constr.var_mapping);
+
+            constr.add(
+                "variable with only expression",
+                &Expected::from(var),
+                &ty,
+                &env,
+            );
+            if let Some(expr) = expr {
+                let msg = format!("tuple literal element {lit}");
+                constr.add(&msg, &ty, &Expected::from(expr), &env);
+            }
+            Ok((temp_name, env))
+        }
+        Node::Tuple { elements: vars } => {
+            let elements: Vec<Option<&AST>> = match expr.map(|expr| (expr.pos, &expr.node)) {
+                Some((_, Node::Tuple { elements })) if elements.len() == vars.len() => {
+                    elements.iter().map(Some).collect()
+                }
+                Some((pos, Node::Tuple { elements })) => {
+                    let msg = format!(
+                        "Expected tuple of {} elements, was {}",
+                        vars.len(),
+                        elements.len()
+                    );
+                    return Err(vec![TypeErr::new(pos, &msg)]);
+                }
+                _ => vec![None; vars.len()],
+            };
+
+            let (mut names, mut env) = (vec![], env.clone());
+            for (var, element) in vars.iter().zip(elements) {
+                let (name, element_env) = fresh_vars(var, element, mutable, constr, &env)?;
+                names.push(name);
+                env = element_env;
+            }
+            Ok((Name::tuple(&names), env))
+        }
+        _ => {
+            let msg = format!("Expected id or tuple of id's, was {}", var.node);
+            Err(vec![TypeErr::new(var.pos, &msg)])
+        }
+    }
+}
@@ -1,27 +1,37 @@
 use std::collections::{HashMap, HashSet};
 
 use crate::check::constrain::constraint::builder::{format_var_map, VarMapping};
+use crate::check::constrain::constraint::expected::Expect::Type;
 use crate::check::constrain::constraint::expected::Expected;
 use crate::check::name::string_name::StringName;
 use crate::check::name::true_name::TrueName;
+use crate::check::name::{ContainsTemp, Name};
+use crate::common::position::Position;
+use crate::common::result::Cause;
 
 #[derive(Clone, Debug, Default)]
 pub struct Environment {
     pub in_loop: bool,
     pub in_fun: bool,
+    pub in_async: bool,
     pub is_expr: bool,
     pub is_def_mode: bool,
     pub is_destruct_mode: bool,
     pub return_type: Option<Expected>,
+    pub yield_type: Option<Expected>,
 
     pub raises_caught: HashSet<TrueName>,
 
     pub class: Option<StringName>,
 
     pub unassigned: HashSet<String>,
+    pub unassigned_vars: HashMap<String, Vec<Cause>>,
 
     pub vars: HashMap<String, HashSet<(bool, Expected)>>,
     pub var_mapping: VarMapping,
+    pub narrowed: HashMap<String, Option<usize>>,
+    /// Narrowed fields, by the mapped name of the variable which is not mutable and field name.
+    pub narrowed_fields: HashMap<(String, String), Expected>,
 }
 
 impl Environment {
@@ -40,6 +50,14 @@ impl Environment {
         }
     }
 
+    /// Specify whether we are in the body of an async function.
+    pub fn in_async(&self, in_async: bool) -> Environment {
+        Environment {
+            in_async,
+            ..self.clone()
+        }
+    }
+
     /// Sets environment into define mode.
     ///
     /// Causes all identifiers to be treated as definitions.
@@ -137,6 +155,16 @@ impl Environment {
         }
     }
 
+    /// Specify the type of values yielded in a function body.
+    ///
+    /// [None] if the function is not a generator.
+    pub fn yield_type(&self, yield_type: &Option<Expected>) -> Environment {
+        Environment {
+            yield_type: yield_type.clone(),
+            ..self.clone()
+        }
+    }
+
     /// Gets a variable.
     ///
     /// Is Some, Vector wil usually contain only one expected.
@@ -153,15 +181,116 @@ impl Environment {
         var: &str,
         var_mapping: &VarMapping,
     ) -> Option<HashSet<(bool, Expected)>> {
-        let var_name = if let Some(offset) = self.var_mapping.get(var) {
+        self.vars.get(&self.mapped_var(var, var_mapping)).cloned()
+    }
+
+    fn mapped_var(&self, var: &str, var_mapping: &VarMapping) -> String {
+        if let Some(offset) = self.var_mapping.get(var) {
             format_var_map(var, offset)
         } else if let Some(offset) = var_mapping.get(var) {
             format_var_map(var, offset)
         } else {
             String::from(var)
+        }
+    }
+
+    /// Narrow the type of a field of a variable which is not mutable.
+    ///
+    /// The variable itself is narrowed to the given offset, so that constraints on the narrowed
+    /// field do not leak to the field outside the narrowed scope.
+    /// Fields of the variable which were narrowed before remain narrowed.
+    pub fn narrow_field(
+        &self,
+        (var, var_expect): (&str, &Expected),
+        (field, field_expect): (&str, &Expected),
+        offset: usize,
+        var_mapping: &VarMapping,
+    ) -> Self {
+        let old_var = self.mapped_var(var, var_mapping);
+        let new_var = format_var_map(var, &offset);
+        let mut narrowed_fields: HashMap<(String, String), Expected> = self
+            .narrowed_fields
+            .iter()
+            .map(|((var, field), expect)| match var {
+                var if *var == old_var => ((new_var.clone(), field.clone()), expect.clone()),
+                _ => ((var.clone(), field.clone()), expect.clone()),
+            })
+            .collect();
+        narrowed_fields.insert((new_var, String::from(field)), field_expect.clone());
+
+        Environment {
+            narrowed_fields,
+            ..self.narrow_var(false, var, offset, var_expect)
+        }
+    }
+
+    /// Narrowed type of a field of a variable, if any.
+    pub fn get_narrowed_field(
+        &self,
+        var: &str,
+        field: &str,
+        var_mapping: &VarMapping,
+    ) -> Option<&Expected> {
+        let key = (self.mapped_var(var, var_mapping), String::from(field));
+        self.narrowed_fields.get(&key)
+    }
+
+    /// Gets the type a variable was defined with, and whether it's mutable.
+    ///
+    /// Is None if not known during generation, for instance if the type must still be inferred.
+    pub fn get_var_ty(&self, var: &str, var_mapping: &VarMapping) -> Option<(bool, Name)> {
+        let expected = self.get_var(var, var_mapping)?;
+        if expected.len() != 1 {
+            return None;
+        }
+
+        let (mutable, expected) = expected.into_iter().next()?;
+        match expected.expect {
+            Type { name } if !name.contains_temp() => Some((mutable, name)),
+            _ => None,
+        }
+    }
+
+    /// Narrow the type of a variable.
+    ///
+    /// The variable is mapped to the given offset, which should be fresh, so constraints on the
+    /// narrowed variable do not leak to the variable outside the narrowed scope.
+    /// The original mapping is kept so we can widen the variable again.
+    pub fn narrow_var(&self, mutable: bool, var: &str, offset: usize, expect: &Expected) -> Self {
+        let mut vars = self.vars.clone();
+        let expected_set = HashSet::from([(mutable, expect.clone())]);
+        vars.insert(format_var_map(var, &offset), expected_set);
+
+        let mut narrowed = self.narrowed.clone();
+        let original = self.var_mapping.get(var).copied();
+        narrowed.entry(String::from(var)).or_insert(original);
+        Environment {
+            vars,
+            narrowed,
+            ..self.override_mapping(var, offset)
+        }
+    }
+
+    /// Undo narrowing of a variable, for instance when it is reassigned.
+    ///
+    /// If not narrowed, then nothing happens.
+    pub fn widen_var(&self, var: &str) -> Self {
+        let mut narrowed = self.narrowed.clone();
+        let Some(original) = narrowed.remove(var) else {
+            return self.clone();
         };
 
-        self.vars.get(&var_name).cloned()
+        let mut var_mapping = self.var_mapping.clone();
+        if let Some(original) = original {
+            var_mapping.insert(String::from(var), original);
+        } else {
+            var_mapping.remove(var);
+        }
+        Environment {
+            narrowed,
+            var_mapping,
+            ..self.clone()
+        }
     }
 
     pub fn remove_var(&self, var: &str) -> Self {
@@ -194,6 +323,67 @@ impl Environment {
         }
     }
 
+    /// Denote that a local variable was declared without a value.
+    ///
+    /// The variable must then be assigned to on every path before it is used.
+    pub fn declare_unassigned(&self, var: &str, pos: Position) -> Environment {
+        let mut unassigned_vars = self.unassigned_vars.clone();
+        let cause = Cause::new(&format!("'{var}' declared without a value"), pos);
+        unassigned_vars.insert(String::from(var), vec![cause]);
+        Environment {
+            unassigned_vars,
+            ..self.clone()
+        }
+    }
+
+    /// Denote that a local variable was assigned to.
+    ///
+    /// If not in environment, then nothing happens.
+    pub fn assigned_to_var(&self, var: &str) -> Environment {
+        let mut unassigned_vars = self.unassigned_vars.clone();
+        unassigned_vars.remove(var);
+        Environment {
+            unassigned_vars,
+            ..self.clone()
+        }
+    }
+
+    pub fn with_unassigned_vars(&self, unassigned_vars: &HashMap<String, Vec<Cause>>) -> Self {
+        Environment {
+            unassigned_vars: unassigned_vars.clone(),
+            ..self.clone()
+        }
+    }
+
+    /// Join the environments of the branches which continue after a branching construct.
+    ///
+    /// A local variable is unassigned after the join if it is unassigned in any branch.
+    /// If another branch did assign to it, the cause of the branch is appended to its path, so we
+    /// can later report the path which leaves it unassigned.
+    /// If no branch continues, the code after the join is unreachable.
+    pub fn join_branches(&self, branches: &[(Environment, Cause)]) -> Environment {
+        let mut unassigned_vars = HashMap::new();
+        for var in self.unassigned_vars.keys() {
+            let unassigned_in: Vec<&(Environment, Cause)> = branches
+                .iter()
+                .filter(|(env, _)| env.unassigned_vars.contains_key(var))
+                .collect();
+
+            if let Some((env, cause)) = unassigned_in.first() {
+                let mut path = env.unassigned_vars[var].clone();
+                if unassigned_in.len() < branches.len() {
+                    path.push(cause.clone());
+                }
+                unassigned_vars.insert(var.clone(), path);
+            }
+        }
+
+        Environment {
+            unassigned_vars,
+            ..self.clone()
+        }
+    }
+
     /// Union with unassigned of other.
     pub fn union(&self, other: &Environment) -> Environment {
         let unassigned = self.unassigned.union(&other.unassigned);
@@ -217,7 +407,46 @@ impl Environment {
 mod tests {
     use std::collections::HashSet;
 
+    use crate::check::constrain::constraint::builder::VarMapping;
+    use crate::check::constrain::constraint::expected::Expected;
     use crate::check::constrain::generate::env::Environment;
+    use crate::common::position::Position;
+    use crate::common::result::Cause;
+
+    #[test]
+    fn widen_restores_mapping() {
+        let any = Expected::any(Position::invisible());
+        let env = Environment::default().override_mapping("a", 1);
+
+        let narrowed = env
+            .narrow_var(false, "a", 3, &any)
+            .narrow_var(false, "a", 4, &any);
+        assert_eq!(narrowed.var_mapping.get("a"), Some(&4));
+        assert!(narrowed.get_var("a", &VarMapping::new()).is_some());
+
+        let widened = narrowed.widen_var("a");
+        assert_eq!(widened.var_mapping.get("a"), Some(&1));
+        assert!(widened.narrowed.is_empty());
+    }
+
+    #[test]
+    fn narrow_field_keeps_narrowed_fields() {
+        let any = Expected::any(Position::invisible());
+        let none = Expected::none(Position::invisible());
+        let global = VarMapping::new();
+        let env = Environment::default().insert_var(false, "a", &any, &global);
+
+        let narrowed = env
+            .narrow_field(("a", &any), ("x", &none), 1, &global)
+            .narrow_field(("a", &any), ("y", &any), 2, &global);
+        assert_eq!(narrowed.var_mapping.get("a"), Some(&2));
+        assert_eq!(narrowed.get_narrowed_field("a", "x", &global), Some(&none));
+        assert_eq!(narrowed.get_narrowed_field("a", "y", &global), Some(&any));
+        assert!(env.get_narrowed_field("a", "x", &global).is_none());
+
+        let widened = narrowed.widen_var("a");
+        assert!(widened.get_narrowed_field("a", "x", &global).is_none());
+    }
 
     #[test]
     fn union_unassigned() {
@@ -234,4 +463,28 @@ mod tests {
         assert!(env3.unassigned.contains(&String::from("a")));
         assert_eq!(env3.unassigned.len(), 1);
     }
+
+    #[test]
+    fn join_branches_unassigned_in_one() {
+        let env = Environment::default().declare_unassigned("a", Position::invisible());
+        let then_env = env.assigned_to_var("a");
+        let else_cause = Cause::new("else branch", Position::invisible());
+
+        let joined = env.join_branches(&[
+            (
+                then_env.clone(),
+                Cause::new("then branch", Position::invisible()),
+            ),
+            (env.clone(), else_cause.clone()),
+        ]);
+        let path = joined.unassigned_vars.get("a").expect("a still unassigned");
+        assert_eq!(path.len(), 2);
+        assert_eq!(path.last(), Some(&else_cause));
+
+        let joined = env.join_branches(&[
+            (then_env.clone(), else_cause.clone()),
+            (then_env, else_cause),
+        ]);
+        assert!(joined.unassigned_vars.is_empty());
+    }
 }
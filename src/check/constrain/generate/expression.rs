@@ -1,6 +1,8 @@
 use std::convert::TryFrom;
+use std::ops::Deref;
 
 use crate::check::constrain::constraint::builder::ConstrBuilder;
+use crate::check::constrain::constraint::expected::Expect::{Access, Function, Type};
 use crate::check::constrain::constraint::expected::Expected;
 use crate::check::constrain::constraint::Constraint;
 use crate::check::constrain::generate::definition::{constrain_args, id_from_var};
@@ -8,9 +10,13 @@ use crate::check::constrain::generate::env::Environment;
 use crate::check::constrain::generate::operation::gen_primitive;
 use crate::check::constrain::generate::{generate, Constrained};
 use crate::check::context::clss::BOOL;
+use crate::check::context::function::python::AWAIT;
 use crate::check::context::Context;
-use crate::check::name::Name;
+use crate::check::ident::Identifier;
+use crate::check::name::string_name::StringName;
+use crate::check::name::{match_name, Name, Nullable};
 use crate::check::result::TypeErr;
+use crate::common::result::WithCause;
 use crate::parse::ast::{Node, OptAST, AST};
 
 pub fn gen_expr(
@@ -20,25 +26,51 @@ pub fn gen_expr(
     constr: &mut ConstrBuilder,
 ) -> Constrained {
     match &ast.node {
-        Node::AnonFun { args, body } => {
-            let anon_env = constrain_args(args, &env.is_def_mode(true), ctx, constr)?;
-            generate(body, &anon_env, ctx, constr)?;
+        Node::AnonFun { args, body } => gen_anon_fun(args, body, &[], env, ctx, constr),
+        Node::Await { expr } if env.in_async => {
+            generate(expr, env, ctx, constr)?;
+            let exp_expr = Expected::from(expr);
+            let fun = Function {
+                name: StringName::from(AWAIT),
+                args: vec![exp_expr.clone()],
+            };
+            let awaited = Access {
+                entity: Box::from(exp_expr),
+                name: Box::from(Expected::new(expr.pos, &fun)),
+            };
+
+            let awaited = Expected::new(expr.pos, &awaited);
+            constr.add("await", &Expected::from(ast), &awaited, env);
             Ok(env.clone())
         }
+        Node::Await { .. } => Err(vec![TypeErr::new(
+            ast.pos,
+            "Cannot await outside async function",
+        )]),
         Node::ExpressionType { expr, mutable, ty } => {
             match_id(expr, ty, *mutable, env, ctx, constr)
         }
         Node::Id { .. } => match_id(ast, &None, false, env, ctx, constr),
         Node::Question { left, right } => {
+            generate(left, env, ctx, constr)?;
             constr.add(
                 "question",
                 &Expected::from(left),
                 &Expected::none(left.pos),
                 env,
             );
-
-            generate(left, env, ctx, constr)?;
             generate(right, env, ctx, constr)?;
+
+            // Default used if None, so result is never None if default is not None
+            let (ast_exp, right_exp) = (Expected::from(ast), Expected::from(right));
+            constr.add("question default", &ast_exp, &right_exp, env);
+            if let Node::Id { lit } = &left.node {
+                if let Some((_, name)) = env.get_var_ty(lit, &constr.var_mapping) {
+                    let name = name.as_non_nullable();
+                    let left_exp = Expected::new(left.pos, &Type { name });
+                    constr.add("question", &ast_exp, &left_exp, env);
+                }
+            }
             Ok(env.clone())
         }
         Node::Pass => {
@@ -54,6 +86,41 @@ pub fn gen_expr(
     }
 }
 
+/// Generate constraints for an anonymous function.
+///
+/// An argument without a type takes the matching expected type, if given.
+/// If the argument is a tuple, each element takes the matching element of the expected tuple.
+pub fn gen_anon_fun(
+    args: &[AST],
+    body: &AST,
+    expected: &[Name],
+    env: &Environment,
+    ctx: &Context,
+    constr: &mut ConstrBuilder,
+) -> Constrained {
+    let anon_env = constrain_args(args, &env.is_def_mode(true), ctx, constr)?;
+    for (arg, name) in args.iter().zip(expected) {
+        let Node::FunArg { var, ty: None, .. } = &arg.node else {
+            continue;
+        };
+        let identifier = Identifier::try_from(var.deref())?;
+        for (var, (_, name)) in match_name(&identifier, name, var.pos)? {
+            let var = AST::new(arg.pos, Node::Id { lit: var });
+            let ty = Expected::new(arg.pos, &Type { name });
+            constr.add(
+                "anonymous function argument",
+                &Expected::from(&var),
+                &ty,
+                &anon_env,
+            );
+        }
+    }
+
+    let anon_env = anon_env.in_async(false).yield_type(&None);
+    generate(body, &anon_env, ctx, constr)?;
+    Ok(env.clone())
+}
+
 fn match_id(
     ast: &AST,
     ty: &OptAST,
@@ -84,6 +151,13 @@ fn match_id(
             } else if env.is_destruct_mode {
                 Ok(env.remove_var(lit))
             } else if env.get_var(lit, &constr.var_mapping).is_some() {
+                if let Some(path) = env.unassigned_vars.get(lit) {
+                    let msg = format!("Variable '{lit}' might not have been assigned to");
+                    let err = path.iter().fold(TypeErr::new(ast.pos, &msg), |err, cause| {
+                        err.with_cause(&cause.msg, cause.pos)
+                    });
+                    return Err(vec![err]);
+                }
                 Ok(env.clone())
             } else {
                 Err(vec![TypeErr::new(
@@ -1,6 +1,7 @@
 use env::Environment;
 
 use crate::check::constrain::constraint::builder::ConstrBuilder;
+use crate::check::constrain::constraint::expected::{Expect, Expected};
 use crate::check::constrain::constraint::iterator::Constraints;
 use crate::check::constrain::generate::call::gen_call;
 use crate::check::constrain::generate::class::gen_class;
@@ -14,6 +15,8 @@ use crate::check::constrain::generate::statement::gen_stmt;
 use crate::check::constrain::generate::ty::gen_ty;
 use crate::check::context::Context;
 use crate::check::result::TypeErr;
+use crate::check::Defined;
+use crate::common::position::Position;
 use crate::parse::ast::Node::*;
 use crate::parse::ast::AST;
 
@@ -32,10 +35,20 @@ pub(super) mod env;
 
 pub type Constrained<T = Environment> = Result<T, Vec<TypeErr>>;
 
-pub fn gen_all(ast: &AST, ctx: &Context) -> Constrained<Vec<Constraints>> {
+pub fn gen_all(ast: &AST, defined: &[Defined], ctx: &Context) -> Constrained<Vec<Constraints>> {
     let mut builder = ConstrBuilder::new();
 
-    generate(ast, &Environment::default(), ctx, &mut builder)?;
+    let mut env = Environment::default();
+    for (mutable, var, ty) in defined {
+        let ty = Expected::new(Position::invisible(), &Expect::Type { name: ty.clone() });
+        builder.insert_var(var);
+        env = env.insert_var(*mutable, var, &ty, &builder.var_mapping);
+
+        let var = AST::new(Position::invisible(), Id { lit: var.clone() });
+        builder.add("defined variable", &Expected::from(&var), &ty, &env);
+    }
+
+    generate(ast, &env, ctx, &mut builder)?;
     Ok(builder.all_constr())
 }
 
@@ -52,9 +65,12 @@ pub fn generate(
         TypeAlias { .. } | Condition { .. } => gen_class(ast, env, ctx, constr),
 
         VariableDef { .. } | FunDef { .. } | FunArg { .. } => gen_def(ast, env, ctx, constr),
+        Property { .. } => gen_def(ast, env, ctx, constr),
 
         Reassign { .. } => gen_call(ast, env, ctx, constr),
-        FunctionCall { .. } | PropertyCall { .. } => gen_call(ast, env, ctx, constr),
+        FunctionCall { .. } | PropertyCall { .. } | SafeCall { .. } => {
+            gen_call(ast, env, ctx, constr)
+        }
         Index { .. } => gen_call(ast, env, ctx, constr),
 
         TypeTup { .. } | TypeUnion { .. } | Type { .. } => gen_ty(ast, env, ctx, constr),
@@ -62,7 +78,7 @@ pub fn generate(
         QuestionOp { .. } => gen_ty(ast, env, ctx, constr),
 
         ExpressionType { .. } | Id { .. } | Question { .. } => gen_expr(ast, env, ctx, constr),
-        AnonFun { .. } => gen_expr(ast, env, ctx, constr),
+        AnonFun { .. } | Await { .. } => gen_expr(ast, env, ctx, constr),
         Pass => gen_expr(ast, env, ctx, constr),
 
         With { .. } => gen_resources(ast, env, ctx, constr),
@@ -73,7 +89,7 @@ pub fn generate(
         Set { .. } | List { .. } | Tuple { .. } | Dict { .. } => gen_coll(ast, env, ctx, constr),
 
         Range { .. } | Slice { .. } => gen_op(ast, env, ctx, constr),
-        Real { .. } | Int { .. } | ENum { .. } => gen_op(ast, env, ctx, constr),
+        Real { .. } | Int { .. } | Imaginary { .. } => gen_op(ast, env, ctx, constr),
         Str { .. } => gen_op(ast, env, ctx, constr),
 
         In { .. } => gen_op(ast, env, ctx, constr),
@@ -95,9 +111,12 @@ pub fn generate(
 
         IfElse { .. } => gen_flow(ast, env, ctx, constr),
         Match { .. } | Handle { .. } | Case { .. } => gen_flow(ast, env, ctx, constr),
+        PatternOr { .. } | PatternClass { .. } => gen_flow(ast, env, ctx, constr),
+        PatternField { .. } | PatternRest { .. } => gen_flow(ast, env, ctx, constr),
         For { .. } | While { .. } | Break | Continue => gen_flow(ast, env, ctx, constr),
 
         Return { .. } | ReturnEmpty => gen_stmt(ast, env, ctx, constr),
+        Yield { .. } | YieldFrom { .. } => gen_stmt(ast, env, ctx, constr),
         Raise { .. } => gen_stmt(ast, env, ctx, constr),
 
         Import { .. } | Generic { .. } | Parent { .. } | DocStr { .. } | Underscore => {
@@ -6,7 +6,7 @@ use crate::check::constrain::constraint::expected::Expected;
 use crate::check::constrain::constraint::Constraint;
 use crate::check::constrain::generate::env::Environment;
 use crate::check::constrain::generate::{gen_vec, generate, Constrained};
-use crate::check::context::clss::{BOOL, FLOAT, INT, RANGE, SLICE, STRING};
+use crate::check::context::clss::{BOOL, COMPLEX, FLOAT, INT, NONE, RANGE, SLICE, STRING};
 use crate::check::context::function::python::CONTAINS;
 use crate::check::context::function::python::{
     ADD, DIV, EQ, FDIV, GE, GEQ, LE, LEQ, MOD, MUL, NEQ, POW, SUB,
@@ -38,7 +38,7 @@ pub fn gen_op(
 
         Node::Real { .. } => gen_primitive(ast, FLOAT, env, constr),
         Node::Int { .. } => gen_primitive(ast, INT, env, constr),
-        Node::ENum { .. } => gen_primitive(ast, INT, env, constr),
+        Node::Imaginary { .. } => gen_primitive(ast, COMPLEX, env, constr),
         Node::Str { expressions, .. } => {
             gen_vec(expressions, env, false, ctx, constr)?;
             for expr in expressions {
@@ -59,6 +59,17 @@ pub fn gen_op(
         Node::Ge { left, right } => gen_magic(GE, ast, left, right, env, ctx, constr),
         Node::Leq { left, right } => gen_magic(LEQ, ast, left, right, env, ctx, constr),
         Node::Geq { left, right } => gen_magic(GEQ, ast, left, right, env, ctx, constr),
+        Node::Neq { left, right } | Node::Eq { left, right } if is_none(left) || is_none(right) => {
+            // Any value may be compared to None, even if it does not define equality
+            let bool = Expected::new(
+                ast.pos,
+                &Type {
+                    name: Name::from(BOOL),
+                },
+            );
+            constr.add("compare with None", &Expected::from(ast), &bool, env);
+            bin_op(left, right, env, ctx, constr)
+        }
         Node::Neq { left, right } => gen_magic(NEQ, ast, left, right, env, ctx, constr),
         Node::Eq { left, right } => gen_magic(EQ, ast, left, right, env, ctx, constr),
 
@@ -153,6 +164,14 @@ pub fn gen_op(
                 let class_name = TrueName::try_from(right)?;
                 ctx.class(&class_name, right.pos)?;
 
+                let bool = Expected::new(
+                    ast.pos,
+                    &Type {
+                        name: Name::from(BOOL),
+                    },
+                );
+                constr.add("is a", &Expected::from(ast), &bool, env);
+
                 generate(left, env, ctx, constr)?;
                 generate(right, &env.is_def_mode(true), ctx, constr)?;
                 Ok(env.clone())
@@ -310,6 +329,10 @@ fn access(fun: &str, left: &AST, right: &AST) -> Expected {
     )
 }
 
+fn is_none(ast: &AST) -> bool {
+    matches!(&ast.node, Node::Id { lit } if lit == NONE)
+}
+
 fn bin_op(
     left: &AST,
     right: &AST,
@@ -1,12 +1,15 @@
 use std::convert::TryFrom;
 
 use crate::check::constrain::constraint::builder::ConstrBuilder;
-use crate::check::constrain::constraint::expected::Expect::Type;
+use crate::check::constrain::constraint::expected::Expect::{Access, Function, Type};
 use crate::check::constrain::constraint::expected::Expected;
+use crate::check::constrain::constraint::Constraint;
 use crate::check::constrain::generate::definition::id_from_var;
 use crate::check::constrain::generate::env::Environment;
 use crate::check::constrain::generate::{generate, Constrained};
+use crate::check::context::function::python::{AENTER, AEXIT, AWAIT, ENTER, EXIT};
 use crate::check::context::Context;
+use crate::check::name::string_name::StringName;
 use crate::check::name::Name;
 use crate::check::result::TypeErr;
 use crate::parse::ast::{Node, AST};
@@ -18,40 +21,33 @@ pub fn gen_resources(
     constr: &mut ConstrBuilder,
 ) -> Constrained {
     match &ast.node {
+        Node::With { is_async: true, .. } if !env.in_async => Err(vec![TypeErr::new(
+            ast.pos,
+            "Cannot have async with outside async function",
+        )]),
         Node::With {
+            is_async,
             resource,
             alias: Some((alias, mutable, ty)),
             expr,
         } => {
-            constr.add(
-                "with alias",
-                &Expected::from(resource),
-                &Expected::from(alias),
-                env,
-            );
-            constr.add(
-                "with resource",
-                &Expected::from(resource),
-                &Expected::any(resource.pos),
-                env,
-            );
+            let resource_env = generate(resource, &env.is_destruct_mode(true), ctx, constr)?
+                .is_destruct_mode(false)
+                .is_def_mode(true);
 
+            let entered = constr_context_manager(resource, *is_async, env, constr);
             if let Some(ty) = ty {
                 let ty_exp = Type {
                     name: Name::try_from(ty)?,
                 };
                 constr.add(
                     "with alias type",
-                    &Expected::from(resource),
+                    &entered,
                     &Expected::new(ty.pos, &ty_exp),
                     env,
                 );
             }
 
-            let resource_env = generate(resource, &env.is_destruct_mode(true), ctx, constr)?
-                .is_destruct_mode(false)
-                .is_def_mode(true);
-
             constr.branch_point();
             let ty = if let Some(ty) = ty {
                 Some(Name::try_from(ty)?)
@@ -67,19 +63,25 @@ pub fn gen_resources(
                 constr,
                 &resource_env,
             )?;
+            // Alias only in scope after its definition
+            constr.add(
+                "with alias",
+                &entered,
+                &Expected::from(alias),
+                &resource_env,
+            );
             generate(expr, &resource_env.is_def_mode(false), ctx, constr)?;
 
             Ok(env.clone())
         }
-        Node::With { resource, expr, .. } => {
-            constr.add(
-                "with",
-                &Expected::from(resource),
-                &Expected::any(resource.pos),
-                env,
-            );
-
+        Node::With {
+            is_async,
+            resource,
+            expr,
+            ..
+        } => {
             let resource_env = generate(resource, env, ctx, constr)?;
+            constr_context_manager(resource, *is_async, env, constr);
             generate(expr, &resource_env, ctx, constr)?;
             Ok(env.clone())
         }
@@ -87,3 +89,88 @@ pub fn gen_resources(
         _ => Err(vec![TypeErr::new(ast.pos, "Expected resources")]),
     }
 }
+
+/// Constrain a resource to be a context manager, returning the expected type of the entered
+/// resource.
+///
+/// A context manager must define both `__enter__` and `__exit__`, where the latter is called with
+/// the (absent) exception type, value and traceback.
+/// If async, these are `__aenter__` and `__aexit__`, and the result of `__aenter__` is awaited.
+fn constr_context_manager(
+    resource: &AST,
+    is_async: bool,
+    env: &Environment,
+    constr: &mut ConstrBuilder,
+) -> Expected {
+    let exp_resource = Expected::from(resource);
+    let (enter, exit) = if is_async {
+        (AENTER, AEXIT)
+    } else {
+        (ENTER, EXIT)
+    };
+
+    let access = |entity: &Expected, name: &str, args: Vec<Expected>| {
+        let fun = Function {
+            name: StringName::from(name),
+            args,
+        };
+        Expected::new(
+            entity.pos,
+            &Access {
+                entity: Box::from(entity.clone()),
+                name: Box::new(Expected::new(entity.pos, &fun)),
+            },
+        )
+    };
+
+    let exit_ty = Expected::new(
+        resource.pos,
+        &Type {
+            name: constr.temp_name(),
+        },
+    );
+    let none = Expected::none(resource.pos);
+    let exit_args = vec![exp_resource.clone(), none.clone(), none.clone(), none];
+    let exit_access = access(&exp_resource, exit, exit_args);
+    constr.add_constr(
+        &Constraint::new(
+            &format!("context manager exit of {exp_resource}"),
+            &exit_ty,
+            &exit_access,
+        ),
+        env,
+    );
+
+    let enter_ty = Expected::new(
+        resource.pos,
+        &Type {
+            name: constr.temp_name(),
+        },
+    );
+    let enter_access = access(&exp_resource, enter, vec![exp_resource.clone()]);
+    constr.add_constr(
+        &Constraint::new(
+            &format!("context manager {exp_resource}"),
+            &enter_ty,
+            &enter_access,
+        ),
+        env,
+    );
+
+    if is_async {
+        let entered = Expected::new(
+            resource.pos,
+            &Type {
+                name: constr.temp_name(),
+            },
+        );
+        let awaited = access(&enter_ty, AWAIT, vec![enter_ty.clone()]);
+        constr.add_constr(
+            &Constraint::new("async context manager", &entered, &awaited),
+            env,
+        );
+        entered
+    } else {
+        Expected::new(resource.pos, &enter_ty.expect)
+    }
+}
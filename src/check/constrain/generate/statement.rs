@@ -3,6 +3,7 @@ use std::iter::FromIterator;
 
 use crate::check::constrain::constraint::builder::ConstrBuilder;
 use crate::check::constrain::constraint::expected::Expected;
+use crate::check::constrain::generate::collection::constr_col_items;
 use crate::check::constrain::generate::env::Environment;
 use crate::check::constrain::generate::{generate, Constrained};
 use crate::check::context::clss::HasParent;
@@ -61,6 +62,25 @@ pub fn gen_stmt(
                 )])
             }
         }
+        Node::Yield { expr } | Node::YieldFrom { expr } => {
+            if let Some(expected_yield_ty) = &env.yield_type {
+                generate(expr, env, ctx, constr)?;
+                let yielded = if let Node::YieldFrom { .. } = &ast.node {
+                    constr_col_items(expr, false, env, constr)
+                } else {
+                    Expected::from(expr)
+                };
+                constr.add("yield", expected_yield_ty, &yielded, env);
+                Ok(env.clone())
+            } else if !env.in_fun {
+                Err(vec![TypeErr::new(ast.pos, "Yield outside function")])
+            } else {
+                Err(vec![TypeErr::new(
+                    ast.pos,
+                    "Yield outside function with return type",
+                )])
+            }
+        }
         _ => Err(vec![TypeErr::new(ast.pos, "Expected statement")]),
     }
 }
@@ -1,8 +1,10 @@
+use crate::check::constrain::constraint::iterator::Constraints;
 use crate::check::constrain::generate::gen_all;
 use crate::check::constrain::unify::finished::Finished;
 use crate::check::constrain::unify::unify;
 use crate::check::context::Context;
 use crate::check::result::TypeErr;
+use crate::check::Defined;
 use crate::parse::ast::AST;
 
 pub(super) mod constraint;
@@ -12,11 +14,21 @@ pub(super) mod unify;
 
 pub type Unified<T = Finished> = Result<T, Vec<TypeErr>>;
 
-pub fn constraints(ast: &AST, ctx: &Context) -> Unified<Finished> {
-    let constrained = gen_all(ast, ctx)?;
+pub fn constraints(ast: &AST, defined: &[Defined], ctx: &Context) -> Unified<Finished> {
+    let constrained = gen_all(ast, defined, ctx)?;
     unify(&constrained, ctx)
 }
 
+/// Constraint sets generated for an [AST], without unifying them.
+pub fn constraint_sets(ast: &AST, ctx: &Context) -> Unified<Vec<Constraints>> {
+    gen_all(ast, &[], ctx)
+}
+
+/// Substitution which unifies the given constraint sets.
+pub fn substitution(constraints: &[Constraints], ctx: &Context) -> Unified<Finished> {
+    unify(constraints, ctx)
+}
+
 #[cfg(test)]
 mod tests {
     use crate::check::constrain::constraints;
@@ -29,7 +41,7 @@ mod tests {
     fn if_stmt_no_type() {
         let src = "if True then 10 else 20";
         let ast = src.parse().unwrap();
-        let finished = constraints(&ast, &Context::default().into_with_primitives().unwrap())
+        let finished = constraints(&ast, &[], &Context::default().into_with_primitives().unwrap())
             .unwrap()
             .pos_to_name;
 
@@ -52,7 +64,7 @@ mod tests {
     fn it_stmt_as_expression() {
         let src = "def a := if True then 10 else 20";
         let ast = src.parse().unwrap();
-        let finished = constraints(&ast, &Context::default().into_with_primitives().unwrap())
+        let finished = constraints(&ast, &[], &Context::default().into_with_primitives().unwrap())
             .unwrap()
             .pos_to_name;
 
@@ -80,7 +92,7 @@ mod tests {
     fn it_stmt_as_expression_none() {
         let src = "def a := if True then 10 else None";
         let ast = src.parse::<AST>().unwrap();
-        let finished = constraints(&ast, &Context::default().into_with_primitives().unwrap())
+        let finished = constraints(&ast, &[], &Context::default().into_with_primitives().unwrap())
             .unwrap()
             .pos_to_name;
 
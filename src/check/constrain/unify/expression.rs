@@ -92,6 +92,18 @@ fn sub_recursive(side: &str, inspected: &Expected, old: &Expected, new: &Expecte
             };
             (any_substituted, Expected::new(inspected.pos, &func))
         }
+        Expect::NonNull { expected } => {
+            let (subs, expected) = sub_recursive(side, expected, old, new);
+            let expected = Box::from(expected);
+            let non_null = Expect::NonNull { expected }.resolve_nullable();
+            (subs, Expected::new(inspected.pos, &non_null))
+        }
+        Expect::OrNone { expected } => {
+            let (subs, expected) = sub_recursive(side, expected, old, new);
+            let expected = Box::from(expected);
+            let nullable = Expect::OrNone { expected }.resolve_nullable();
+            (subs, Expected::new(inspected.pos, &nullable))
+        }
         _ => (false, inspected.clone()),
     }
 }
@@ -1,4 +1,8 @@
 use std::collections::HashMap;
+use std::fmt::{Display, Error, Formatter};
+
+use itertools::Itertools;
+use serde::{Serialize, Serializer};
 
 use crate::check::constrain::constraint::expected::Expected;
 use crate::check::context::clss::COLLECTION;
@@ -19,7 +23,36 @@ pub struct Finished {
     pub(crate) pos_to_name: PosNameMap,
 }
 
+impl Display for Finished {
+    /// Each position with its type, ordered by position.
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        for (pos, name) in self.ordered() {
+            writeln!(f, "{pos} {name}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Serialize for Finished {
+    /// Each position with its type as a pair, ordered by position, as keys must be strings in JSON.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.ordered())
+    }
+}
+
 impl Finished {
+    fn ordered(&self) -> impl Iterator<Item = (&Position, &Name)> {
+        (self.pos_to_name.iter()).sorted_by(|(left, _), (right, _)| {
+            let left = (left.start.line, left.start.pos, left.end.line, left.end.pos);
+            left.cmp(&(
+                right.start.line,
+                right.start.pos,
+                right.end.line,
+                right.end.pos,
+            ))
+        })
+    }
+
     /// Push name associated with specific position in [AST].
     ///
     /// If already present at position, then union is created between current [Name] and given
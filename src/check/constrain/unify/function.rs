@@ -1,9 +1,11 @@
+use std::collections::HashMap;
+
 use itertools::{EitherOrBoth, Itertools};
 
 use crate::check::constrain::constraint::expected::Expect::{Access, Field, Function, Type};
 use crate::check::constrain::constraint::expected::Expected;
 use crate::check::constrain::constraint::iterator::Constraints;
-use crate::check::constrain::constraint::Constraint;
+use crate::check::constrain::constraint::{Constraint, IMMUTABLE_CALL, REASSIGN};
 use crate::check::constrain::unify::finished::Finished;
 use crate::check::constrain::unify::link::{reinsert, unify_link};
 use crate::check::constrain::unify::ty::unify_type_message;
@@ -11,11 +13,11 @@ use crate::check::constrain::Unified;
 use crate::check::context::arg::{FunctionArg, SELF};
 use crate::check::context::clss::{GetField, GetFun};
 use crate::check::context::function::python::STR;
-use crate::check::context::{Context, LookupClass};
+use crate::check::context::{function, Context, LookupClass};
 use crate::check::name::string_name::StringName;
 use crate::check::name::true_name::TrueName;
-use crate::check::name::{Empty, Mutable, Name, TupleCallable};
-use crate::check::result::TypeErr;
+use crate::check::name::{ContainsTemp, Empty, Mutable, Name, Substitute, TupleCallable, TEMP};
+use crate::check::result::{TypeErr, TypeResult};
 use crate::common::delimit::comma_delm;
 use crate::common::position::Position;
 use crate::common::result::WithCause;
@@ -116,13 +118,18 @@ fn access(
                 left,
                 right,
                 &constraint.msg,
+                access_left && constraint.msg == REASSIGN,
                 total,
             ),
             Function { name, args } => function_access(
                 constraints,
                 finished,
                 ctx,
-                entity_name,
+                &if constraint.msg == IMMUTABLE_CALL {
+                    entity_name.as_immutable()
+                } else {
+                    entity_name.clone()
+                },
                 name,
                 args,
                 left,
@@ -151,6 +158,7 @@ fn field_access(
     accessed: &Expected,
     other: &Expected,
     msg: &str,
+    reassigned: bool,
     total: usize,
 ) -> Unified {
     if entity_name.is_empty() {
@@ -165,6 +173,10 @@ fn field_access(
             .map_err(|errs| access_class_cause(&errs, other, accessed, entity_name, msg))?
             .field(name, accessed.pos)
             .map_err(|errs| access_field_cause(&errs, other, entity_name, name, msg))?;
+        if reassigned && !field.mutable && field.assigned_to {
+            let msg = format!("Cannot reassign to immutable field '{name}' of {entity_name}");
+            return Err(vec![TypeErr::new(accessed.pos, &msg)]);
+        }
 
         let field_ty_exp = Expected::new(accessed.pos, &Type { name: field.ty });
         constraints.push("field access", &field_ty_exp, other);
@@ -200,6 +212,11 @@ fn function_access(
         let fun = class
             .fun(name, accessed.pos)
             .map_err(|errs| access_fun_cause(&errs, other, entity_name, name, args, msg))?;
+        let (fun, bounds) = instantiate(&fun, accessed.pos)?;
+        if !entity_name.is_mutable && fun.self_mutable == Some(true) {
+            let msg = format!("Cannot call {name} on {entity_name}, as it mutates {SELF}");
+            return Err(vec![TypeErr::new(accessed.pos, &msg)]);
+        }
 
         let fun_ty_exp = Expected::new(
             accessed.pos,
@@ -210,6 +227,13 @@ fn function_access(
         constraints.push("function access", other, &fun_ty_exp);
         pushed += 1;
 
+        // function which does not take self may also be called on an instance
+        // a class method instead takes the class of the instance
+        let args = if fun.self_mutable.is_none() && !fun.is_class_method() && !args.is_empty() {
+            &args[1..]
+        } else {
+            args
+        };
         pushed += unify_fun_arg(
             entity_name,
             name,
@@ -218,11 +242,62 @@ fn function_access(
             constraints,
             accessed.pos,
         )?;
+
+        for (bound, ty) in bounds {
+            let bound = Expected::new(accessed.pos, &Type { name: bound });
+            let ty = Expected::new(accessed.pos, &Type { name: ty });
+            constraints.push("generic bound", &bound, &ty);
+            pushed += 1;
+        }
     }
 
     unify_link(constraints, finished, ctx, total + pushed)
 }
 
+/// Substitute generics of function with temporary types unique to the call at the given position.
+///
+/// These are inferred during unification.
+/// Also returns the bound of each generic, together with the type substituted for it.
+pub fn instantiate(
+    fun: &function::Function,
+    pos: Position,
+) -> TypeResult<(function::Function, Vec<(Name, Name)>)> {
+    let (mut generics, mut bounds) = (HashMap::new(), vec![]);
+    for parameter in &fun.generics {
+        let temp = format!("{TEMP}{}{TEMP}{}", parameter.name, pos.start);
+        let temp = Name::from(temp.as_str());
+        if let Some(parent) = &parameter.parent {
+            bounds.push((Name::from(parent), temp.clone()));
+        }
+        generics.insert(Name::from(&parameter.name), temp);
+    }
+    if generics.is_empty() {
+        return Ok((fun.clone(), bounds));
+    }
+
+    let arguments = fun
+        .arguments
+        .iter()
+        .map(|arg| {
+            let ty = arg.ty.as_ref().map(|ty| ty.substitute(&generics, pos));
+            Ok(FunctionArg {
+                ty: ty.transpose()?,
+                ..arg.clone()
+            })
+        })
+        .collect::<TypeResult<_>>()?;
+
+    let ret_ty = fun.ret_ty.substitute(&generics, pos)?;
+    Ok((
+        function::Function {
+            arguments,
+            ret_ty,
+            ..fun.clone()
+        },
+        bounds,
+    ))
+}
+
 fn unify_fun_arg(
     entity_name: &TrueName,
     name: &StringName,
@@ -337,7 +412,10 @@ fn access_field_cause(
     field_name: &str,
     cause: &str,
 ) -> Vec<TypeErr> {
-    let msg = format!("We expect {other}, but {entity_name} does not define {field_name}");
+    let msg = format!(
+        "{}{entity_name} does not define {field_name}",
+        we_expect(other)
+    );
     access_cause(errs, other, &msg, cause)
 }
 
@@ -351,12 +429,22 @@ fn access_fun_cause(
 ) -> Vec<TypeErr> {
     let args: Vec<Expected> = args.iter().map(|a| a.and_or_a(false)).collect();
     let msg = format!(
-        "We expect {other}, but {entity_name} does not define {fun_name}({})",
+        "{}{entity_name} does not define {fun_name}({})",
+        we_expect(other),
         comma_delm(args)
     );
     access_cause(errs, other, &msg, cause)
 }
 
+/// What we expect of an access, which is omitted if that is a temporary type only the checker knows
+/// of.
+fn we_expect(other: &Expected) -> String {
+    match &other.expect {
+        Type { name } if name.contains_temp() => String::new(),
+        _ => format!("We expect {other}, but "),
+    }
+}
+
 fn access_cause(errs: &[TypeErr], other: &Expected, msg: &str, cause: &str) -> Vec<TypeErr> {
     errs.iter()
         .map(|err| {
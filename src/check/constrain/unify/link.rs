@@ -1,4 +1,7 @@
-use crate::check::constrain::constraint::expected::Expect::{Access, Expression, Function, Type};
+use crate::check::constrain::constraint::expected::Expect::{
+    Access, Expression, Function, NonNull, OrNone, Type,
+};
+use crate::check::constrain::constraint::expected::Expected;
 use crate::check::constrain::constraint::iterator::Constraints;
 use crate::check::constrain::constraint::Constraint;
 use crate::check::constrain::unify::expression::sub;
@@ -74,6 +77,20 @@ pub fn unify_link(
                 unify_link(constraints, finished, ctx, total)
             }
 
+            (NonNull { .. } | OrNone { .. }, _) | (_, NonNull { .. } | OrNone { .. }) => {
+                let parent = left.expect.resolve_nullable();
+                let child = right.expect.resolve_nullable();
+                if parent != left.expect || child != right.expect {
+                    let parent = Expected::new(left.pos, &parent);
+                    let child = Expected::new(right.pos, &child);
+                    constraints.push(&constraint.msg, &parent, &child);
+                } else {
+                    // Wait until we know the type of what may be None
+                    reinsert(constraints, constraint, total)?;
+                }
+                unify_link(constraints, finished, ctx, total + 1)
+            }
+
             (Type { .. }, _) | (_, Type { .. }) => {
                 unify_type(constraint, constraints, finished, ctx, total)
             }
@@ -12,7 +12,7 @@ pub mod finished;
 mod link;
 
 mod expression;
-mod function;
+pub(super) mod function;
 mod ty;
 
 pub fn unify(all_constraints: &[Constraints], ctx: &Context) -> Unified {
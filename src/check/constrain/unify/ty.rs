@@ -161,6 +161,18 @@ fn recursive_sub_ty(
             };
             (any_substituted, Expected::new(inspected.pos, &func))
         }
+        Expect::NonNull { expected } => {
+            let (subs, expected) = recursive_sub_ty(side, expected, old_to_new, pos)?;
+            let expected = Box::from(expected);
+            let non_null = Expect::NonNull { expected }.resolve_nullable();
+            (subs, Expected::new(inspected.pos, &non_null))
+        }
+        Expect::OrNone { expected } => {
+            let (subs, expected) = recursive_sub_ty(side, expected, old_to_new, pos)?;
+            let expected = Box::from(expected);
+            let nullable = Expect::OrNone { expected }.resolve_nullable();
+            (subs, Expected::new(inspected.pos, &nullable))
+        }
         Type { name } => {
             let new_name = name.substitute(old_to_new, pos)?;
             (
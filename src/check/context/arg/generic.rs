@@ -2,6 +2,7 @@ use std::convert::TryFrom;
 use std::hash::{Hash, Hasher};
 use std::ops::Deref;
 
+use crate::check::context::arg::python::CLS;
 use crate::check::context::clss;
 use crate::check::context::field::generic::GenericField;
 use crate::check::name::string_name::StringName;
@@ -45,10 +46,10 @@ impl Hash for GenericFunctionArg {
 
 impl GenericFunctionArg {
     pub fn in_class(self, class: Option<&StringName>) -> TypeResult<GenericFunctionArg> {
-        if self.name.as_str() == SELF {
+        if self.name.as_str() == SELF || self.name.as_str() == CLS {
             if class.is_none() {
-                let msg = "Cannot have self argument outside class";
-                return Err(vec![TypeErr::new(self.pos, msg)]);
+                let msg = format!("Cannot have {} argument outside class", self.name);
+                return Err(vec![TypeErr::new(self.pos, &msg)]);
             }
 
             if self.ty.is_none() {
@@ -93,8 +94,13 @@ impl TryFrom<&AST> for ClassArgument {
                     },
                 };
 
+                // Assigned to by the constructor, even if it has no default
+                let field = GenericField {
+                    assigned_to: true,
+                    ..GenericField::try_from(ast)?
+                };
                 Ok(ClassArgument {
-                    field: Some(GenericField::try_from(ast)?),
+                    field: Some(field),
                     fun_arg,
                 })
             }
@@ -134,7 +140,7 @@ impl TryFrom<&AST> for GenericFunctionArg {
                     pos: ast.pos,
                     ty: match ty {
                         Some(ty) => Some(Name::try_from(ty.deref())?),
-                        None if name.as_str() == SELF => None,
+                        None if name.as_str() == SELF || name.as_str() == CLS => None,
                         None => {
                             if let Some(default) = default {
                                 Some(match &default.deref().node {
@@ -146,7 +152,9 @@ impl TryFrom<&AST> for GenericFunctionArg {
                                     }
                                     Node::Int { .. } => Name::from(clss::python::INT_PRIMITIVE),
                                     Node::Real { .. } => Name::from(clss::python::FLOAT_PRIMITIVE),
-                                    Node::ENum { .. } => Name::from(clss::python::INT_PRIMITIVE),
+                                    Node::Imaginary { .. } => {
+                                        Name::from(clss::python::COMPLEX_PRIMITIVE)
+                                    }
                                     _ => {
                                         return Err(vec![TypeErr::new(
                                             default.pos,
@@ -5,6 +5,7 @@ use crate::check::name::Name;
 use crate::common::position::Position;
 
 pub const SELF: &str = "self";
+pub const CLS: &str = "cls";
 
 impl From<(&String, &Option<Expression>, &Option<Expression>)> for GenericFunctionArg {
     fn from(
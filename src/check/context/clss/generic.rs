@@ -7,7 +7,8 @@ use std::ops::Deref;
 use crate::check::context::arg::generic::{ClassArgument, GenericFunctionArg};
 use crate::check::context::field::generic::{GenericField, GenericFields};
 use crate::check::context::function::generic::GenericFunction;
-use crate::check::context::function::python::{INIT, STR};
+use crate::check::context::function::python::{EQ, HASH, INIT, NEQ, STR};
+use crate::check::context::parameter::generic::GenericParameter;
 use crate::check::context::parent::generic::GenericParent;
 use crate::check::context::{arg, clss};
 use crate::check::name::string_name::StringName;
@@ -76,7 +77,10 @@ impl Any for GenericClass {
             is_py_type: false,
             name: StringName::new(STR, &[]),
             pure: false,
+            is_async: false,
+            is_generator: false,
             pos: Position::invisible(),
+            generics: vec![],
             arguments: vec![],
             raises: Name::empty(),
             in_class: None,
@@ -96,6 +100,32 @@ impl Any for GenericClass {
     }
 }
 
+impl From<&GenericParameter> for GenericClass {
+    /// A generic parameter behaves as a class which only has the fields and functions of its bound.
+    fn from(parameter: &GenericParameter) -> Self {
+        let parents = parameter.parent.iter().map(|parent| GenericParent {
+            is_py_type: parameter.is_py_type,
+            name: parent.clone(),
+            pos: Position::invisible(),
+        });
+
+        GenericClass {
+            is_py_type: parameter.is_py_type,
+            name: parameter.name.clone(),
+            pos: Position::invisible(),
+            concrete: false,
+            args: vec![],
+            fields: Default::default(),
+            functions: if parameter.parent.is_none() {
+                GenericClass::any().functions
+            } else {
+                Default::default()
+            },
+            parents: parents.collect(),
+        }
+    }
+}
+
 impl TryFrom<&AST> for GenericClass {
     type Error = Vec<TypeErr>;
 
@@ -106,6 +136,7 @@ impl TryFrom<&AST> for GenericClass {
                 args,
                 parents,
                 body,
+                ..
             } => {
                 let name = StringName::try_from(ty)?;
                 let statements = if let Some(body) = body {
@@ -184,7 +215,15 @@ impl TryFrom<&AST> for GenericClass {
                         .collect());
                 }
 
-                let (body_fields, functions) = get_fields_and_functions(&name, &statements, false)?;
+                let (body_fields, mut functions) =
+                    get_fields_and_functions(&name, &statements, false)?;
+                if let Some(frozen) = class.node.data_class() {
+                    for function in data_class_functions(&name, frozen, class.pos) {
+                        if !functions.iter().any(|f| f.name == function.name) {
+                            functions.insert(function);
+                        }
+                    }
+                }
                 if let Some(function) = functions.iter().find(|f| f.name == StringName::from(INIT))
                 {
                     if class_args.is_empty() {
@@ -292,6 +331,45 @@ impl TryFrom<&AST> for GenericClass {
     }
 }
 
+/// Functions synthesized for a data class.
+///
+/// Equality compares all fields by value, and the string representation shows all fields.
+/// Only instances of a frozen data class are hashable, by value, like in Python.
+fn data_class_functions(class: &StringName, frozen: bool, pos: Position) -> Vec<GenericFunction> {
+    let arg = |name: &str| GenericFunctionArg {
+        is_py_type: false,
+        name: String::from(name),
+        pos,
+        has_default: false,
+        vararg: false,
+        mutable: false,
+        ty: Some(Name::from(class)),
+    };
+    let function = |name: &str, arguments: Vec<GenericFunctionArg>, ret_ty: &str| GenericFunction {
+        is_py_type: false,
+        name: StringName::from(name),
+        pure: true,
+        is_async: false,
+        is_generator: false,
+        pos,
+        generics: vec![],
+        arguments,
+        raises: Name::empty(),
+        in_class: Some(class.clone()),
+        ret_ty: Some(Name::from(ret_ty)),
+    };
+
+    let mut functions = vec![
+        function(EQ, vec![arg(arg::SELF), arg("other")], clss::BOOL),
+        function(NEQ, vec![arg(arg::SELF), arg("other")], clss::BOOL),
+        function(STR, vec![arg(arg::SELF)], clss::STRING),
+    ];
+    if frozen {
+        functions.push(function(HASH, vec![arg(arg::SELF)], clss::INT));
+    }
+    functions
+}
+
 fn get_fields_and_functions(
     class: &StringName,
     statements: &[AST],
@@ -326,6 +404,10 @@ fn get_fields_and_functions(
 
                 fields = fields.union(&stmt_fields).cloned().collect();
             }
+            Node::Property { .. } => {
+                let field = GenericField::try_from(statement)?;
+                fields.insert(field.in_class(Some(class), type_def, statement.pos)?);
+            }
             Node::DocStr { .. } => {}
             _ => {
                 let msg = "Expected function or variable definition";
@@ -344,6 +426,7 @@ mod test {
     use itertools::Itertools;
 
     use crate::check::context::clss::generic::GenericClass;
+    use crate::check::context::function::python::{EQ, HASH, NEQ};
     use crate::check::name::string_name::StringName;
     use crate::check::name::true_name::TrueName;
     use crate::check::name::Name;
@@ -581,4 +664,91 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn from_class_properties() -> Result<(), Vec<TypeErr>> {
+        let source = "class MyClass(def a: Int)\n    property b: Int => self.a\n    property c: Int\n        get => self.a\n        set(x) => self.a := x\n";
+        let ast = parse_direct(source)
+            .expect("valid class syntax")
+            .into_iter()
+            .next()
+            .expect("class AST");
+
+        let generic_class = GenericClass::try_from(&ast)?;
+        let fields: Vec<_> = generic_class
+            .fields
+            .iter()
+            .sorted_by_key(|f| f.name.clone())
+            .collect();
+        assert_eq!(fields.len(), 3);
+
+        assert_eq!(fields[1].name, String::from("b"));
+        assert_eq!(fields[1].ty, Some(Name::from("Int")));
+        assert!(!fields[1].mutable);
+        assert!(fields[1].assigned_to);
+
+        assert_eq!(fields[2].name, String::from("c"));
+        assert!(fields[2].mutable);
+        assert!(generic_class
+            .functions
+            .iter()
+            .all(|f| f.name != StringName::from("b")));
+        Ok(())
+    }
+
+    #[test]
+    fn from_data_class_has_value_equality() -> Result<(), Vec<TypeErr>> {
+        let source = "class Point(def fin x: Int, def fin y: Int)\n    def __str__(self) -> Str => \"point\"\n";
+        let ast = parse_direct(source)
+            .expect("valid class syntax")
+            .into_iter()
+            .next()
+            .expect("class AST");
+
+        let generic_class = GenericClass::try_from(&ast)?;
+        let eq = generic_class
+            .functions
+            .iter()
+            .find(|f| f.name == StringName::from(EQ))
+            .expect("synthesized __eq__");
+        assert_eq!(eq.arguments.len(), 2);
+        assert_eq!(eq.arguments[1].ty, Some(Name::from("Point")));
+        assert_eq!(eq.ret_ty, Some(Name::from("Bool")));
+        assert!(generic_class
+            .functions
+            .iter()
+            .any(|f| f.name == StringName::from(NEQ)));
+        let str_count = generic_class
+            .functions
+            .iter()
+            .filter(|f| f.name == StringName::from("__str__"))
+            .count();
+        assert_eq!(str_count, 1);
+        assert!(generic_class
+            .functions
+            .iter()
+            .any(|f| f.name == StringName::from(HASH)));
+        Ok(())
+    }
+
+    #[test]
+    fn from_mutable_data_class_not_hashable() -> Result<(), Vec<TypeErr>> {
+        let source = "class Point(def x: Int, def y: Int)\n";
+        let ast = parse_direct(source)
+            .expect("valid class syntax")
+            .into_iter()
+            .next()
+            .expect("class AST");
+
+        let generic_class = GenericClass::try_from(&ast)?;
+        assert!(generic_class
+            .functions
+            .iter()
+            .any(|f| f.name == StringName::from(EQ)));
+        assert!(generic_class
+            .functions
+            .iter()
+            .all(|f| f.name != StringName::from(HASH)));
+        Ok(())
+    }
 }
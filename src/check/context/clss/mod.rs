@@ -37,6 +37,8 @@ pub const DICT: &str = "Dict";
 
 pub const TUPLE: &str = "Tuple";
 pub const CALLABLE: &str = "Callable";
+pub const COROUTINE: &str = "Coroutine";
+pub const ITERATOR: &str = "Iterator";
 pub const UNION: &str = "Union";
 
 pub const ANY: &str = "Any";
@@ -279,12 +281,21 @@ impl TryFrom<(&GenericClass, &HashMap<Name, Name>, Position)> for Class {
 }
 
 impl Class {
+    /// True if nothing is known about this class, as is the case for imports.
+    pub fn is_opaque(&self) -> bool {
+        self.args.is_empty()
+            && self.fields.is_empty()
+            && self.functions.is_empty()
+            && self.parents.is_empty()
+    }
+
     pub fn constructor(&self, without_self: bool) -> Function {
         Function {
             is_py_type: false,
             name: self.name.clone(),
             self_mutable: None,
             pure: false,
+            generics: vec![],
             arguments: if without_self && !self.args.is_empty() {
                 self.args.iter().skip(1).cloned().collect()
             } else {
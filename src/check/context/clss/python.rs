@@ -34,6 +34,7 @@ pub const TUPLE: &str = "Tuple";
 pub const CALLABLE: &str = "Callable";
 pub const UNION: &str = "Union";
 pub const ANY: &str = "Any";
+pub const ITERATOR: &str = "Iterator";
 
 pub const NONE: &str = "None";
 pub const EXCEPTION: &str = "Exception";
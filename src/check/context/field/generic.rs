@@ -61,6 +61,15 @@ impl TryFrom<&AST> for GenericField {
                 },
                 assigned_to: expr.is_some(),
             }),
+            Node::Property { id, ty, setter, .. } => Ok(GenericField {
+                is_py_type: false,
+                name: field_name(id.deref())?,
+                mutable: setter.is_some(),
+                pos: ast.pos,
+                in_class: None,
+                ty: Some(Name::try_from(ty.deref())?),
+                assigned_to: true,
+            }),
             _ => Err(vec![TypeErr::new(ast.pos, "Expected variable")]),
         }
     }
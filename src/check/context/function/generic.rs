@@ -1,10 +1,12 @@
+use std::collections::HashMap;
 use std::convert::TryFrom;
 use std::hash::{Hash, Hasher};
 use std::ops::Deref;
 
 use crate::check::context::arg::generic::GenericFunctionArg;
+use crate::check::context::parameter::generic::GenericParameter;
 use crate::check::name::string_name::StringName;
-use crate::check::name::Name;
+use crate::check::name::{scoped, Name, SCOPE};
 use crate::check::result::{TypeErr, TypeResult};
 use crate::common::position::Position;
 use crate::parse::ast::{Node, AST};
@@ -14,7 +16,10 @@ pub struct GenericFunction {
     pub is_py_type: bool,
     pub name: StringName,
     pub pure: bool,
+    pub is_async: bool,
+    pub is_generator: bool,
     pub pos: Position,
+    pub generics: Vec<GenericParameter>,
     pub arguments: Vec<GenericFunctionArg>,
     pub raises: Name,
     pub in_class: Option<StringName>,
@@ -79,19 +84,32 @@ impl TryFrom<&AST> for GenericFunction {
     /// If [AST](crate::parser::ast::AST)'s node is not the
     /// [FunDef](crate::parser::ast::Node::FunDef) variant of the [Node](crate::parser::ast::Node).
     fn try_from(ast: &AST) -> TypeResult<GenericFunction> {
+        if let Some(scoped) = scope_generics(ast) {
+            return GenericFunction::try_from(&scoped);
+        }
+
         match &ast.node {
             Node::FunDef {
                 pure,
+                is_async,
                 id,
+                generics,
                 args: fun_args,
                 ret: ret_ty,
                 raises,
+                body,
                 ..
             } => Ok(GenericFunction {
                 is_py_type: false,
                 name: function_name(id.deref())?,
                 pure: *pure,
+                is_async: *is_async,
+                is_generator: body.as_ref().is_some_and(|body| body.node.contains_yield()),
                 pos: ast.pos,
+                generics: generics
+                    .iter()
+                    .map(GenericParameter::try_from)
+                    .collect::<Result<_, _>>()?,
                 arguments: {
                     let args: Vec<GenericFunctionArg> = fun_args
                         .iter()
@@ -121,6 +139,50 @@ impl TryFrom<&AST> for GenericFunction {
     }
 }
 
+/// Give the generics of a function definition names unique to that definition.
+///
+/// Generics are only in scope in the signature and body of the function.
+/// [None] if the definition has no generics, or if these are already scoped.
+pub fn scope_generics(fun_def: &AST) -> Option<AST> {
+    let Node::FunDef { generics, .. } = &fun_def.node else {
+        return None;
+    };
+    let names: HashMap<String, String> = generics
+        .iter()
+        .filter_map(|generic| match &generic.node {
+            Node::Generic { id, .. } => match &id.node {
+                Node::Id { lit } if !lit.contains(SCOPE) => {
+                    Some((lit.clone(), scoped(lit, fun_def.pos)))
+                }
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect();
+    if names.is_empty() {
+        return None;
+    }
+
+    let rename = |id: &AST| match &id.node {
+        Node::Id { lit } if names.contains_key(lit) => {
+            let lit = names[lit].clone();
+            Box::from(AST::new(id.pos, Node::Id { lit }))
+        }
+        _ => Box::from(id.clone()),
+    };
+    Some(fun_def.map(&|node| match node {
+        Node::Generic { id, isa } => Node::Generic {
+            id: rename(id),
+            isa: isa.clone(),
+        },
+        Node::Type { id, generics } => Node::Type {
+            id: rename(id),
+            generics: generics.clone(),
+        },
+        other => other.clone(),
+    }))
+}
+
 pub fn function_name(ast: &AST) -> TypeResult<StringName> {
     match &ast.node {
         Node::Id { lit } => Ok(StringName::from(lit.as_str())),
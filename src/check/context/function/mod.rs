@@ -7,12 +7,14 @@ use std::hash::{Hash, Hasher};
 use itertools::{EitherOrBoth, Itertools};
 
 use crate::check::context::arg::FunctionArg;
-use crate::check::context::clss::Class;
+use crate::check::context::clss;
+use crate::check::context::clss::{Class, GetFun};
 use crate::check::context::function::generic::GenericFunction;
-use crate::check::context::{arg, Context, LookupFunction};
+use crate::check::context::parameter::generic::GenericParameter;
+use crate::check::context::{arg, Context, LookupClass, LookupFunction};
 use crate::check::name::string_name::StringName;
 use crate::check::name::Name;
-use crate::check::name::{Empty, IsSuperSet, Substitute};
+use crate::check::name::{Any, Empty, IsSuperSet, Substitute, TupleCallable};
 use crate::check::result::{TypeErr, TypeResult};
 use crate::common::delimit::comma_delm;
 use crate::common::position::Position;
@@ -35,6 +37,7 @@ pub struct Function {
     pub name: StringName,
     pub self_mutable: Option<bool>,
     pub pure: bool,
+    pub generics: Vec<GenericParameter>,
     pub arguments: Vec<FunctionArg>,
     pub raises: Name,
     pub in_class: Option<StringName>,
@@ -61,6 +64,28 @@ impl LookupFunction<&StringName, Function> for Context {
     }
 }
 
+impl LookupFunction<(&StringName, &StringName), Function> for Context {
+    /// Look up a function of a class which does not take `self`.
+    ///
+    /// Such a function may be called on the class itself.
+    /// The class is then given implicitly to a class method, so its `cls` argument is dropped.
+    fn function(
+        &self,
+        (class, function): (&StringName, &StringName),
+        pos: Position,
+    ) -> TypeResult<Function> {
+        let mut fun = self.class(class, pos)?.fun(function, pos)?;
+        if fun.self_mutable.is_some() {
+            let msg = format!("Cannot call {function} on {class}, as it takes {}", arg::SELF);
+            return Err(vec![TypeErr::new(pos, &msg)]);
+        }
+        if fun.is_class_method() {
+            fun.arguments.remove(0);
+        }
+        Ok(fun)
+    }
+}
+
 impl Hash for Function {
     fn hash<H: Hasher>(&self, state: &mut H) {
         self.name.hash(state);
@@ -122,21 +147,75 @@ impl TryFrom<(&GenericFunction, &HashMap<Name, Name>, Position)> for Function {
                 function_arg.map(|a| a.mutable)
             },
             pure: fun.pure,
+            generics: fun.generics.clone(),
             arguments,
             raises: fun.raises.substitute(generics, pos)?,
             in_class: match &fun.in_class {
                 Some(in_class) => Some(in_class.substitute(generics, pos)?),
                 None => None,
             },
-            ret_ty: match &fun.ret_ty {
-                Some(ty) => ty.substitute(generics, pos)?,
-                None => Name::empty(),
+            ret_ty: match (&fun.ret_ty, fun.is_async, fun.is_generator) {
+                (Some(ty), _, true) => iterator(&yielded(&ty.substitute(generics, pos)?)),
+                (None, _, true) => iterator(&Name::from(clss::NONE)),
+                (Some(ty), true, false) => coroutine(&ty.substitute(generics, pos)?),
+                (None, true, false) => coroutine(&Name::from(clss::NONE)),
+                (Some(ty), false, false) => ty.substitute(generics, pos)?,
+                (None, false, false) => Name::empty(),
             },
         })
     }
 }
 
+/// Type of calling an async function which returns given type.
+pub fn coroutine(ret_ty: &Name) -> Name {
+    Name::from(&StringName::new(
+        clss::COROUTINE,
+        std::slice::from_ref(ret_ty),
+    ))
+}
+
+/// Type of calling a generator which yields given type.
+pub fn iterator(yield_ty: &Name) -> Name {
+    Name::from(&StringName::new(
+        clss::ITERATOR,
+        std::slice::from_ref(yield_ty),
+    ))
+}
+
+/// Type of values yielded by a generator with given return type.
+///
+/// A generator is annotated with either the type of values it yields, or an `Iterator` thereof.
+pub fn yielded(ret_ty: &Name) -> Name {
+    match Vec::from_iter(&ret_ty.names).as_slice() {
+        [name]
+            if name.variant.name == clss::ITERATOR
+                && name.variant.generics.len() == 1
+                && !name.is_nullable =>
+        {
+            name.variant.generics[0].clone()
+        }
+        _ => ret_ty.clone(),
+    }
+}
+
 impl Function {
+    /// Whether function is a class method, which takes the class as its first argument.
+    pub fn is_class_method(&self) -> bool {
+        self.arguments.first().is_some_and(|arg| arg.name == arg::python::CLS)
+    }
+
+    /// Type of function when used as a value.
+    ///
+    /// Arguments without a type are treated as [Any](clss::ANY).
+    pub fn callable(&self) -> Name {
+        let args: Vec<Name> = self
+            .arguments
+            .iter()
+            .map(|arg| arg.ty.clone().unwrap_or_else(Name::any))
+            .collect();
+        Name::callable(&args, &self.ret_ty)
+    }
+
     pub fn args_compatible(&self, args: &[Name], ctx: &Context, pos: Position) -> TypeResult<()> {
         for pair in self.arguments.iter().zip_longest(args) {
             match pair {
@@ -188,6 +267,7 @@ impl Function {
             name: name.clone(),
             self_mutable: None,
             pure: false,
+            generics: vec![],
             arguments: vec![FunctionArg {
                 is_py_type: false,
                 name: String::from(arg::SELF),
@@ -1,6 +1,7 @@
 use python_parser::ast::Funcdef;
 
 use crate::check::context::arg::generic::GenericFunctionArg;
+use crate::check::context::arg::SELF;
 use crate::check::context::function::generic::GenericFunction;
 use crate::check::name::string_name::StringName;
 use crate::check::name::{Empty, Name};
@@ -23,27 +24,52 @@ pub const POW: &str = "__pow__";
 pub const SUB: &str = "__sub__";
 
 pub const STR: &str = "__str__";
+pub const HASH: &str = "__hash__";
 pub const TRUTHY: &str = "__bool__";
 pub const NEXT: &str = "__next__";
 pub const ITER: &str = "__iter__";
 pub const CONTAINS: &str = "__contains__";
+pub const AWAIT: &str = "__await__";
+pub const AITER: &str = "__aiter__";
+pub const ANEXT: &str = "__anext__";
+pub const ENTER: &str = "__enter__";
+pub const EXIT: &str = "__exit__";
+pub const AENTER: &str = "__aenter__";
+pub const AEXIT: &str = "__aexit__";
 
 pub const SUPER: &str = "super";
 
 pub const GET_ITEM: &str = "__getitem__";
 
+/// Decorator of a function in a Python stub which mutates `self`.
+///
+/// In stubs, `self` is otherwise immutable.
+pub const MUTATING: &str = "mutating";
+
 impl From<&Funcdef> for GenericFunction {
     fn from(func_def: &Funcdef) -> GenericFunction {
+        let mutating = func_def
+            .decorators
+            .iter()
+            .any(|decorator| decorator.name == [String::from(MUTATING)]);
+
         GenericFunction {
             is_py_type: true,
             name: StringName::from(func_def.name.as_str()),
             pure: false,
+            is_async: func_def.r#async,
+            is_generator: false,
             pos: Position::invisible(),
+            generics: vec![],
             arguments: func_def
                 .parameters
                 .positional_args
                 .iter()
                 .map(|(name, ty, expr)| GenericFunctionArg::from((name, ty, expr)))
+                .map(|arg| GenericFunctionArg {
+                    mutable: arg.name != SELF || mutating,
+                    ..arg
+                })
                 .collect(),
             raises: Name::empty(),
             in_class: None,
@@ -109,4 +135,26 @@ mod test {
         assert!(generic_function.arguments[3].has_default);
         assert!(generic_function.arguments[3].mutable);
     }
+
+    #[test]
+    fn from_py_mutating() {
+        let source =
+            "class A:\n    def f(self): pass\n\n    @mutating\n    def g(self, a: int): pass\n";
+        let (_, statements) =
+            python_parser::file_input(python_parser::make_strspan(&source)).expect("parse source");
+
+        let class_def = match statements.first().expect("class") {
+            Statement::Compound(compound) => match compound.deref() {
+                CompoundStatement::Classdef(class_def) => class_def.clone(),
+                other => panic!("Not class def but {:?}", other),
+            },
+            other => panic!("Not compound statement but {:?}", other),
+        };
+        let (f, g) = (fun_def(&class_def.code[0]), fun_def(&class_def.code[1]));
+
+        assert!(!GenericFunction::from(&f).arguments[0].mutable);
+        let g = GenericFunction::from(&g);
+        assert!(g.arguments[0].mutable);
+        assert!(g.arguments[1].mutable);
+    }
 }
@@ -14,9 +14,9 @@ pub mod arg;
 pub mod clss;
 pub mod field;
 pub mod function;
+pub mod parameter;
 pub mod parent;
 
-mod parameter;
 mod resource;
 
 mod generic;
@@ -27,7 +27,7 @@ mod python;
 ///
 /// Functions and fields are also stored alongside identified classes such that
 /// we can also check usage of top-level fields and functions.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Context {
     pub classes: HashSet<GenericClass>,
     pub functions: HashSet<GenericFunction>,
@@ -52,6 +52,17 @@ impl TryFrom<&[AST]> for Context {
     type Error = Vec<TypeErr>;
 
     fn try_from(files: &[AST]) -> Result<Self, Self::Error> {
+        Context::user(files)?
+            .into_with_primitives()?
+            .into_with_std_lib()
+    }
+}
+
+impl Context {
+    /// Context with only the definitions of the given files.
+    ///
+    /// Unlike [Context::try_from], primitives and the standard library are not loaded.
+    pub fn user(files: &[AST]) -> TypeResult<Self> {
         let (classes, fields, functions) = generics(files)?;
         let mut context = Context::default();
         classes.iter().for_each(|clss| {
@@ -64,7 +75,24 @@ impl TryFrom<&[AST]> for Context {
             context.functions.insert(func.clone());
         });
 
-        context.into_with_primitives()?.into_with_std_lib()
+        // Generics of functions are scoped to that function, so each has a unique name
+        let methods = classes.iter().flat_map(|clss| clss.functions.iter());
+        for function in functions.iter().chain(methods) {
+            for parameter in &function.generics {
+                context.classes.insert(GenericClass::from(parameter));
+            }
+        }
+
+        Ok(context)
+    }
+
+    /// Add definitions of other context not already present in this context.
+    pub fn union(self, other: &Context) -> Context {
+        Context {
+            classes: self.classes.union(&other.classes).cloned().collect(),
+            functions: self.functions.union(&other.functions).cloned().collect(),
+            fields: self.fields.union(&other.fields).cloned().collect(),
+        }
     }
 }
 
@@ -288,4 +316,22 @@ mod tests {
         let tuple = StringName::new("Tuple", &[Name::from("Int"), Name::from("Int")]);
         assert_eq!(arg_ty, Name::from(&tuple));
     }
+
+    #[test]
+    pub fn class_function_without_self() {
+        let source = "class A\n    def f(x: Int) -> Int => x\n    def g(self) -> Int => 1\n";
+        let file = source.parse::<AST>().unwrap();
+        let context = Context::try_from(vec![file].as_slice()).unwrap();
+
+        let (class, pos) = (StringName::from("A"), Position::invisible());
+        let f = context
+            .function((&class, &StringName::from("f")), pos)
+            .expect("function without self");
+        assert_eq!(f.arguments.len(), 1);
+        assert_eq!(f.self_mutable, None);
+
+        context
+            .function((&class, &StringName::from("g")), pos)
+            .expect_err("function with self");
+    }
 }
@@ -1,9 +1,15 @@
+use std::convert::TryFrom;
 use std::fmt::{Display, Error, Formatter};
+use std::ops::Deref;
+
+use serde::Serialize;
 
 use crate::check::name::string_name::StringName;
 use crate::check::name::true_name::TrueName;
+use crate::check::result::{TypeErr, TypeResult};
+use crate::parse::ast::{Node, AST};
 
-#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize)]
 pub struct GenericParameter {
     pub is_py_type: bool,
     pub name: StringName,
@@ -24,3 +30,24 @@ impl Display for GenericParameter {
         )
     }
 }
+
+impl TryFrom<&AST> for GenericParameter {
+    type Error = Vec<TypeErr>;
+
+    fn try_from(ast: &AST) -> TypeResult<GenericParameter> {
+        match &ast.node {
+            Node::Generic { id, isa } => Ok(GenericParameter {
+                is_py_type: false,
+                name: StringName::try_from(id.deref())?,
+                parent: match isa {
+                    Some(isa) => Some(TrueName::try_from(isa.deref())?),
+                    None => None,
+                },
+            }),
+            _ => {
+                let msg = format!("Expected generic, was {}", ast.node);
+                Err(vec![TypeErr::new(ast.pos, &msg)])
+            }
+        }
+    }
+}
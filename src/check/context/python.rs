@@ -30,6 +30,10 @@ pub fn python_files(
         let path = entry
             .map_err(|err| TypeErr::new_no_pos(err.to_string().as_str()))?
             .path();
+        let extension = path.extension().and_then(|ext| ext.to_str());
+        if !path.is_file() || !matches!(extension, Some("py") | Some("pyi")) {
+            continue;
+        }
         let python_src_path = path
             .as_os_str()
             .to_str()
@@ -46,7 +50,10 @@ pub fn python_files(
         let python_src = python_src.replace("\r\n", "\n"); // Replace CRLF
         let statements =
             python_parser::file_input(python_parser::make_strspan(python_src.as_ref()))
-                .unwrap()
+                .map_err(|err| {
+                    let msg = format!("Unable to parse python file {python_src_path}: {err:?}");
+                    TypeErr::new_no_pos(&msg)
+                })?
                 .1;
 
         for statement in statements {
@@ -33,6 +33,22 @@ impl Context {
         })
     }
 
+    /// Loads definitions of Python files in each given directory into Context.
+    ///
+    /// Definitions already present take precedence.
+    pub fn into_with_stubs(self, dirs: &[PathBuf]) -> TypeResult<Self> {
+        let mut context = self;
+        for dir in dirs {
+            let (py_types, py_fields, py_functions) = python_files(dir)?;
+            context = context.union(&Context {
+                classes: py_types,
+                functions: py_functions,
+                fields: py_fields,
+            });
+        }
+        Ok(context)
+    }
+
     /// Loads pre-defined Python standard library into Context.
     pub fn into_with_std_lib(self) -> TypeResult<Self> {
         let python_dir = resource("std");
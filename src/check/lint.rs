@@ -0,0 +1,191 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use itertools::EitherOrBoth::{Both, Left};
+use itertools::Itertools;
+
+use crate::check::result::TypeErr;
+use crate::parse::ast::{Node, AST};
+
+pub const UNUSED_IMPORT: &str = "unused-import";
+
+/// Level of a lint.
+///
+/// Unlike type errors, lints only prevent a file from being transpiled if denied.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Level {
+    Allow,
+    #[default]
+    Warn,
+    Deny,
+}
+
+impl Display for Level {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Level::Allow => write!(f, "allow"),
+            Level::Warn => write!(f, "warn"),
+            Level::Deny => write!(f, "deny"),
+        }
+    }
+}
+
+impl FromStr for Level {
+    type Err = String;
+
+    fn from_str(level: &str) -> Result<Self, Self::Err> {
+        match level {
+            "allow" => Ok(Level::Allow),
+            "warn" => Ok(Level::Warn),
+            "deny" => Ok(Level::Deny),
+            other => Err(format!(
+                "Expected lint level 'allow', 'warn' or 'deny', was '{other}'"
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Lints {
+    pub unused_import: Level,
+}
+
+impl Lints {
+    pub fn set(&mut self, lint: &str, level: Level) -> Result<(), String> {
+        match lint {
+            UNUSED_IMPORT => self.unused_import = level,
+            other => return Err(format!("Unknown lint '{other}'")),
+        }
+        Ok(())
+    }
+}
+
+impl Display for Lints {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{UNUSED_IMPORT}={}", self.unused_import)
+    }
+}
+
+/// Lint a file, giving each finding which is not allowed with its level.
+pub fn lint(ast: &AST, lints: &Lints) -> Vec<(Level, TypeErr)> {
+    let mut findings = vec![];
+    if lints.unused_import != Level::Allow {
+        let errs = unused_imports(ast);
+        findings.extend(errs.into_iter().map(|err| (lints.unused_import, err)));
+    }
+    findings
+}
+
+/// Imports whose name is not used as an identifier anywhere else in the file.
+fn unused_imports(ast: &AST) -> Vec<TypeErr> {
+    let statements = match &ast.node {
+        Node::Block { statements } => statements.clone(),
+        _ => vec![ast.clone()],
+    };
+
+    let mut used = HashSet::new();
+    for statement in &statements {
+        if !matches!(statement.node, Node::Import { .. }) {
+            used_ids(statement, &mut used);
+        }
+    }
+
+    let mut errs = vec![];
+    for statement in &statements {
+        if let Node::Import { import, alias, .. } = &statement.node {
+            for pair in import.iter().zip_longest(alias) {
+                let name = match pair {
+                    Left(name) | Both(_, name) => name,
+                    _ => continue,
+                };
+                if let Node::Id { lit } = &name.node {
+                    if !used.contains(lit) {
+                        errs.push(TypeErr::new(name.pos, &format!("Unused import: {lit}")));
+                    }
+                }
+            }
+        }
+    }
+    errs
+}
+
+/// Identifiers used in a node.
+///
+/// The property of a property call is not an identifier, as it is looked up in the instance.
+fn used_ids(ast: &AST, used: &mut HashSet<String>) {
+    match &ast.node {
+        Node::Id { lit } => {
+            used.insert(lit.clone());
+        }
+        Node::PropertyCall { instance, property } | Node::SafeCall { instance, property } => {
+            used_ids(instance, used);
+            match &property.node {
+                Node::Id { .. } => {}
+                Node::FunctionCall { args, .. } => args.iter().for_each(|arg| used_ids(arg, used)),
+                _ => used_ids(property, used),
+            }
+        }
+        node => node
+            .children()
+            .into_iter()
+            .for_each(|child| used_ids(child, used)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::check::lint::{lint, Level, Lints, UNUSED_IMPORT};
+    use crate::parse::ast::AST;
+
+    fn findings(source: &str, lints: &Lints) -> Vec<(Level, String)> {
+        let ast = source.parse::<AST>().unwrap();
+        let findings = lint(&ast, lints).into_iter();
+        findings.map(|(level, err)| (level, err.msg)).collect()
+    }
+
+    #[test]
+    fn unused_import_warns_by_default() {
+        let source = "import a\nimport b as c\nimport d\nprint(d)\n";
+        let findings = findings(source, &Lints::default());
+        assert_eq!(
+            findings,
+            vec![
+                (Level::Warn, String::from("Unused import: a")),
+                (Level::Warn, String::from("Unused import: c"))
+            ]
+        );
+    }
+
+    #[test]
+    fn import_named_in_string_or_property_unused() {
+        let source = "import a\nimport b\nimport c\nprint(\"a\")\nc.b\n";
+        let findings = findings(source, &Lints::default());
+        assert_eq!(
+            findings,
+            vec![
+                (Level::Warn, String::from("Unused import: a")),
+                (Level::Warn, String::from("Unused import: b"))
+            ]
+        );
+    }
+
+    #[test]
+    fn import_used_in_call_argument_of_property() {
+        let source = "import a\nimport b\nb.f(a)\n";
+        assert!(findings(source, &Lints::default()).is_empty());
+    }
+
+    #[test]
+    fn unused_import_allowed() {
+        let mut lints = Lints::default();
+        lints.set(UNUSED_IMPORT, Level::Allow).unwrap();
+        assert!(findings("import a\n", &lints).is_empty());
+    }
+
+    #[test]
+    fn unknown_lint() {
+        assert!(Lints::default().set("unknown", Level::Deny).is_err());
+    }
+}
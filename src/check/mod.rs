@@ -3,15 +3,19 @@ use std::convert::TryFrom;
 use crate::check::ast::ASTTy;
 use crate::check::constrain::constraints;
 use crate::check::context::Context;
+use crate::check::name::Name;
 use crate::check::result::TypeResult;
 use crate::parse::ast::AST;
 use crate::TypeErr;
 
 mod constrain;
+
+pub(crate) use constrain::{constraint_sets, substitution};
 mod ident;
 
 pub mod ast;
 pub mod context;
+pub mod lint;
 pub mod name;
 pub mod result;
 
@@ -22,6 +26,16 @@ pub mod result;
 ///
 /// Any ill-typed [AST](mamba::parser::ast::AST) results in a failure.
 pub fn check(ast: &AST, ctx: &Context) -> TypeResult {
+    check_defined(ast, &[], ctx)
+}
+
+/// Variable defined before an [AST](mamba::parser::ast::AST) is checked.
+///
+/// Consists of whether it is mutable, its name, and its type.
+pub type Defined = (bool, String, Name);
+
+/// Checks an [AST](mamba::parser::ast::AST) in which the given variables are already defined.
+pub fn check_defined(ast: &AST, defined: &[Defined], ctx: &Context) -> TypeResult {
     trace!(
         "Constructed context with {} classes, {} functions, {} fields",
         ctx.classes.len(),
@@ -29,7 +43,7 @@ pub fn check(ast: &AST, ctx: &Context) -> TypeResult {
         ctx.fields.len()
     );
 
-    let finished = constraints(ast, ctx)?;
+    let finished = constraints(ast, defined, ctx)?;
     Ok(ASTTy::from((ast, &finished)))
 }
 
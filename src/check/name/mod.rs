@@ -7,6 +7,7 @@ use std::hash::{Hash, Hasher};
 use std::iter::FromIterator;
 
 use itertools::Itertools;
+use serde::Serialize;
 
 use crate::check::context::clss::Class;
 use crate::check::context::{clss, Context};
@@ -24,9 +25,21 @@ pub mod generic;
 pub mod python;
 
 pub const TEMP: char = '@';
+/// Separates a generic of a function from the position of that function, see [scoped].
+pub const SCOPE: char = '#';
 
 pub type NameMap = HashMap<Name, Name>;
 
+/// Name of a generic of the function defined at the given position, unique to that function.
+pub fn scoped(generic: &str, pos: Position) -> String {
+    format!("{generic}{SCOPE}{}", pos.start)
+}
+
+/// Name of a generic as written in the source, without the function it is scoped to.
+pub fn unscoped(name: &str) -> &str {
+    name.split(SCOPE).next().unwrap_or(name)
+}
+
 pub trait Union<T> {
     fn union(&self, value: &T) -> Self;
 }
@@ -48,10 +61,13 @@ pub trait Nullable {
     fn is_nullable(&self) -> bool;
     fn is_null(&self) -> bool;
     fn as_nullable(&self) -> Self;
+    /// Remove possibility of being None.
+    fn as_non_nullable(&self) -> Self;
 }
 
 pub trait Mutable {
     fn as_mutable(&self) -> Self;
+    fn as_immutable(&self) -> Self;
 }
 
 pub trait Substitute {
@@ -80,7 +96,7 @@ pub trait TupleCallable<T1, T2, T3> {
     fn ret_ty(&self, pos: Position) -> TypeResult<T3>;
 }
 
-#[derive(Debug, Clone, Eq, Default)]
+#[derive(Debug, Clone, Eq, Default, Serialize)]
 pub struct Name {
     pub names: HashSet<TrueName>,
     pub is_interchangeable: bool,
@@ -184,6 +200,13 @@ impl Mutable for Name {
             ..self.clone()
         }
     }
+
+    fn as_immutable(&self) -> Self {
+        Name {
+            names: self.names.iter().map(|n| n.as_immutable()).collect(),
+            ..self.clone()
+        }
+    }
 }
 
 impl Union<Name> for Name {
@@ -331,6 +354,14 @@ impl Nullable for Name {
             ..self.clone()
         }
     }
+
+    fn as_non_nullable(&self) -> Self {
+        let names = self.names.iter().filter(|n| !n.is_null());
+        Name {
+            names: names.map(|n| n.as_non_nullable()).collect(),
+            ..self.clone()
+        }
+    }
 }
 
 impl Empty for Name {
@@ -809,6 +840,18 @@ mod tests {
         assert!(!name1.is_null());
     }
 
+    #[test]
+    fn test_name_as_non_nullable() {
+        let name = Name::from("MyType").as_nullable();
+        assert_eq!(name.as_non_nullable(), Name::from("MyType"));
+    }
+
+    #[test]
+    fn test_name_union_none_as_non_nullable() {
+        let name = Name::from(&HashSet::from(["MyType", clss::NONE]));
+        assert_eq!(name.as_non_nullable(), Name::from("MyType"));
+    }
+
     #[test]
     fn test_name_none_is_null() {
         let name1 = Name::from(clss::NONE);
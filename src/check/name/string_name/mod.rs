@@ -5,13 +5,15 @@ use std::iter::FromIterator;
 
 use itertools::EitherOrBoth::Both;
 use itertools::Itertools;
+use serde::Serialize;
 
 use crate::check::context::clss::{GetFun, HasParent, CALLABLE, TUPLE, UNION};
 use crate::check::context::{function, Context, LookupClass};
 use crate::check::name::true_name::{IsTemp, MatchTempName, TrueName};
 use crate::check::name::Name;
 use crate::check::name::{
-    ColType, ContainsTemp, Empty, IsSuperSet, NameMap, Substitute, TupleCallable, Union, TEMP,
+    unscoped, Any, ColType, ContainsTemp, Empty, IsSuperSet, NameMap, Substitute, TupleCallable,
+    Union, TEMP,
 };
 use crate::check::result::{TypeErr, TypeResult};
 use crate::common::delimit::comma_delm;
@@ -20,7 +22,7 @@ use crate::common::position::Position;
 pub mod generic;
 
 /// Useful to denote class and function names, where Tuples and Anonymous functions are not permitted.
-#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
 pub struct StringName {
     pub name: String,
     pub generics: Vec<Name>,
@@ -31,7 +33,7 @@ impl Display for StringName {
         write!(
             f,
             "{}{}",
-            self.name,
+            unscoped(&self.name),
             if self.generics.is_empty() {
                 String::new()
             } else {
@@ -278,6 +280,15 @@ impl StringName {
     pub fn trim(&self, ty: &str) -> Option<Self> {
         if self.name == ty {
             None
+        } else if self.name == TUPLE {
+            // Elements of a tuple are positional, so none may be dropped
+            let generics: Vec<Name> = self
+                .generics
+                .iter()
+                .map(|n| n.trim(ty))
+                .map(|n| if n.names.is_empty() { Name::any() } else { n })
+                .collect();
+            Some(StringName::new(&self.name, generics.as_slice()))
         } else {
             let generics: Vec<Name> = self
                 .generics
@@ -324,10 +335,10 @@ impl StringName {
 
 #[cfg(test)]
 mod test {
-    use crate::check::context::clss::{HasParent, ANY, BOOL, INT, STRING};
+    use crate::check::context::clss::{HasParent, ANY, BOOL, COLLECTION, INT, STRING, TUPLE};
     use crate::check::context::LookupClass;
     use crate::check::name::string_name::StringName;
-    use crate::check::name::IsSuperSet;
+    use crate::check::name::{IsSuperSet, Name};
     use crate::common::position::Position;
     use crate::Context;
 
@@ -396,4 +407,12 @@ mod test {
             .is_superset_of(&name_2, &ctx, Position::invisible())
             .unwrap())
     }
+
+    #[test]
+    fn trim_keeps_empty_tuple_element() {
+        let empty = Name::from(&StringName::from(TUPLE));
+        let tuple = StringName::new(TUPLE, &[Name::from(INT), empty.clone()]);
+        let trimmed = tuple.trim(COLLECTION).unwrap();
+        assert_eq!(trimmed.generics, vec![Name::from(INT), empty]);
+    }
 }
@@ -4,6 +4,8 @@ use std::fmt::{Display, Error, Formatter};
 use std::hash::Hash;
 use std::iter::FromIterator;
 
+use serde::Serialize;
+
 use crate::check::context::clss::NONE;
 use crate::check::context::{clss, Context};
 use crate::check::name::string_name::StringName;
@@ -17,7 +19,7 @@ use crate::common::position::Position;
 pub mod generic;
 pub mod python;
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
 pub struct TrueName {
     pub is_nullable: bool,
     pub is_mutable: bool,
@@ -69,6 +71,13 @@ impl Mutable for TrueName {
             ..self.clone()
         }
     }
+
+    fn as_immutable(&self) -> Self {
+        TrueName {
+            is_mutable: false,
+            ..self.clone()
+        }
+    }
 }
 
 impl ColType for TrueName {
@@ -160,6 +169,12 @@ impl Nullable for TrueName {
             ..self.clone()
         }
     }
+    fn as_non_nullable(&self) -> Self {
+        TrueName {
+            is_nullable: false,
+            ..self.clone()
+        }
+    }
 }
 
 impl Substitute for TrueName {
@@ -24,6 +24,21 @@ class set(Generic[T], collection[T]):
 
     def __str__(self) -> str: pass
 
+    @mutating
+    def add(self, item: T): pass
+
+    @mutating
+    def remove(self, item: T): pass
+
+    @mutating
+    def discard(self, item: T): pass
+
+    @mutating
+    def pop(self) -> T: pass
+
+    @mutating
+    def clear(self): pass
+
 
 class set_iterator(Generic[T]):
     def __init__(self): pass
@@ -42,6 +57,24 @@ class list(Generic[T], collection[T]):
 
     def __str__(self) -> str: pass
 
+    @mutating
+    def append(self, item: T): pass
+
+    @mutating
+    def extend(self, items: collection[T]): pass
+
+    @mutating
+    def insert(self, index: int, item: T): pass
+
+    @mutating
+    def remove(self, item: T): pass
+
+    @mutating
+    def pop(self) -> T: pass
+
+    @mutating
+    def clear(self): pass
+
 
 class list_iterator(Generic[T]):
     def __init__(self): pass
@@ -78,6 +111,17 @@ class dict(Generic[T, R]):
 
     def values(self) -> dict_values[R]: pass
 
+    def items(self) -> dict_items[T, R]: pass
+
+    @mutating
+    def pop(self, item: T) -> R: pass
+
+    @mutating
+    def update(self, other: dict[T, R]): pass
+
+    @mutating
+    def clear(self): pass
+
 
 class dict_keys(Generic[T]):
     def __init__(self): pass
@@ -95,6 +139,12 @@ class dict_values(Generic[T]):
     def __contains__(self, item: T) -> bool: pass
 
 
+class dict_items(Generic[T, R]):
+    def __init__(self): pass
+
+    def __iter__(self) -> dict_itemiterator[T, R]: pass
+
+
 class dict_keyiterator(Generic[T]):
     def __init__(self): pass
 
@@ -105,3 +155,9 @@ class dict_valueiterator(Generic[T]):
     def __init__(self): pass
 
     def __next__(self) -> T: pass
+
+
+class dict_itemiterator(Generic[T, R]):
+    def __init__(self): pass
+
+    def __next__(self) -> Tuple[T, R]: pass
@@ -0,0 +1,9 @@
+from typing import Generic
+
+
+class Coroutine(Generic[T]):
+    def __init__(self): pass
+
+    def __await__(self) -> T: pass
+
+    def __str__(self) -> str: pass
@@ -0,0 +1,11 @@
+from typing import Generic
+
+
+class Iterator(Generic[T]):
+    def __init__(self): pass
+
+    def __iter__(self) -> Iterator[T]: pass
+
+    def __next__(self) -> T: pass
+
+    def __str__(self) -> str: pass
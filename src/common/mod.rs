@@ -1,3 +1,4 @@
 pub mod delimit;
 pub mod position;
 pub mod result;
+pub mod version;
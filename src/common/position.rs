@@ -1,7 +1,9 @@
 use std::cmp::{max, min, Ordering};
 use std::fmt::{Display, Error, Formatter};
 
-#[derive(Clone, Debug, PartialEq, Eq, Hash, Copy)]
+use serde::Serialize;
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Copy, Serialize)]
 /// A position represents a rectangle in the source code.
 pub struct Position {
     pub start: CaretPos,
@@ -20,7 +22,7 @@ impl Display for Position {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Hash, Copy)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Copy, Serialize)]
 /// An endpoint represents either the top left or bottom right points of a
 /// [Position] rectangle.
 ///
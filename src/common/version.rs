@@ -0,0 +1,54 @@
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// Python version targeted by the generated output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct PythonVersion {
+    pub major: usize,
+    pub minor: usize,
+}
+
+impl PythonVersion {
+    pub const fn new(major: usize, minor: usize) -> PythonVersion {
+        PythonVersion { major, minor }
+    }
+}
+
+impl Display for PythonVersion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
+
+impl FromStr for PythonVersion {
+    type Err = String;
+
+    fn from_str(version: &str) -> Result<Self, Self::Err> {
+        let msg = || format!("Expected Python version of form '3.10', was '{version}'");
+        let (major, minor) = version.split_once('.').ok_or_else(msg)?;
+        Ok(PythonVersion {
+            major: major.parse().map_err(|_| msg())?,
+            minor: minor.parse().map_err(|_| msg())?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::common::version::PythonVersion;
+
+    #[test]
+    fn parse_and_order() {
+        let version = "3.9".parse::<PythonVersion>().unwrap();
+        assert_eq!(version, PythonVersion::new(3, 9));
+        assert!(version < PythonVersion::new(3, 10));
+        assert_eq!(format!("{version}"), "3.9");
+    }
+
+    #[test]
+    fn parse_invalid() {
+        assert!("3".parse::<PythonVersion>().is_err());
+        assert!("3.x".parse::<PythonVersion>().is_err());
+    }
+}
@@ -0,0 +1,45 @@
+use std::collections::HashSet;
+
+use crate::parse::ast::{Node, AST};
+
+/// Names of top-level definitions of a file.
+pub fn defines(ast: &AST) -> HashSet<String> {
+    let statements = match &ast.node {
+        Node::Block { statements } => statements.clone(),
+        _ => vec![ast.clone()],
+    };
+
+    let mut names = HashSet::new();
+    for statement in statements {
+        match statement.node {
+            Node::Class { ty, .. } | Node::TypeDef { ty, .. } | Node::TypeAlias { ty, .. } => {
+                names.extend(ids(&ty))
+            }
+            Node::FunDef { id, .. } => names.extend(ids(&id)),
+            Node::VariableDef { var, .. } => names.extend(ids(&var)),
+            _ => {}
+        }
+    }
+    names
+}
+
+fn ids(ast: &AST) -> Vec<String> {
+    match &ast.node {
+        Node::Id { lit } => vec![lit.clone()],
+        Node::Type { id, .. } => ids(id),
+        Node::Tuple { elements } => elements.iter().flat_map(ids).collect(),
+        _ => vec![],
+    }
+}
+
+/// All words in a source which may be an identifier.
+///
+/// This over-approximates, as it also contains words in strings and comments, which at worst
+/// causes a file to be rebuilt unnecessarily.
+pub fn uses(source: &str) -> HashSet<String> {
+    source
+        .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .filter(|word| !word.is_empty())
+        .map(String::from)
+        .collect()
+}
@@ -0,0 +1,252 @@
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+use serde::Serialize;
+
+use crate::check::ast::ASTTy;
+use crate::check::context::Context;
+use crate::check::result::TypeResult;
+use crate::check::{constraint_sets, substitution};
+use crate::generate::{gen_arguments, GenArguments};
+use crate::parse::ast::AST;
+use crate::parse::tokens;
+use crate::{io, PipelineArguments};
+
+/// Stage of the pipeline of which a dump may be written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Emit {
+    /// Tokens of the lexer.
+    Tokens,
+    Ast,
+    /// AST annotated with types.
+    Typed,
+    /// Constraint sets of the check stage, each with the message of each constraint.
+    Constraints,
+    /// Substitution found by unifying the constraints.
+    Finished,
+    /// Python AST.
+    Core,
+}
+
+pub const STAGES: [Emit; 6] = [
+    Emit::Tokens,
+    Emit::Ast,
+    Emit::Typed,
+    Emit::Constraints,
+    Emit::Finished,
+    Emit::Core,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EmitFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+impl Display for Emit {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Emit::Tokens => write!(f, "tokens"),
+            Emit::Ast => write!(f, "ast"),
+            Emit::Typed => write!(f, "typed"),
+            Emit::Constraints => write!(f, "constraints"),
+            Emit::Finished => write!(f, "finished"),
+            Emit::Core => write!(f, "core"),
+        }
+    }
+}
+
+impl FromStr for Emit {
+    type Err = String;
+
+    fn from_str(stage: &str) -> Result<Self, Self::Err> {
+        (STAGES.iter())
+            .find(|emit| emit.to_string() == stage)
+            .copied()
+            .ok_or_else(|| format!("Unknown stage to emit: '{stage}'"))
+    }
+}
+
+impl Display for EmitFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            EmitFormat::Text => write!(f, "txt"),
+            EmitFormat::Json => write!(f, "json"),
+        }
+    }
+}
+
+impl FromStr for EmitFormat {
+    type Err = String;
+
+    fn from_str(format: &str) -> Result<Self, Self::Err> {
+        match format {
+            "text" => Ok(EmitFormat::Text),
+            "json" => Ok(EmitFormat::Json),
+            other => Err(format!("Expected 'text' or 'json', was '{other}'")),
+        }
+    }
+}
+
+/// Write a dump of each stage to emit next to given output path, such as `a.ast.txt`.
+///
+/// Stages after a stage which fails are not written, and dumps of them from a previous build are
+/// removed.
+/// Text dumps use the [Display] of a stage where it is readable, and its pretty [Debug]
+/// otherwise.
+/// JSON dumps use the [Serialize] of a stage.
+///
+/// Returns the result of the check, so that it need not be checked again.
+pub fn emit(
+    ast: &AST,
+    ctx: &Context,
+    source: &str,
+    out_path: &Path,
+    pipeline_args: &PipelineArguments,
+) -> Result<TypeResult<ASTTy>, String> {
+    let format = pipeline_args.emit_format;
+    let mut dumps: Vec<(Emit, Option<String>)> = vec![];
+
+    let tokens = tokens(source);
+    let dump = match (&tokens, format) {
+        (Ok(tokens), EmitFormat::Text) => Some(
+            (tokens.iter())
+                .map(|lex| format!("{} {:?}\n", lex.pos, lex.token))
+                .collect(),
+        ),
+        (Ok(tokens), EmitFormat::Json) => Some(json(tokens)?),
+        (Err(_), _) => None,
+    };
+    dumps.push((Emit::Tokens, dump));
+    dumps.push((Emit::Ast, Some(debug(ast, format)?)));
+
+    let constraints = constraint_sets(ast, ctx);
+    let dump = match (&constraints, format) {
+        (Ok(constraints), EmitFormat::Text) => {
+            Some(constraints.iter().map(|c| format!("{c}\n")).collect())
+        }
+        (Ok(constraints), EmitFormat::Json) => Some(json(constraints)?),
+        (Err(_), _) => None,
+    };
+    dumps.push((Emit::Constraints, dump));
+
+    let finished = constraints.and_then(|constraints| substitution(&constraints, ctx));
+    let dump = match (&finished, format) {
+        (Ok(finished), EmitFormat::Text) => Some(format!("{finished}")),
+        (Ok(finished), EmitFormat::Json) => Some(json(finished)?),
+        (Err(_), _) => None,
+    };
+    dumps.push((Emit::Finished, dump));
+
+    let typed = finished.map(|finished| ASTTy::from((ast, &finished)));
+    let dump = typed.as_ref().ok().map(|typed| debug(typed, format));
+    dumps.push((Emit::Typed, dump.transpose()?));
+
+    let core = typed.as_ref().ok().and_then(|typed| {
+        let gen_args = GenArguments::from(pipeline_args);
+        gen_arguments(typed, &gen_args, ctx).ok()
+    });
+    let dump = core.map(|core| debug(&core, format));
+    dumps.push((Emit::Core, dump.transpose()?));
+
+    for (stage, dump) in dumps {
+        if !pipeline_args.emit.contains(&stage) {
+            continue;
+        }
+
+        let path = out_path.with_extension(format!("{stage}.{format}"));
+        match dump {
+            Some(dump) => {
+                io::write_source(&dump, &path)?;
+            }
+            None if path.exists() => {
+                fs::remove_file(&path).map_err(|e| format!("{}: {}", e, path.display()))?;
+            }
+            None => {}
+        }
+    }
+    Ok(typed)
+}
+
+fn debug<T: fmt::Debug + Serialize>(value: &T, format: EmitFormat) -> Result<String, String> {
+    match format {
+        EmitFormat::Text => Ok(format!("{value:#?}\n")),
+        EmitFormat::Json => json(value),
+    }
+}
+
+fn json<T: Serialize>(value: &T) -> Result<String, String> {
+    serde_json::to_string_pretty(value)
+        .map(|json| format!("{json}\n"))
+        .map_err(|err| format!("Unable to write JSON dump: {err}"))
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use tempfile::tempdir;
+
+    use crate::emit::{Emit, EmitFormat};
+    use crate::{transpile_dir, Arguments};
+
+    #[test]
+    fn emit_stages_next_to_output() {
+        let dir = tempdir().expect("temp dir");
+        fs::create_dir(dir.path().join("src")).unwrap();
+        fs::write(dir.path().join("src").join("a.mamba"), "def x := 10\n").unwrap();
+
+        let args = Arguments {
+            emit: vec![Emit::Tokens, Emit::Constraints, Emit::Finished, Emit::Core],
+            ..Default::default()
+        };
+        transpile_dir(dir.path(), None, None, &args).unwrap();
+        let target = dir.path().join("target");
+        let tokens = fs::read_to_string(target.join("a.tokens.txt")).unwrap();
+        assert!(tokens.starts_with("(1:1-4) Def"), "{tokens}");
+        assert!(target.join("a.constraints.txt").is_file());
+        assert!(target.join("a.finished.txt").is_file());
+        assert!(target.join("a.core.txt").is_file());
+        assert!(!target.join("a.ast.txt").exists());
+
+        let args = Arguments {
+            emit: vec![Emit::Typed, Emit::Finished],
+            emit_format: EmitFormat::Json,
+            ..Default::default()
+        };
+        transpile_dir(dir.path(), None, None, &args).unwrap();
+        let typed = fs::read_to_string(target.join("a.typed.json")).unwrap();
+        let typed: serde_json::Value = serde_json::from_str(&typed).unwrap();
+        assert_eq!(typed["node"]["Block"]["statements"][0]["ty"], serde_json::Value::Null);
+
+        let finished = fs::read_to_string(target.join("a.finished.json")).unwrap();
+        let finished: serde_json::Value = serde_json::from_str(&finished).unwrap();
+        let (pos, name) = (&finished[0][0], &finished[0][1]);
+        assert_eq!(pos["start"], serde_json::json!({"line": 1, "pos": 5}));
+        assert_eq!(name["names"][0]["variant"]["name"], "Int");
+    }
+
+    #[test]
+    fn dump_of_failed_stage_is_removed() {
+        let dir = tempdir().expect("temp dir");
+        fs::create_dir(dir.path().join("src")).unwrap();
+        fs::write(dir.path().join("src").join("a.mamba"), "def x := 10\n").unwrap();
+
+        let args = Arguments {
+            emit: vec![Emit::Typed],
+            emit_format: EmitFormat::Json,
+            ..Default::default()
+        };
+        transpile_dir(dir.path(), None, None, &args).unwrap();
+        let typed = dir.path().join("target").join("a.typed.json");
+        assert!(typed.is_file());
+
+        fs::write(dir.path().join("src").join("a.mamba"), "def x: Str := 10\n").unwrap();
+        transpile_dir(dir.path(), None, None, &args).unwrap_err();
+        assert!(!typed.exists());
+    }
+}
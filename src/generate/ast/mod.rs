@@ -77,15 +77,22 @@ fn to_py(core: &Core, ind: usize) -> String {
         Core::Str { string } => format!("\"{string}\""),
         Core::FStr { string } => format!("f\"{string}\""),
         Core::Int { int } => int.clone(),
-        Core::ENum { num, exp } => format!("({num} * 10 ** {exp})"),
+        Core::Imaginary { imaginary } => imaginary.clone(),
         Core::Float { float } => float.clone(),
         Core::Bool { boolean } => String::from(if *boolean { "True" } else { "False" }),
 
-        Core::FunDefOp { op, arg, ty, body } => {
+        Core::FunDefOp {
+            is_async,
+            op,
+            arg,
+            ty,
+            body,
+        } => {
             let id = format!("{op}");
             let dec = vec![];
             to_py(
                 &Core::FunDef {
+                    is_async: *is_async,
                     dec,
                     id,
                     arg: arg.clone(),
@@ -96,30 +103,17 @@ fn to_py(core: &Core, ind: usize) -> String {
             )
         }
         Core::FunDef {
+            is_async,
             dec,
             id,
             arg,
             ty,
             body,
         } => {
-            let dec: Vec<Core> = dec
-                .iter()
-                .map(|d| Core::Id {
-                    lit: format!("@{d}"),
-                })
-                .collect();
             format!(
                 "{}{}def {id}({}){}: {}\n",
-                if dec.is_empty() {
-                    String::from("")
-                } else {
-                    newline_delimited(&dec, ind - 1)
-                },
-                if dec.is_empty() {
-                    String::from("")
-                } else {
-                    indent(ind)
-                },
+                decorators(dec, ind),
+                async_prefix(*is_async),
                 comma_delimited(arg, ind),
                 if let Some(ret_ty) = ty {
                     format!(" -> {}", to_py(ret_ty.as_ref(), ind))
@@ -248,9 +242,28 @@ fn to_py(core: &Core, ind: usize) -> String {
                 newline_delimited(cases, ind + 1)
             )
         }
-        Core::Case { expr, body } => {
-            format!("case {}: {}", to_py(expr, ind), newline_if_body(body, ind))
+        Core::Case { expr, guard, body } => {
+            let guard = guard
+                .as_ref()
+                .map_or(String::new(), |guard| format!(" if {}", to_py(guard, ind)));
+            format!(
+                "case {}{}: {}",
+                to_py(expr, ind),
+                guard,
+                newline_if_body(body, ind)
+            )
+        }
+        Core::PatternOr { patterns } => {
+            let patterns: Vec<String> = patterns.iter().map(|p| to_py(p, ind)).collect();
+            patterns.join(" | ")
+        }
+        Core::PatternClass { class, fields } => {
+            format!("{}({})", to_py(class, ind), comma_delimited(fields, ind))
         }
+        Core::PatternField { id, pattern } => {
+            format!("{}={}", to_py(id, ind), to_py(pattern, ind))
+        }
+        Core::PatternRest { id } => format!("*{}", to_py(id, ind)),
         Core::KeyValue { key, value } => format!("{}: {}", to_py(key, ind), to_py(value, ind)),
 
         Core::UnderScore => String::from("_"),
@@ -285,6 +298,7 @@ fn to_py(core: &Core, ind: usize) -> String {
         }
 
         Core::Not { expr } => format!("not {}", to_py(expr.as_ref(), ind)),
+        Core::Await { expr } => format!("await {}", to_py(expr.as_ref(), ind)),
         Core::And { left, right } => {
             format!(
                 "{} and {}",
@@ -426,9 +440,17 @@ fn to_py(core: &Core, ind: usize) -> String {
         }
 
         Core::Return { expr } => format!("return {}", to_py(expr.as_ref(), ind)),
+        Core::Yield { expr } => format!("yield {}", to_py(expr.as_ref(), ind)),
+        Core::YieldFrom { expr } => format!("yield from {}", to_py(expr.as_ref(), ind)),
 
-        Core::For { expr, col, body } => format!(
-            "for {} in {}:{}",
+        Core::For {
+            is_async,
+            expr,
+            col,
+            body,
+        } => format!(
+            "{}for {} in {}:{}",
+            async_prefix(*is_async),
             to_py(expr.as_ref(), ind),
             to_py(col.as_ref(), ind),
             newline_if_body(body, ind)
@@ -455,6 +477,9 @@ fn to_py(core: &Core, ind: usize) -> String {
             to_py(cond.as_ref(), ind + 1),
             to_py(el.as_ref(), ind + 1)
         ),
+        Core::NamedExpr { var, expr } => {
+            format!("({} := {})", to_py(var, ind), to_py(expr, ind))
+        }
         Core::While { cond, body } => {
             format!(
                 "while {}:{}",
@@ -466,11 +491,13 @@ fn to_py(core: &Core, ind: usize) -> String {
         Core::Break => String::from("break"),
 
         Core::ClassDef {
+            dec,
             name,
             parent_names,
             body,
         } => format!(
-            "class {}{}: {}\n",
+            "{}class {}{}: {}\n",
+            decorators(dec, ind),
             to_py(name, ind),
             if parent_names.is_empty() {
                 String::new()
@@ -481,22 +508,31 @@ fn to_py(core: &Core, ind: usize) -> String {
         ),
 
         Core::Pass => String::from("pass"),
+        Core::Ellipsis => String::from("..."),
         Core::None => String::from("None"),
         Core::Empty => String::new(),
+        Core::Comment { comment } => format!("# {comment}"),
 
-        Core::With { resource, expr } => {
+        Core::With {
+            is_async,
+            resource,
+            expr,
+        } => {
             format!(
-                "with {}: {}",
+                "{}with {}: {}",
+                async_prefix(*is_async),
                 to_py(resource, ind),
                 newline_if_body(expr, ind)
             )
         }
         Core::WithAs {
+            is_async,
             resource,
             alias,
             expr,
         } => format!(
-            "with {} as {}: {}",
+            "{}with {} as {}: {}",
+            async_prefix(*is_async),
             to_py(resource, ind),
             to_py(alias, ind),
             newline_if_body(expr, ind)
@@ -531,6 +567,14 @@ fn to_py(core: &Core, ind: usize) -> String {
     }
 }
 
+fn async_prefix(is_async: bool) -> &'static str {
+    if is_async {
+        "async "
+    } else {
+        ""
+    }
+}
+
 fn indent(amount: usize) -> String {
     " ".repeat(IND_SPACES * amount)
 }
@@ -542,6 +586,13 @@ fn newline_if_body(core: &Core, ind: usize) -> String {
     }
 }
 
+/// Each decorator on its own line, followed by indentation of the decorated definition.
+fn decorators(dec: &[Core], ind: usize) -> String {
+    dec.iter()
+        .map(|d| format!("@{}\n{}", to_py(d, ind), indent(ind)))
+        .collect()
+}
+
 fn newline_delimited(items: &[Core], ind: usize) -> String {
     let mut s = String::new();
     items
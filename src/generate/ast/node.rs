@@ -1,12 +1,14 @@
 use std::convert::TryFrom;
 use std::fmt::{Display, Formatter};
 
+use serde::Serialize;
+
 use crate::check::context::function;
 use crate::generate::result::UnimplementedErr;
 use crate::parse::ast::node_op::NodeOp;
 use crate::ASTTy;
 
-#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize)]
 pub enum Core {
     Import {
         from: Option<Box<Core>>,
@@ -14,6 +16,7 @@ pub enum Core {
         alias: Vec<Core>,
     },
     ClassDef {
+        dec: Vec<Core>,
         name: Box<Core>,
         parent_names: Vec<Core>,
         body: Box<Core>,
@@ -48,13 +51,15 @@ pub enum Core {
         expr: Option<Box<Core>>,
     },
     FunDefOp {
+        is_async: bool,
         op: CoreFunOp,
         arg: Vec<Core>,
         ty: Option<Box<Core>>,
         body: Box<Core>,
     },
     FunDef {
-        dec: Vec<String>,
+        is_async: bool,
+        dec: Vec<Core>,
         id: String,
         arg: Vec<Core>,
         ty: Option<Box<Core>>,
@@ -79,9 +84,8 @@ pub enum Core {
     Int {
         int: String,
     },
-    ENum {
-        num: String,
-        exp: String,
+    Imaginary {
+        imaginary: String,
     },
     DocStr {
         string: String,
@@ -144,6 +148,9 @@ pub enum Core {
     Not {
         expr: Box<Core>,
     },
+    Await {
+        expr: Box<Core>,
+    },
     Is {
         left: Box<Core>,
         right: Box<Core>,
@@ -233,6 +240,7 @@ pub enum Core {
         right: Box<Core>,
     },
     For {
+        is_async: bool,
         expr: Box<Core>,
         col: Box<Core>,
         body: Box<Core>,
@@ -252,13 +260,32 @@ pub enum Core {
     },
     Case {
         expr: Box<Core>,
+        guard: Option<Box<Core>>,
         body: Box<Core>,
     },
+    PatternOr {
+        patterns: Vec<Core>,
+    },
+    PatternClass {
+        class: Box<Core>,
+        fields: Vec<Core>,
+    },
+    PatternField {
+        id: Box<Core>,
+        pattern: Box<Core>,
+    },
+    PatternRest {
+        id: Box<Core>,
+    },
     Ternary {
         cond: Box<Core>,
         then: Box<Core>,
         el: Box<Core>,
     },
+    NamedExpr {
+        var: Box<Core>,
+        expr: Box<Core>,
+    },
     KeyValue {
         key: Box<Core>,
         value: Box<Core>,
@@ -276,10 +303,20 @@ pub enum Core {
     Return {
         expr: Box<Core>,
     },
+    Yield {
+        expr: Box<Core>,
+    },
+    YieldFrom {
+        expr: Box<Core>,
+    },
     UnderScore,
     Pass,
+    Ellipsis,
     None,
     Empty,
+    Comment {
+        comment: String,
+    },
     TryExcept {
         setup: Option<Box<Core>>,
         attempt: Box<Core>,
@@ -298,17 +335,19 @@ pub enum Core {
         error: Box<Core>,
     },
     With {
+        is_async: bool,
         resource: Box<Core>,
         expr: Box<Core>,
     },
     WithAs {
+        is_async: bool,
         resource: Box<Core>,
         alias: Box<Core>,
         expr: Box<Core>,
     },
 }
 
-#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize)]
 pub enum CoreOp {
     Assign,
     AddAssign,
@@ -338,7 +377,7 @@ impl TryFrom<(&ASTTy, &NodeOp)> for CoreOp {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize)]
 pub enum CoreFunOp {
     Ge,
     Geq,
@@ -7,12 +7,45 @@ use crate::generate::name::ToPy;
 use crate::generate::result::{GenResult, UnimplementedErr};
 use crate::{ASTTy, Context};
 
+const SAFE_CALL_VAR: &str = "_safe_call";
+
 pub fn convert_call(ast: &ASTTy, imp: &mut Imports, state: &State, ctx: &Context) -> GenResult {
     Ok(match &ast.node {
         NodeTy::PropertyCall { instance, property } => Core::PropertyCall {
             object: Box::from(convert_node(instance, imp, state, ctx)?),
             property: Box::from(convert_node(property, imp, state, ctx)?),
         },
+        NodeTy::SafeCall { instance, property } => {
+            // Receiver only evaluated once, so we assign it if it is not a variable
+            let (receiver, object) = match convert_node(instance, imp, state, ctx)? {
+                id @ Core::Id { .. } => (id.clone(), id),
+                expr => {
+                    let var = Core::Id {
+                        lit: imp.fresh_var(SAFE_CALL_VAR),
+                    };
+                    let receiver = Core::NamedExpr {
+                        var: Box::from(var.clone()),
+                        expr: Box::from(expr),
+                    };
+                    (receiver, var)
+                }
+            };
+
+            let ternary = Core::Ternary {
+                cond: Box::from(Core::Is {
+                    left: Box::from(receiver),
+                    right: Box::from(Core::None),
+                }),
+                then: Box::from(Core::None),
+                el: Box::from(Core::PropertyCall {
+                    object: Box::from(object),
+                    property: Box::from(convert_node(property, imp, state, ctx)?),
+                }),
+            };
+            Core::Tuple {
+                elements: vec![ternary],
+            }
+        }
         NodeTy::FunctionCall { name, args } => Core::FunctionCall {
             function: Box::from(name.to_py(imp)),
             args: convert_vec(args, imp, state, ctx)?,
@@ -41,27 +41,61 @@ pub fn convert_class(ast: &ASTTy, imp: &mut Imports, state: &State, ctx: &Contex
             })
         }
         NodeTy::TypeDef { ty, body, isa } => {
+            imp.enter_type_vars(&type_vars(ty, state));
             let parents = isa
                 .as_ref()
                 .map_or_else(Vec::new, |isa| vec![isa.to_py(imp)]);
-            extract_class(ty, body, &[], &parents, imp, &state.in_interface(true), ctx)
+            let class = extract_class(ty, body, &[], &parents, imp, &state.in_interface(true), ctx);
+            imp.exit_type_vars();
+            class
         }
         NodeTy::Class {
+            decorators,
             ty,
             body,
             args,
             parents,
+            data,
         } => {
+            let mut dec = convert_vec(decorators, imp, state, ctx)?;
+            imp.enter_type_vars(&type_vars(ty, state));
             let parents = convert_vec(parents, imp, state, ctx)?;
-            extract_class(
-                ty,
-                body,
-                args,
-                &parents,
-                imp,
-                &state.in_interface(false),
-                ctx,
-            )
+            let state = state.in_interface(false);
+
+            let (args, fields) = if let Some(frozen) = data {
+                dec.push(data_class(*frozen, imp));
+                (vec![], data_class_fields(args, imp, &state, ctx)?)
+            } else {
+                (args.clone(), vec![])
+            };
+
+            let class = extract_class(ty, body, &args, &parents, imp, &state, ctx);
+            imp.exit_type_vars();
+            match class? {
+                Core::ClassDef {
+                    name,
+                    parent_names,
+                    body,
+                    ..
+                } => {
+                    let body = match *body {
+                        Core::Block { statements } if !fields.is_empty() => {
+                            let statements = statements.into_iter().filter(|s| *s != Core::Pass);
+                            Box::from(Core::Block {
+                                statements: fields.into_iter().chain(statements).collect(),
+                            })
+                        }
+                        other => Box::from(other),
+                    };
+                    Ok(Core::ClassDef {
+                        dec,
+                        name,
+                        parent_names,
+                        body,
+                    })
+                }
+                other => Ok(other),
+            }
         }
 
         NodeTy::Parent { ty, args } if args.is_empty() => Ok(ty.to_py(imp)),
@@ -77,6 +111,74 @@ pub fn convert_class(ast: &ASTTy, imp: &mut Imports, state: &State, ctx: &Contex
     }
 }
 
+/// Type variables of a generic class, which are only defined if annotating.
+fn type_vars(ty: &StringName, state: &State) -> Vec<(String, Option<Core>)> {
+    if state.annotate {
+        ty.generics.iter().map(|g| (g.to_string(), None)).collect()
+    } else {
+        vec![]
+    }
+}
+
+/// The decorator of a data class, which is frozen if all its fields are immutable.
+fn data_class(frozen: bool, imp: &mut Imports) -> Core {
+    imp.add_from_import("dataclasses", "dataclass");
+    let dataclass = Core::Id {
+        lit: String::from("dataclass"),
+    };
+    if !frozen {
+        return dataclass;
+    }
+
+    let frozen = Core::FunArg {
+        vararg: false,
+        var: Box::from(Core::Id {
+            lit: String::from("frozen"),
+        }),
+        ty: None,
+        default: Some(Box::from(Core::Bool { boolean: true })),
+    };
+    Core::FunctionCall {
+        function: Box::from(dataclass),
+        args: vec![frozen],
+    }
+}
+
+/// Fields of a data class, which must always be annotated.
+fn data_class_fields(
+    args: &[ASTTy],
+    imp: &mut Imports,
+    state: &State,
+    ctx: &Context,
+) -> GenResult<Vec<Core>> {
+    let state = state.def_as_fun_arg(true).annotate(true).expand_ty(true);
+    let fields = convert_vec(args, imp, &state, ctx)?;
+    Ok(fields
+        .into_iter()
+        .map(|field| match field {
+            Core::FunArg {
+                var, ty, default, ..
+            } => {
+                let ty = ty.unwrap_or_else(|| {
+                    imp.add_from_import("typing", "Any");
+                    Box::from(Core::Id {
+                        lit: String::from("Any"),
+                    })
+                });
+                match default {
+                    Some(default) => Core::VarDef {
+                        var,
+                        ty: Some(ty),
+                        expr: Some(default),
+                    },
+                    None => Core::ExpressionType { expr: var, ty },
+                }
+            }
+            other => other,
+        })
+        .collect())
+}
+
 /// Extract class.
 ///
 /// Construct custom constructor to call parents if:
@@ -94,9 +196,11 @@ fn extract_class(
     state: &State,
     ctx: &Context,
 ) -> GenResult {
+    imp.enter_scope(body.as_deref());
     let body = body
         .clone()
         .map(|body| convert_node(body.deref(), imp, state, ctx));
+    imp.exit_scope();
     let body = if let Some(body) = body {
         Some(body?)
     } else {
@@ -107,12 +211,20 @@ fn extract_class(
         Some(other) => vec![other],
         None => vec![],
     }
-    .iter()
+    .into_iter()
+    .flat_map(|stmt| match stmt {
+        // getter and setter of a property
+        Core::Block { statements } => statements,
+        other => vec![other],
+    })
     .enumerate()
     .map(|(i, stmt)| {
         // function two further to leave place for init
-        let (pos, key) = match stmt {
-            Core::FunDef { id, .. } => (i + 2, Core::Id { lit: id.clone() }),
+        let (pos, key) = match &stmt {
+            Core::FunDef { id, dec, .. } => (
+                i + 2,
+                setter(dec).unwrap_or_else(|| Core::Id { lit: id.clone() }),
+            ),
             Core::FunDefOp { op, .. } => (
                 i + 2,
                 Core::Id {
@@ -123,11 +235,11 @@ fn extract_class(
             _ => (
                 i,
                 Core::Id {
-                    lit: String::from("@"),
+                    lit: format!("@{i}"),
                 },
             ),
         };
-        (key, (pos, stmt.clone()))
+        (key, (pos, stmt))
     })
     .collect();
 
@@ -137,7 +249,7 @@ fn extract_class(
         .iter()
         .find(|(name, _)| matches!(name, Core::Id { lit } if *lit == function::python::INIT))
         .map(|(_, (_, function))| function);
-    if let Some(new_init) = init(&old_init, &args, parents)? {
+    if let Some(new_init) = init(&old_init, &args, parents, state.annotate)? {
         let init = Core::Id {
             lit: String::from(function::python::INIT),
         };
@@ -159,6 +271,7 @@ fn extract_class(
         .iter()
         .map(|parent| match parent.clone() {
             Core::FunctionCall { function, .. } => match *function {
+                Core::Type { lit, generics } if state.annotate => Ok(Core::Type { lit, generics }),
                 Core::Type { lit, .. } => Ok(Core::Id { lit }),
                 other => panic!("Expected type in parent, was {}", other),
             },
@@ -167,6 +280,24 @@ fn extract_class(
         })
         .collect::<GenResult<Vec<Core>>>()?;
 
+    let parent_names = if state.annotate && !ty.generics.is_empty() {
+        imp.add_from_import("typing", "Generic");
+        let generics = ty
+            .generics
+            .iter()
+            .map(|generic| generic.to_py(imp))
+            .collect();
+        parent_names
+            .into_iter()
+            .chain(vec![Core::Type {
+                lit: String::from("Generic"),
+                generics,
+            }])
+            .collect()
+    } else {
+        parent_names
+    };
+
     let class = ctx.class(ty, Position::invisible()).ok();
 
     let parent_names = if state.interface && !has_abstract_parent(&class, ctx) {
@@ -184,7 +315,7 @@ fn extract_class(
     let body_stmts: Vec<Core> = body_name_stmts
         .values()
         .sorted_by_key(|(pos, _)| *pos)
-        .map(|(_, stmt)| stmt.clone())
+        .map(|(_, stmt)| class_level(stmt, &class))
         .collect();
 
     let statements = if body_stmts.is_empty() {
@@ -197,6 +328,7 @@ fn extract_class(
     if let Core::Type { lit, .. } = ty.to_py(imp) {
         let name = Box::from(Core::Id { lit });
         Ok(Core::ClassDef {
+            dec: vec![],
             name,
             parent_names,
             body: Box::from(body),
@@ -206,6 +338,52 @@ fn extract_class(
     }
 }
 
+/// Decorate functions of a class which do not take `self`.
+fn class_level(stmt: &Core, class: &Option<Class>) -> Core {
+    let (Some(class), Core::FunDef { id, .. }) = (class, stmt) else {
+        return stmt.clone();
+    };
+
+    let fun = class.functions.iter().find(|f| f.name.name == *id);
+    match fun.filter(|f| f.self_mutable.is_none()) {
+        Some(fun) => class_level_fun(stmt, fun.is_class_method()),
+        None => stmt.clone(),
+    }
+}
+
+/// Decorate a function as a class method, which takes `cls`, or otherwise as a static method.
+pub fn class_level_fun(fun_def: &Core, class_method: bool) -> Core {
+    match fun_def.clone() {
+        Core::FunDef {
+            is_async,
+            dec,
+            id,
+            arg,
+            ty,
+            body,
+        } => {
+            let decorator = if class_method {
+                "classmethod"
+            } else {
+                "staticmethod"
+            };
+            let decorator = Core::Id {
+                lit: String::from(decorator),
+            };
+
+            Core::FunDef {
+                is_async,
+                dec: vec![decorator].into_iter().chain(dec).collect(),
+                id,
+                arg,
+                ty,
+                body,
+            }
+        }
+        other => other,
+    }
+}
+
 fn has_abstract_parent(clss: &Option<Class>, ctx: &Context) -> bool {
     if let Some(clss) = clss {
         clss.parents.iter().any(|parent| {
@@ -233,6 +411,7 @@ fn init(
     old_init: &Option<&Core>,
     class_args: &[Core],
     parents: &[Core],
+    annotate: bool,
 ) -> GenResult<Option<Core>> {
     let (parent_inits, parent_args): (Vec<Core>, Vec<Vec<Core>>) = parents
         .iter()
@@ -288,23 +467,33 @@ fn init(
         &mut class_args
             .iter()
             .flat_map(|arg| match arg {
-                Core::FunArg { var, .. } => Some(var.deref().clone()),
+                Core::FunArg { var, ty, .. } => Some((var.deref().clone(), ty.clone())),
                 _ => None,
             })
-            .filter(|arg| {
+            .filter(|(arg, _)| {
                 !parent_args
                     .iter()
                     .any(|p_args| p_args.iter().any(|p_arg| p_arg == arg))
             })
-            .map(|var| Core::Assign {
-                left: Box::from(Core::PropertyCall {
+            .map(|(var, ty)| {
+                let field = Box::from(Core::PropertyCall {
                     object: Box::from(Core::Id {
                         lit: String::from(arg::python::SELF),
                     }),
                     property: Box::from(var.clone()),
-                }),
-                right: Box::from(var),
-                op: CoreOp::Assign,
+                });
+                match ty {
+                    Some(ty) if annotate => Core::VarDef {
+                        var: field,
+                        ty: Some(ty),
+                        expr: Some(Box::from(var)),
+                    },
+                    _ => Core::Assign {
+                        left: field,
+                        right: Box::from(var),
+                        op: CoreOp::Assign,
+                    },
+                }
             })
             .collect(),
     );
@@ -336,10 +525,15 @@ fn init(
     Ok(if !statements.is_empty() {
         let dec = vec![];
         Some(Core::FunDef {
+            is_async: false,
             dec,
             id,
             arg: args,
-            ty: None,
+            ty: if annotate {
+                Some(Box::from(Core::None))
+            } else {
+                None
+            },
             body: Box::new(Core::Block { statements }),
         })
     } else {
@@ -347,6 +541,13 @@ fn init(
     })
 }
 
+/// Get the setter decorator of a function, if any, which distinguishes it from its getter.
+fn setter(dec: &[Core]) -> Option<Core> {
+    dec.iter()
+        .find(|dec| matches!(dec, Core::PropertyCall { property, .. } if **property == Core::Id { lit: String::from("setter") }))
+        .cloned()
+}
+
 #[cfg(test)]
 mod tests {
     use crate::common::position::Position;
@@ -374,9 +575,8 @@ mod tests {
     fn import_verify() {
         let from = Some(to_pos!(Node::Break));
         let import = vec![
-            to_pos_unboxed!(Node::ENum {
-                num: String::from("a"),
-                exp: String::from("100")
+            to_pos_unboxed!(Node::Imaginary {
+                lit: String::from("100j")
             }),
             to_pos_unboxed!(Node::Real {
                 lit: String::from("3000.5")
@@ -401,9 +601,8 @@ mod tests {
         assert_eq!(*from.unwrap(), Core::Break);
         assert_eq!(
             import[0],
-            Core::ENum {
-                num: String::from("a"),
-                exp: String::from("100")
+            Core::Imaginary {
+                imaginary: String::from("100j")
             }
         );
         assert_eq!(
@@ -417,7 +616,9 @@ mod tests {
 
     #[test]
     fn condition_verify() {
-        let cond = to_pos!(Node::Id { lit: "True".to_string() });
+        let cond = to_pos!(Node::Id {
+            lit: "True".to_string()
+        });
         let condition = to_pos!(Node::Condition { cond, el: None });
 
         let result = gen(&ASTTy::from(&condition));
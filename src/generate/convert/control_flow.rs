@@ -1,10 +1,14 @@
 use crate::check::ast::NodeTy;
+use crate::common::version::PythonVersion;
 use crate::generate::ast::node::Core;
 use crate::generate::convert::convert_node;
 use crate::generate::convert::state::{Imports, State};
 use crate::generate::result::{GenResult, UnimplementedErr};
 use crate::{ASTTy, Context};
 
+/// Structural pattern matching was introduced in Python 3.10.
+const MATCH_VERSION: PythonVersion = PythonVersion::new(3, 10);
+
 pub fn convert_cntrl_flow(
     ast: &ASTTy,
     imp: &mut Imports,
@@ -51,6 +55,16 @@ pub fn convert_cntrl_flow(
             cond,
             cases: match_cases,
         } => {
+            match state.python {
+                Some(python) if python < MATCH_VERSION => {
+                    return Err(Box::from(UnimplementedErr::unsupported(
+                        ast,
+                        "match",
+                        MATCH_VERSION,
+                    )));
+                }
+                _ => {}
+            }
             let expr = Box::from(convert_node(
                 cond,
                 imp,
@@ -60,18 +74,18 @@ pub fn convert_cntrl_flow(
 
             let mut cases = vec![];
             for case in match_cases {
-                if let NodeTy::Case { cond, body } = &case.node {
-                    if let NodeTy::ExpressionType { expr, .. } = &cond.node {
-                        cases.push(Core::Case {
-                            expr: Box::from(convert_node(
-                                expr.as_ref(),
-                                imp,
-                                &state.is_last_must_be_ret(false).must_assign_to(None, None),
-                                ctx,
-                            )?),
-                            body: Box::from(convert_node(body.as_ref(), imp, state, ctx)?),
-                        })
-                    }
+                if let NodeTy::Case { cond, guard, body } = &case.node {
+                    let state_cond = state.is_last_must_be_ret(false).must_assign_to(None, None);
+                    let guard = match guard {
+                        Some(guard) => Some(Box::from(convert_node(guard, imp, &state_cond, ctx)?)),
+                        None => None,
+                    };
+
+                    cases.push(Core::Case {
+                        expr: Box::from(convert_pattern(cond, imp, &state_cond, ctx)?),
+                        guard,
+                        body: Box::from(convert_node(body.as_ref(), imp, state, ctx)?),
+                    })
                 }
             }
 
@@ -81,7 +95,13 @@ pub fn convert_cntrl_flow(
             cond: Box::from(convert_node(cond, imp, state, ctx)?),
             body: Box::from(convert_node(body, imp, state, ctx)?),
         },
-        NodeTy::For { expr, col, body } => Core::For {
+        NodeTy::For {
+            is_async,
+            expr,
+            col,
+            body,
+        } => Core::For {
+            is_async: *is_async,
             expr: Box::from(convert_node(expr, imp, state, ctx)?),
             col: Box::from(convert_node(col, imp, state, ctx)?),
             body: Box::from(convert_node(body, imp, state, ctx)?),
@@ -95,6 +115,57 @@ pub fn convert_cntrl_flow(
     })
 }
 
+/// Convert pattern of match arm.
+///
+/// Type annotations of identifiers are dropped, as Python does not allow these in patterns.
+fn convert_pattern(ast: &ASTTy, imp: &mut Imports, state: &State, ctx: &Context) -> GenResult {
+    let convert_patterns = |patterns: &[ASTTy], imp: &mut Imports| -> GenResult<Vec<Core>> {
+        patterns
+            .iter()
+            .map(|p| convert_pattern(p, imp, state, ctx))
+            .collect()
+    };
+
+    Ok(match &ast.node {
+        NodeTy::ExpressionType { expr, .. } => convert_node(expr, imp, state, ctx)?,
+        NodeTy::Tuple { elements } => Core::Tuple {
+            elements: convert_patterns(elements, imp)?,
+        },
+        NodeTy::List { elements } => Core::List {
+            elements: convert_patterns(elements, imp)?,
+        },
+        NodeTy::PatternOr { patterns } => Core::PatternOr {
+            patterns: convert_patterns(patterns, imp)?,
+        },
+        NodeTy::PatternClass { ty, fields } => Core::PatternClass {
+            class: Box::from(convert_node(ty, imp, state, ctx)?),
+            fields: convert_patterns(fields, imp)?,
+        },
+        NodeTy::PatternField { id, pattern } => Core::PatternField {
+            id: Box::from(convert_node(id, imp, state, ctx)?),
+            pattern: Box::from(convert_pattern(pattern, imp, state, ctx)?),
+        },
+        NodeTy::PatternRest { id } => Core::PatternRest {
+            id: Box::from(convert_node(id, imp, state, ctx)?),
+        },
+        _ => convert_node(ast, imp, state, ctx)?,
+    })
+}
+
+/// True if an if or match expression is converted to a statement.
+///
+/// Each branch of such a statement must then assign to the variable it defines.
+pub fn is_statement(ast: &ASTTy) -> bool {
+    match &ast.node {
+        NodeTy::IfElse { then, el, .. } => match el {
+            Some(el) => ast.ty.is_none() || !is_valid_in_ternary(then, el),
+            None => true,
+        },
+        NodeTy::Match { .. } => true,
+        _ => false,
+    }
+}
+
 fn is_valid_in_ternary(then: &ASTTy, el: &ASTTy) -> bool {
     !matches!(then.node, NodeTy::Block { .. } | NodeTy::Raise { .. })
         && !matches!(el.node, NodeTy::Block { .. } | NodeTy::Raise { .. })
@@ -204,9 +275,8 @@ mod tests {
         let cond = to_pos!(Node::Id {
             lit: String::from("cond")
         });
-        let body = to_pos!(Node::ENum {
-            num: String::from("num"),
-            exp: String::from("")
+        let body = to_pos!(Node::Imaginary {
+            lit: String::from("3j")
         });
         let while_stmt = to_pos!(Node::While { cond, body });
 
@@ -223,9 +293,8 @@ mod tests {
         );
         assert_eq!(
             *core_body,
-            Core::ENum {
-                num: String::from("num"),
-                exp: String::from("0")
+            Core::Imaginary {
+                imaginary: String::from("3j")
             }
         );
     }
@@ -241,10 +310,17 @@ mod tests {
         let body = to_pos!(Node::Id {
             lit: String::from("body")
         });
-        let for_stmt = to_pos!(Node::For { expr, col, body });
+        let for_stmt = to_pos!(Node::For {
+            is_async: false,
+            expr,
+            col,
+            body
+        });
 
         let (core_expr, core_col, core_body) = match gen(&ASTTy::from(&for_stmt)) {
-            Ok(Core::For { expr, col, body }) => (expr, col, body),
+            Ok(Core::For {
+                expr, col, body, ..
+            }) => (expr, col, body),
             other => panic!("Expected for but was {:?}", other),
         };
 
@@ -2,103 +2,179 @@ use std::ops::Deref;
 
 use crate::check::ast::NodeTy;
 use crate::check::context::arg::python::SELF;
+use crate::check::context::clss;
 use crate::check::context::function;
+use crate::check::name::Name;
 use crate::generate::ast::node::{Core, CoreFunOp};
 use crate::generate::convert::common::convert_vec;
+use crate::generate::convert::control_flow::is_statement;
 use crate::generate::convert::convert_node;
 use crate::generate::convert::state::{Imports, State};
 use crate::generate::name::ToPy;
 use crate::generate::result::{GenResult, UnimplementedErr};
 use crate::{ASTTy, Context};
 
+const TUPLE_ARG_VAR: &str = "_tuple_arg";
+
 pub fn convert_def(ast: &ASTTy, imp: &mut Imports, state: &State, ctx: &Context) -> GenResult {
     match &ast.node {
-        NodeTy::VariableDef { var, expr, ty, .. } => {
-            let var = convert_node(var, imp, &state.tuple_literal(), ctx)?;
+        NodeTy::VariableDef {
+            var: var_ty,
+            expr,
+            ty,
+            ..
+        } => {
+            let var = convert_node(var_ty, imp, &state.tuple_literal(true), ctx)?;
             let state = state.in_tup(match var.clone() {
                 Core::Tuple { elements } => elements.len(),
                 _ => 1,
             });
 
-            let annotate =
-                state.annotate && state.expand_ty && !matches!(var, Core::TupleLiteral { .. });
+            let annotate = state.annotate && state.expand_ty;
             let ty = match (ty, expr) {
-                (Some(ty), _) if annotate => Some(Box::from(ty.to_py(imp))),
-                (_, Some(expr)) if annotate => {
-                    expr.clone().ty.map(|name| name.to_py(imp)).map(Box::from)
-                }
+                (Some(ty), _) if annotate => Some(ty.clone()),
+                (_, Some(expr)) if annotate => expr.ty.clone(),
                 _ => None,
             };
 
-            Ok(if state.def_as_fun_arg {
+            if state.def_as_fun_arg {
                 let default = match expr {
                     Some(expression) => {
                         Some(Box::from(convert_node(expression, imp, &state, ctx)?))
                     }
                     None => None,
                 };
-                Core::FunArg {
+                return Ok(Core::FunArg {
                     vararg: false,
                     var: Box::from(var),
-                    ty,
+                    ty: ty.map(|ty| Box::from(state.annotation(ty.to_py(imp)))),
                     default,
+                });
+            }
+
+            // Only first definition in a scope is annotated
+            let (ty, declarations) = match &var {
+                Core::Id { lit } if annotate && imp.declare(lit) => {
+                    (imp.declared_ty(lit, ty), vec![])
+                }
+                Core::TupleLiteral { .. } if annotate => {
+                    (None, declare_tuple(var_ty, &ty, imp, &state))
                 }
+                _ => (None, vec![]),
+            };
+            let ty = ty.map(|ty| Box::from(state.annotation(ty.to_py(imp))));
+
+            let expr = match (&var, expr) {
+                (_, Some(expr)) if is_statement(expr) && annotate => {
+                    // Declare before, as each branch assigns to the variable
+                    let state = state.must_assign_to(Some(&var.clone()), None);
+                    let assign = convert_node(expr, imp, &state, ctx)?;
+                    return Ok(match ty {
+                        Some(ty) => Core::Block {
+                            statements: vec![
+                                Core::ExpressionType {
+                                    expr: Box::from(var),
+                                    ty,
+                                },
+                                assign,
+                            ],
+                        },
+                        None => assign,
+                    });
+                }
+                (_, Some(expr)) if is_statement(expr) => {
+                    let state = state.must_assign_to(Some(&var.clone()), expr.ty.clone());
+                    return convert_node(expr, imp, &state, ctx);
+                }
+                (_, Some(expr)) => Some(Box::from(convert_node(expr, imp, &state, ctx)?)),
+                (Core::TupleLiteral { elements }, None) => Some(Box::from(Core::Tuple {
+                    elements: vec![Core::None; elements.len()],
+                })),
+                (_, None) => None,
+            };
+
+            let var_def = Core::VarDef {
+                var: Box::from(var),
+                ty,
+                expr,
+            };
+            Ok(if declarations.is_empty() {
+                var_def
             } else {
-                let expr = match (&var, expr) {
-                    (_, Some(expr)) => match convert_node(expr, imp, &state, ctx)? {
-                        Core::IfElse { .. } | Core::Match { .. } => {
-                            // redo convert but with assign to state
-                            let state = state.must_assign_to(Some(&var.clone()), expr.ty.clone());
-                            return convert_node(expr, imp, &state, ctx);
-                        }
-                        other => Some(Box::from(other)),
-                    },
-                    (Core::TupleLiteral { elements }, None) => Some(Box::from(Core::Tuple {
-                        elements: vec![Core::None; elements.len()],
-                    })),
-                    (_, None) => None,
-                };
-                Core::VarDef {
-                    var: Box::from(var),
-                    ty,
-                    expr,
+                Core::Block {
+                    statements: declarations.into_iter().chain(vec![var_def]).collect(),
                 }
             })
         }
         NodeTy::FunDef {
+            is_async,
+            is_generator,
+            decorators,
             id,
+            generics,
             args: fun_args,
             body: expression,
             ret: ret_ty,
             ..
         } => {
+            let type_vars: Vec<_> = if state.annotate {
+                generics
+                    .iter()
+                    .map(|generic| {
+                        let bound = generic.parent.as_ref().map(|parent| parent.to_py(imp));
+                        (generic.name.name.clone(), bound)
+                    })
+                    .collect()
+            } else {
+                vec![]
+            };
+            imp.enter_type_vars(&type_vars);
+
+            let mut dec = convert_vec(decorators, imp, state, ctx)?;
+            imp.enter_scope(expression.as_deref());
             let arg = convert_vec(fun_args, imp, state, ctx)?;
             let ty = match ret_ty {
-                Some(ret_ty) if state.annotate => Some(Box::from(ret_ty.to_py(imp))),
+                Some(ret_ty) if state.annotate && *is_generator => {
+                    let ret_ty = function::iterator(&function::yielded(ret_ty));
+                    Some(state.annotation(ret_ty.to_py(imp)))
+                }
+                Some(ret_ty) if state.annotate => Some(state.annotation(ret_ty.to_py(imp))),
+                None if state.annotate => Some(Core::None),
                 _ => None,
             };
-            let (dec, body) = if state.interface && expression.is_none() {
+            let body = if state.interface && expression.is_none() {
                 imp.add_from_import("abc", "abstractmethod");
-                (vec![String::from("abstractmethod")], Box::from(Core::Pass))
+                dec.push(Core::Id {
+                    lit: String::from("abstractmethod"),
+                });
+                Box::from(Core::Pass)
             } else {
-                (
-                    vec![],
-                    Box::from(match expression {
-                        Some(expr) => convert_node(
-                            expr,
-                            imp,
-                            &state.expand_ty(true).is_last_must_be_ret(ty.is_some()),
-                            ctx,
-                        )?,
-                        None => Core::Pass,
-                    }),
-                )
+                Box::from(match expression {
+                    Some(expr) => convert_node(
+                        expr,
+                        imp,
+                        &state.expand_ty(true).is_last_must_be_ret(
+                            state.annotate && ret_ty.is_some() && !*is_generator,
+                        ),
+                        ctx,
+                    )?,
+                    None => Core::Pass,
+                })
             };
+            imp.exit_scope();
+            imp.exit_type_vars();
+            let ty = ty.map(Box::from);
 
             let c_id = Box::from(convert_node(id, imp, state, ctx)?);
             match c_id.deref() {
                 Core::Id { lit } => Ok(if let Some(op) = CoreFunOp::from(lit.as_str()) {
-                    Core::FunDefOp { op, arg, ty, body }
+                    Core::FunDefOp {
+                        is_async: *is_async,
+                        op,
+                        arg,
+                        ty,
+                        body,
+                    }
                 } else {
                     let id = match c_id.as_ref() {
                         Core::Id { ref lit, .. } => match lit.as_str() {
@@ -113,6 +189,7 @@ pub fn convert_def(ast: &ASTTy, imp: &mut Imports, state: &State, ctx: &Context)
                     };
 
                     Core::FunDef {
+                        is_async: *is_async,
                         dec,
                         id,
                         arg,
@@ -123,6 +200,31 @@ pub fn convert_def(ast: &ASTTy, imp: &mut Imports, state: &State, ctx: &Context)
                 _ => Err(Box::from(UnimplementedErr::new(id, "Non-id function"))),
             }
         }
+        NodeTy::Property { id, getter, setter } => {
+            let property = convert_node(id, imp, state, ctx)?;
+            let getter = with_decorator(
+                convert_def(getter, imp, state, ctx)?,
+                Core::Id {
+                    lit: String::from("property"),
+                },
+            );
+            let setter = match setter {
+                Some(setter) => Some(with_decorator(
+                    convert_def(setter, imp, state, ctx)?,
+                    Core::PropertyCall {
+                        object: Box::from(property),
+                        property: Box::from(Core::Id {
+                            lit: String::from("setter"),
+                        }),
+                    },
+                )),
+                None => None,
+            };
+
+            Ok(Core::Block {
+                statements: vec![getter].into_iter().chain(setter).collect(),
+            })
+        }
         NodeTy::FunArg {
             vararg,
             var,
@@ -137,12 +239,16 @@ pub fn convert_def(ast: &ASTTy, imp: &mut Imports, state: &State, ctx: &Context)
                     != Core::Id {
                         lit: String::from(SELF),
                     };
+            if let (true, Core::Id { lit }) = (annotate, &var) {
+                imp.declare(lit);
+            }
 
             Ok(Core::FunArg {
                 vararg: *vararg,
                 var: Box::from(var),
                 ty: if annotate {
-                    ty.as_ref().map(|ty| ty.to_py(imp)).map(Box::from)
+                    let ty = ty.as_ref().map(|ty| state.annotation(ty.to_py(imp)));
+                    ty.map(Box::from)
                 } else {
                     None
                 },
@@ -159,6 +265,134 @@ pub fn convert_def(ast: &ASTTy, imp: &mut Imports, state: &State, ctx: &Context)
     }
 }
 
+/// Convert an anonymous function, where arguments may be (nested) tuples.
+///
+/// Python lambdas cannot destructure their arguments, so each tuple argument is replaced by a
+/// fresh argument, and the body wrapped in an inner lambda which is called with its elements.
+pub fn convert_anon_fun(
+    args: &[ASTTy],
+    body: &ASTTy,
+    imp: &mut Imports,
+    state: &State,
+    ctx: &Context,
+) -> GenResult {
+    let mut args = convert_vec(args, imp, &state.expand_ty(false), ctx)?;
+    let body = convert_node(body, imp, state, ctx)?;
+
+    let (mut inner_args, mut inner_elements) = (vec![], vec![]);
+    for (i, arg) in args.iter_mut().enumerate() {
+        if let Core::FunArg { var, .. } = arg {
+            if let Core::Tuple { .. } | Core::TupleLiteral { .. } = **var {
+                let fresh = Core::Id {
+                    lit: format!("{TUPLE_ARG_VAR}{i}"),
+                };
+                tuple_elements(var, &fresh, &mut inner_args, &mut inner_elements);
+                *var = Box::from(fresh);
+            }
+        }
+    }
+
+    let body = if inner_args.is_empty() {
+        body
+    } else {
+        let inner = Core::AnonFun {
+            args: inner_args,
+            body: Box::from(body),
+        };
+        Core::FunctionCall {
+            function: Box::from(Core::Tuple {
+                elements: vec![inner],
+            }),
+            args: inner_elements,
+        }
+    };
+    Ok(Core::AnonFun {
+        args,
+        body: Box::from(body),
+    })
+}
+
+/// Flatten a tuple into its variables, and how each is accessed given the tuple expression.
+fn tuple_elements(var: &Core, tuple: &Core, vars: &mut Vec<Core>, elements: &mut Vec<Core>) {
+    match var {
+        Core::Tuple { elements: vars_in } | Core::TupleLiteral { elements: vars_in } => {
+            for (i, var) in vars_in.iter().enumerate() {
+                let element = Core::Index {
+                    item: Box::from(tuple.clone()),
+                    range: Box::from(Core::Int { int: i.to_string() }),
+                };
+                tuple_elements(var, &element, vars, elements);
+            }
+        }
+        var => {
+            vars.push(var.clone());
+            elements.push(tuple.clone());
+        }
+    }
+}
+
+/// Declare each variable of a tuple which is not yet declared, as tuples cannot be annotated.
+///
+/// The type of each variable is taken from the tuple type, or otherwise the type of the element.
+fn declare_tuple(var: &ASTTy, ty: &Option<Name>, imp: &mut Imports, state: &State) -> Vec<Core> {
+    let elements = match &var.node {
+        NodeTy::Tuple { elements } => elements,
+        _ => return vec![],
+    };
+    let generics = match ty {
+        Some(ty) if ty.names.len() == 1 => (ty.names.iter())
+            .find(|name| name.variant.name == clss::TUPLE)
+            .map_or_else(Vec::new, |name| name.variant.generics.clone()),
+        _ => vec![],
+    };
+
+    let mut declarations = vec![];
+    for (i, element) in elements.iter().enumerate() {
+        let element_ty = generics.get(i).cloned().or_else(|| element.ty.clone());
+
+        match &element.node {
+            NodeTy::Tuple { .. } => {
+                declarations.append(&mut declare_tuple(element, &element_ty, imp, state))
+            }
+            NodeTy::Id { lit } => {
+                if let Some(element_ty) = element_ty {
+                    if imp.declare(lit) {
+                        let element_ty = imp.declared_ty(lit, Some(element_ty)).unwrap_or_default();
+                        declarations.push(Core::ExpressionType {
+                            expr: Box::from(Core::Id { lit: lit.clone() }),
+                            ty: Box::from(state.annotation(element_ty.to_py(imp))),
+                        })
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    declarations
+}
+
+/// Add a decorator to a function definition, before any existing decorators.
+fn with_decorator(fun_def: Core, decorator: Core) -> Core {
+    match fun_def {
+        Core::FunDef {
+            is_async,
+            dec,
+            id,
+            arg,
+            ty,
+            body,
+        } => Core::FunDef {
+            is_async,
+            dec: vec![decorator].into_iter().chain(dec).collect(),
+            id,
+            arg,
+            ty,
+            body,
+        },
+        other => other,
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::common::position::Position;
@@ -420,6 +654,9 @@ mod test {
                 lit: String::from("fun")
             }),
             pure: false,
+            is_async: false,
+            decorators: vec![],
+            generics: vec![],
             args: vec![
                 to_pos_unboxed!(Node::FunArg {
                     vararg: false,
@@ -485,6 +722,9 @@ mod test {
                 lit: String::from("fun")
             }),
             pure: false,
+            is_async: false,
+            decorators: vec![],
+            generics: vec![],
             args: vec![to_pos_unboxed!(Node::FunArg {
                 vararg: false,
                 mutable: false,
@@ -533,6 +773,9 @@ mod test {
                 lit: String::from("fun")
             }),
             pure: false,
+            is_async: false,
+            decorators: vec![],
+            generics: vec![],
             args: vec![
                 to_pos_unboxed!(Node::Id {
                     lit: String::from("arg1")
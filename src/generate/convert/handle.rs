@@ -1,3 +1,5 @@
+use std::ops::Deref;
+
 use crate::check::ast::NodeTy;
 use crate::generate::ast::node::Core;
 use crate::generate::convert::convert_node;
@@ -17,27 +19,49 @@ pub fn convert_handle(ast: &ASTTy, imp: &mut Imports, state: &State, ctx: &Conte
             cases,
         } => {
             let (var, ty) = if let NodeTy::VariableDef { var, ty, .. } = &expr_or_stmt.node {
-                let ty = ty.as_ref().map(|ty| ty.to_py(imp)).map(Box::from);
-                (Some(Box::from(convert_node(var, imp, state, ctx)?)), ty)
+                (
+                    Some(Box::from(convert_node(var, imp, state, ctx)?)),
+                    ty.clone(),
+                )
             } else {
                 (None, None)
             };
 
-            let assign_state = state.must_assign_to(var.as_deref(), expr_or_stmt.ty.clone());
-            Core::TryExcept {
-                setup: var.map(|var| {
-                    Box::from(Core::VarDef {
+            let (setup, assign_state) = match (var, ty) {
+                // Declare instead of assigning None, which would not match the type
+                (Some(var), Some(ty)) if state.annotate => {
+                    let ty = match var.deref() {
+                        Core::Id { lit } if imp.declare(lit) => imp.declared_ty(lit, Some(ty)),
+                        _ => None,
+                    };
+                    let setup = ty.map(|ty| Core::ExpressionType {
+                        expr: var.clone(),
+                        ty: Box::from(state.annotation(ty.to_py(imp))),
+                    });
+                    let assign_state = state.must_assign_to(Some(&var), None);
+                    (setup, assign_state)
+                }
+                (var, ty) => {
+                    let ty = ty.map(|ty| Box::from(ty.to_py(imp)));
+                    let name = expr_or_stmt.ty.clone();
+                    let assign_state = state.must_assign_to(var.as_deref(), name);
+                    let setup = var.map(|var| Core::VarDef {
                         var,
                         ty,
                         expr: None,
-                    })
-                }),
+                    });
+                    (setup, assign_state)
+                }
+            };
+
+            Core::TryExcept {
+                setup: setup.map(Box::from),
                 attempt: Box::from(convert_node(&expr_or_stmt.clone(), imp, state, ctx)?),
                 except: {
                     let mut except = Vec::new();
                     for case in cases {
                         let (cond, body) = match &case.node {
-                            NodeTy::Case { cond, body } => (cond, body),
+                            NodeTy::Case { cond, body, .. } => (cond, body),
                             other => {
                                 let msg = format!("Expected case, was {other:?}");
                                 return Err(Box::from(UnimplementedErr::new(case, &msg)));
@@ -9,17 +9,18 @@ use crate::generate::convert::call::convert_call;
 use crate::generate::convert::class::convert_class;
 use crate::generate::convert::common::convert_vec;
 use crate::generate::convert::control_flow::convert_cntrl_flow;
-use crate::generate::convert::definition::convert_def;
+use crate::generate::convert::definition::{convert_anon_fun, convert_def};
 use crate::generate::convert::handle::convert_handle;
 use crate::generate::convert::range_slice::convert_range_slice;
 use crate::generate::convert::state::{Imports, State};
 use crate::generate::name::ToPy;
 use crate::generate::result::{GenResult, UnimplementedErr};
+use crate::generate::LINE_MARKER;
 use crate::{ASTTy, Context};
 
 mod builder;
 mod call;
-mod class;
+pub(super) mod class;
 mod common;
 mod control_flow;
 mod definition;
@@ -54,25 +55,52 @@ pub fn convert_node(ast: &ASTTy, imp: &mut Imports, state: &State, ctx: &Context
         NodeTy::VariableDef { .. } | NodeTy::FunDef { .. } | NodeTy::FunArg { .. } => {
             convert_def(ast, imp, state, ctx)?
         }
+        NodeTy::Property { .. } => convert_def(ast, imp, state, ctx)?,
         NodeTy::Reassign { left, right, op } => Core::Assign {
             left: Box::from(convert_node(left, imp, state, ctx)?),
             right: Box::from(convert_node(right, imp, state, ctx)?),
             op: CoreOp::try_from((ast, op))?,
         },
 
-        NodeTy::Block { statements } => Core::Block {
-            statements: convert_vec(statements, imp, state, ctx)?,
-        },
+        NodeTy::Block { statements } => {
+            let mut core_statements = vec![];
+            for (i, statement) in statements.iter().enumerate() {
+                // A class is only defined after its own body
+                let state = if state.annotate {
+                    let classes = statements[i..].iter().flat_map(|stmt| match &stmt.node {
+                        NodeTy::Class { ty, .. }
+                        | NodeTy::TypeDef { ty, .. }
+                        | NodeTy::TypeAlias { ty, .. } => Some(ty.name.clone()),
+                        _ => None,
+                    });
+                    let forward: Vec<String> =
+                        state.forward.iter().cloned().chain(classes).collect();
+                    state.forward(&forward)
+                } else {
+                    state.clone()
+                };
+
+                if state.source_map {
+                    core_statements.push(Core::Comment {
+                        comment: format!("{LINE_MARKER}{}", statement.pos.start.line),
+                    });
+                }
+
+                // A statement may be converted to multiple statements
+                match convert_node(statement, imp, &state, ctx)? {
+                    Core::Block { statements } => core_statements.extend(statements),
+                    other => core_statements.push(other),
+                }
+            }
+            Core::Block {
+                statements: core_statements,
+            }
+        }
 
         NodeTy::Int { lit } => Core::Int { int: lit.clone() },
         NodeTy::Real { lit } => Core::Float { float: lit.clone() },
-        NodeTy::ENum { num, exp } => Core::ENum {
-            num: num.clone(),
-            exp: if exp.is_empty() {
-                String::from("0")
-            } else {
-                exp.clone()
-            },
+        NodeTy::Imaginary { lit } => Core::Imaginary {
+            imaginary: lit.clone(),
         },
         NodeTy::DocStr { lit } => Core::DocStr {
             string: lit.clone(),
@@ -94,7 +122,8 @@ pub fn convert_node(ast: &ASTTy, imp: &mut Imports, state: &State, ctx: &Context
         NodeTy::Bool { lit } => Core::Bool { boolean: *lit },
 
         NodeTy::Tuple { elements } if state.tup_lit => Core::TupleLiteral {
-            elements: convert_vec(elements, imp, state, ctx)?,
+            // Only outermost tuple is a literal, nested tuples are parenthesized
+            elements: convert_vec(elements, imp, &state.tuple_literal(false), ctx)?,
         },
         NodeTy::Tuple { elements } => Core::Tuple {
             elements: convert_vec(elements, imp, state, ctx)?,
@@ -134,6 +163,12 @@ pub fn convert_node(ast: &ASTTy, imp: &mut Imports, state: &State, ctx: &Context
         NodeTy::Return { expr } => Core::Return {
             expr: Box::from(convert_node(expr, imp, state, ctx)?),
         },
+        NodeTy::Yield { expr } => Core::Yield {
+            expr: Box::from(convert_node(expr, imp, state, ctx)?),
+        },
+        NodeTy::YieldFrom { expr } => Core::YieldFrom {
+            expr: Box::from(convert_node(expr, imp, state, ctx)?),
+        },
 
         NodeTy::IfElse { .. } => convert_cntrl_flow(ast, imp, &old_state, ctx)?,
         NodeTy::Match { .. } => convert_cntrl_flow(ast, imp, &old_state, ctx)?,
@@ -238,6 +273,9 @@ pub fn convert_node(ast: &ASTTy, imp: &mut Imports, state: &State, ctx: &Context
         NodeTy::SubU { expr } => Core::SubU {
             expr: Box::from(convert_node(expr, imp, state, ctx)?),
         },
+        NodeTy::Await { expr } => Core::Await {
+            expr: Box::from(convert_node(expr, imp, state, ctx)?),
+        },
         NodeTy::Sqrt { expr } => {
             imp.add_import("math");
             Core::Sqrt {
@@ -262,13 +300,10 @@ pub fn convert_node(ast: &ASTTy, imp: &mut Imports, state: &State, ctx: &Context
             right: Box::from(convert_node(right, imp, state, ctx)?),
         },
 
-        NodeTy::FunctionCall { .. } | NodeTy::PropertyCall { .. } => {
+        NodeTy::FunctionCall { .. } | NodeTy::PropertyCall { .. } | NodeTy::SafeCall { .. } => {
             convert_call(ast, imp, state, ctx)?
         }
-        NodeTy::AnonFun { args, body } => Core::AnonFun {
-            args: convert_vec(args, imp, &state.expand_ty(false), ctx)?,
-            body: Box::from(convert_node(body, imp, state, ctx)?),
-        },
+        NodeTy::AnonFun { args, body } => convert_anon_fun(args, body, imp, state, ctx)?,
 
         NodeTy::In { left, right } => Core::In {
             left: Box::from(convert_node(left, imp, state, ctx)?),
@@ -289,15 +324,23 @@ pub fn convert_node(ast: &ASTTy, imp: &mut Imports, state: &State, ctx: &Context
         NodeTy::Condition { .. } => return Err(Box::from(UnimplementedErr::new(ast, "condition"))),
 
         NodeTy::With {
+            is_async,
             resource,
             alias: Some((alias, ..)),
             expr,
         } => Core::WithAs {
+            is_async: *is_async,
             resource: Box::from(convert_node(resource, imp, state, ctx)?),
             alias: Box::from(convert_node(alias, imp, &state.expand_ty(false), ctx)?),
             expr: Box::from(convert_node(expr, imp, state, ctx)?),
         },
-        NodeTy::With { resource, expr, .. } => Core::With {
+        NodeTy::With {
+            is_async,
+            resource,
+            expr,
+            ..
+        } => Core::With {
+            is_async: *is_async,
             resource: Box::from(convert_node(resource, imp, state, ctx)?),
             expr: Box::from(convert_node(expr, imp, state, ctx)?),
         },
@@ -347,8 +390,9 @@ fn append_assign(core: &Core, assign_to: &Core, name: &Option<Name>, imp: &mut I
                 .map(|c| append_assign(c, assign_to, name, imp))
                 .collect(),
         },
-        Core::Case { expr, body } => Core::Case {
+        Core::Case { expr, guard, body } => Core::Case {
             expr: expr.clone(),
+            guard: guard.clone(),
             body: Box::from(append_assign(body, assign_to, name, imp)),
         },
         Core::TryExcept {
@@ -406,8 +450,9 @@ fn append_ret(core: &Core) -> Core {
             expr: expr.clone(),
             cases: cases.iter().map(append_ret).collect(),
         },
-        Core::Case { expr, body } => Core::Case {
+        Core::Case { expr, guard, body } => Core::Case {
             expr: expr.clone(),
+            guard: guard.clone(),
             body: Box::from(append_ret(body)),
         },
         Core::TryExcept {
@@ -714,9 +759,8 @@ mod tests {
     #[test]
     fn tuple_verify() {
         let elements = vec![
-            to_pos_unboxed!(Node::ENum {
-                num: String::from("a"),
-                exp: String::from("100")
+            to_pos_unboxed!(Node::Imaginary {
+                lit: String::from("100j")
             }),
             to_pos_unboxed!(Node::Real {
                 lit: String::from("3000.5")
@@ -732,9 +776,8 @@ mod tests {
 
         assert_eq!(
             core_elements[0],
-            Core::ENum {
-                num: String::from("a"),
-                exp: String::from("100")
+            Core::Imaginary {
+                imaginary: String::from("100j")
             }
         );
         assert_eq!(
@@ -751,7 +794,9 @@ mod tests {
             to_pos_unboxed!(Node::Id {
                 lit: String::from("a")
             }),
-            to_pos_unboxed!(Node::Id { lit: "True".to_string() }),
+            to_pos_unboxed!(Node::Id {
+                lit: "True".to_string()
+            }),
         ];
         let set = to_pos!(Node::Set { elements });
         let core = gen(&ASTTy::from(&set));
@@ -773,9 +818,8 @@ mod tests {
     #[test]
     fn list_verify() {
         let elements = vec![
-            to_pos_unboxed!(Node::ENum {
-                num: String::from("a"),
-                exp: String::from("100")
+            to_pos_unboxed!(Node::Imaginary {
+                lit: String::from("100j")
             }),
             to_pos_unboxed!(Node::Real {
                 lit: String::from("3000.5")
@@ -791,9 +835,8 @@ mod tests {
 
         assert_eq!(
             core_elements[0],
-            Core::ENum {
-                num: String::from("a"),
-                exp: String::from("100")
+            Core::Imaginary {
+                imaginary: String::from("100j")
             }
         );
         assert_eq!(
@@ -844,6 +887,7 @@ mod tests {
             lit: String::from("9")
         });
         let with = to_pos!(Node::With {
+            is_async: false,
             resource,
             alias,
             expr
@@ -853,6 +897,7 @@ mod tests {
             resource,
             alias,
             expr,
+            ..
         }) = gen(&ASTTy::from(&with))
         else {
             panic!("Expected with as but was {:?}", gen(&ASTTy::from(&with)))
@@ -887,13 +932,14 @@ mod tests {
             lit: String::from("2341")
         });
         let with = to_pos!(Node::With {
+            is_async: false,
             resource,
             alias: None,
             expr
         });
 
         let (resource, expr) = match gen(&ASTTy::from(&with)) {
-            Ok(Core::With { resource, expr }) => (resource, expr),
+            Ok(Core::With { resource, expr, .. }) => (resource, expr),
             other => panic!("Expected with but was {:?}", other),
         };
 
@@ -960,7 +1006,11 @@ mod tests {
         let body = to_pos!(Node::Int {
             lit: String::from("9999")
         });
-        let case = to_pos_unboxed!(Node::Case { cond, body });
+        let case = to_pos_unboxed!(Node::Case {
+            cond,
+            guard: None,
+            body
+        });
         let handle = to_pos!(Node::Handle {
             expr_or_stmt,
             cases: vec![case]
@@ -1,10 +1,13 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 use itertools::Itertools;
 
-use crate::check::name::Name;
+use crate::check::ast::NodeTy;
+use crate::check::name::{unscoped, Name, Union};
+use crate::common::version::PythonVersion;
 use crate::generate::ast::node::Core;
 use crate::generate::GenArguments;
+use crate::ASTTy;
 
 #[derive(Clone, Debug)]
 pub struct State {
@@ -17,6 +20,12 @@ pub struct State {
 
     pub tup_lit: bool,
     pub annotate: bool,
+    /// Targeted Python version, if any.
+    pub python: Option<PythonVersion>,
+    /// Whether each statement is preceded by the line it originates from.
+    pub source_map: bool,
+    /// Classes of the file which are not yet defined, which must be quoted in annotations.
+    pub forward: Vec<String>,
 
     pub is_last_must_be_ret: bool,
     pub must_assign_to: Option<(Core, Option<Name>)>,
@@ -27,6 +36,8 @@ impl From<&GenArguments> for State {
     fn from(gen_arguments: &GenArguments) -> Self {
         State {
             annotate: gen_arguments.annotate,
+            python: gen_arguments.python,
+            source_map: gen_arguments.source_map,
             ..State::new()
         }
     }
@@ -44,6 +55,37 @@ impl State {
             is_remove_last_ret: false,
             must_assign_to: None,
             annotate: false,
+            python: None,
+            source_map: false,
+            forward: vec![],
+        }
+    }
+
+    pub fn forward(&self, forward: &[String]) -> State {
+        State {
+            forward: Vec::from(forward),
+            ..self.clone()
+        }
+    }
+
+    /// Quote an annotation if it refers to a class which is not yet defined.
+    pub fn annotation(&self, ty: Core) -> Core {
+        fn refers_to(core: &Core, names: &[String]) -> bool {
+            match core {
+                Core::Type { lit, generics } => {
+                    names.contains(lit) || generics.iter().any(|g| refers_to(g, names))
+                }
+                Core::Id { lit } => names.contains(lit),
+                _ => false,
+            }
+        }
+
+        if refers_to(&ty, &self.forward) {
+            Core::Str {
+                string: ty.to_string().trim_end().to_string(),
+            }
+        } else {
+            ty
         }
     }
 
@@ -54,9 +96,9 @@ impl State {
         }
     }
 
-    pub fn tuple_literal(&self) -> State {
+    pub fn tuple_literal(&self, tup_lit: bool) -> State {
         State {
-            tup_lit: true,
+            tup_lit,
             ..self.clone()
         }
     }
@@ -96,6 +138,13 @@ impl State {
         }
     }
 
+    pub fn annotate(&self, annotate: bool) -> State {
+        State {
+            annotate,
+            ..self.clone()
+        }
+    }
+
     pub fn must_assign_to(&self, must_assign_to: Option<&Core>, name: Option<Name>) -> State {
         if let Some(must_assign_to) = must_assign_to {
             State {
@@ -114,6 +163,17 @@ impl State {
 pub struct Imports {
     imports: Vec<Core>,
     from_imports: BTreeMap<String, Core>,
+    type_vars: BTreeMap<String, Core>,
+    /// Bound of each type variable, so that type variables with different bounds are not mixed.
+    type_var_bounds: HashMap<String, Option<String>>,
+    /// Name of each type variable of enclosing definitions, per definition.
+    type_var_names: Vec<HashMap<String, String>>,
+    /// Names which have been annotated, per scope, so that each is only annotated once.
+    declared: Vec<HashSet<String>>,
+    /// Union of all types each name is defined with, per scope.
+    defined: Vec<HashMap<String, Name>>,
+    /// Number of variables introduced by the generator, so that each has a fresh name.
+    fresh: usize,
 }
 
 impl Default for Imports {
@@ -127,6 +187,53 @@ impl Imports {
         Imports {
             imports: vec![],
             from_imports: BTreeMap::new(),
+            type_vars: BTreeMap::new(),
+            type_var_bounds: HashMap::new(),
+            type_var_names: vec![],
+            declared: vec![],
+            defined: vec![],
+            fresh: 0,
+        }
+    }
+
+    /// Name of a variable introduced by the generator, which is unique within the file.
+    pub fn fresh_var(&mut self, prefix: &str) -> String {
+        self.fresh += 1;
+        format!("{prefix}{}", self.fresh - 1)
+    }
+
+    /// Enter the scope of a body, such as that of a file, class, or function.
+    pub fn enter_scope(&mut self, body: Option<&ASTTy>) {
+        let mut defined = HashMap::new();
+        if let Some(body) = body {
+            definitions(body, &mut defined);
+        }
+        self.declared.push(HashSet::new());
+        self.defined.push(defined);
+    }
+
+    pub fn exit_scope(&mut self) {
+        self.declared.pop();
+        self.defined.pop();
+    }
+
+    /// Declare name in current scope, returning false if it was already declared.
+    pub fn declare(&mut self, name: &str) -> bool {
+        match self.declared.last_mut() {
+            Some(scope) => scope.insert(String::from(name)),
+            None => true,
+        }
+    }
+
+    /// Type to declare name with, which must also hold for each later definition in the scope.
+    ///
+    /// A name may be shadowed by a definition of another type, in which case it is declared with
+    /// the union of all types, which a type checker narrows upon each assignment.
+    pub fn declared_ty(&self, name: &str, ty: Option<Name>) -> Option<Name> {
+        let defined = self.defined.last().and_then(|scope| scope.get(name));
+        match (ty, defined) {
+            (Some(ty), Some(defined)) => Some(ty.union(defined)),
+            (ty, defined) => ty.or_else(|| defined.cloned()),
         }
     }
 
@@ -189,13 +296,142 @@ impl Imports {
         self.from_imports.insert(String::from(from), import);
     }
 
+    /// Enter a definition with type variables, given by name and optional bound.
+    ///
+    /// Each is renamed if the file already has a type variable by that name with another bound.
+    pub fn enter_type_vars(&mut self, generics: &[(String, Option<Core>)]) {
+        let names = generics
+            .iter()
+            .map(|(name, bound)| (name.clone(), self.add_type_var(name, bound)))
+            .collect();
+        self.type_var_names.push(names);
+    }
+
+    pub fn exit_type_vars(&mut self) {
+        self.type_var_names.pop();
+    }
+
+    /// Name under which a type variable of an enclosing definition is defined.
+    ///
+    /// The generic may be scoped to the function in which it is defined.
+    pub fn type_var(&self, name: &str) -> Option<String> {
+        let mut scopes = self.type_var_names.iter().rev();
+        scopes.find_map(|names| names.get(unscoped(name))).cloned()
+    }
+
+    /// Add a type variable, which is defined after all imports, and return its name.
+    ///
+    /// The bound is a forward reference, as it may be a class which is defined later.
+    fn add_type_var(&mut self, name: &str, bound: &Option<Core>) -> String {
+        self.add_from_import("typing", "TypeVar");
+        let bound = bound.as_ref().map(|b| b.to_string().trim_end().to_string());
+
+        let mut unique = String::from(name);
+        let mut i = 0;
+        while let Some(other) = self.type_var_bounds.get(&unique) {
+            if *other == bound {
+                return unique;
+            }
+            i += 1;
+            unique = format!("{name}{i}");
+        }
+
+        let mut args = vec![Core::Str {
+            string: unique.clone(),
+        }];
+        if let Some(bound) = &bound {
+            args.push(Core::FunArg {
+                vararg: false,
+                var: Box::from(Core::Id {
+                    lit: String::from("bound"),
+                }),
+                ty: None,
+                default: Some(Box::from(Core::Str {
+                    string: bound.clone(),
+                })),
+            });
+        }
+
+        let type_var = Core::VarDef {
+            var: Box::from(Core::Id {
+                lit: unique.clone(),
+            }),
+            ty: None,
+            expr: Some(Box::from(Core::FunctionCall {
+                function: Box::from(Core::Id {
+                    lit: String::from("TypeVar"),
+                }),
+                args,
+            })),
+        };
+        self.type_vars.insert(unique.clone(), type_var);
+        self.type_var_bounds.insert(unique.clone(), bound);
+        unique
+    }
+
     pub fn is_empty(&self) -> bool {
-        self.imports.is_empty() && self.from_imports.is_empty()
+        self.imports.is_empty() && self.from_imports.is_empty() && self.type_vars.is_empty()
     }
 
     pub fn imports(&self) -> Vec<Core> {
         let mut statements = self.imports.clone();
         statements.append(&mut self.from_imports.clone().into_values().collect());
+        statements.append(&mut self.type_vars.clone().into_values().collect());
         statements
     }
 }
+
+/// Collect the types of variables defined in a body, without entering nested scopes.
+fn definitions(ast: &ASTTy, defined: &mut HashMap<String, Name>) {
+    match &ast.node {
+        NodeTy::VariableDef { var, ty, expr, .. } => {
+            let ty = ty
+                .clone()
+                .or_else(|| expr.as_ref().and_then(|expr| expr.ty.clone()));
+            define(var, ty, defined);
+            if let Some(expr) = expr {
+                definitions(expr, defined)
+            }
+        }
+        NodeTy::Block { statements } => statements.iter().for_each(|s| definitions(s, defined)),
+        NodeTy::IfElse { then, el, .. } => {
+            definitions(then, defined);
+            if let Some(el) = el {
+                definitions(el, defined)
+            }
+        }
+        NodeTy::Match { cases, .. } => cases.iter().for_each(|case| definitions(case, defined)),
+        NodeTy::Handle {
+            expr_or_stmt,
+            cases,
+        } => {
+            definitions(expr_or_stmt, defined);
+            cases.iter().for_each(|case| definitions(case, defined))
+        }
+        NodeTy::Case { body, .. }
+        | NodeTy::For { body, .. }
+        | NodeTy::While { body, .. }
+        | NodeTy::With { expr: body, .. } => definitions(body, defined),
+        _ => {}
+    }
+}
+
+fn define(var: &ASTTy, ty: Option<Name>, defined: &mut HashMap<String, Name>) {
+    match &var.node {
+        NodeTy::Id { lit } => {
+            if let Some(ty) = ty {
+                let ty = match defined.get(lit) {
+                    Some(other) => other.union(&ty),
+                    None => ty,
+                };
+                defined.insert(lit.clone(), ty);
+            }
+        }
+        NodeTy::Tuple { elements } => {
+            for element in elements {
+                define(element, element.ty.clone(), defined)
+            }
+        }
+        _ => {}
+    }
+}
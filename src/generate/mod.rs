@@ -1,4 +1,5 @@
 use crate::check::ast::ASTTy;
+use crate::common::version::PythonVersion;
 use crate::generate::ast::node::Core;
 use crate::generate::convert::convert_node;
 use crate::generate::convert::state::{Imports, State};
@@ -9,18 +10,28 @@ mod convert;
 
 pub mod ast;
 pub mod name;
+pub mod stub;
 
 pub mod result;
 
+/// Comment which precedes each statement in the output if a source map is requested, followed by
+/// the line of the statement in the source.
+pub const LINE_MARKER: &str = "mamba-line: ";
+
 #[derive(Default)]
 pub struct GenArguments {
     pub annotate: bool,
+    pub python: Option<PythonVersion>,
+    /// Precede each statement with a [LINE_MARKER] comment.
+    pub source_map: bool,
 }
 
 impl From<&PipelineArguments> for GenArguments {
     fn from(pipeline_args: &PipelineArguments) -> Self {
         GenArguments {
             annotate: pipeline_args.annotate,
+            python: pipeline_args.python,
+            source_map: pipeline_args.source_map,
         }
     }
 }
@@ -79,6 +90,7 @@ pub fn gen_arguments(ast_ty: &ASTTy, gen_args: &GenArguments, ctx: &Context) ->
     let state = State::from(gen_args);
 
     let import = &mut Imports::new();
+    import.enter_scope(Some(ast_ty));
     match convert_node(ast_ty, import, &state, ctx)? {
         Core::Block { statements } => Ok(Core::Block {
             statements: import.imports().into_iter().chain(statements).collect(),
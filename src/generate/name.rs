@@ -2,7 +2,7 @@ use itertools::Itertools;
 
 use crate::check::context::clss;
 use crate::check::context::clss::concrete_to_python;
-use crate::check::context::clss::python::{ANY, CALLABLE, TUPLE, UNION};
+use crate::check::context::clss::python::{ANY, CALLABLE, ITERATOR, TUPLE, UNION};
 use crate::check::name::string_name::StringName;
 use crate::check::name::true_name::TrueName;
 use crate::check::name::{Empty, Name, Nullable, Union};
@@ -47,6 +47,14 @@ impl ToPy for StringName {
                 .sorted()
                 .fold(Name::empty(), |acc, n| acc.union(n))
                 .to_py(imp),
+            clss::TUPLE if self.generics.is_empty() => {
+                imp.add_from_import("typing", TUPLE);
+                let empty = Core::Tuple { elements: vec![] };
+                Core::Type {
+                    lit: String::from(TUPLE),
+                    generics: vec![empty],
+                }
+            }
             clss::TUPLE => {
                 imp.add_from_import("typing", TUPLE);
                 core_type(TUPLE, &self.generics, imp)
@@ -60,9 +68,13 @@ impl ToPy for StringName {
             other => {
                 if other == clss::ANY {
                     imp.add_from_import("typing", ANY);
+                } else if other == clss::ITERATOR {
+                    imp.add_from_import("typing", ITERATOR);
                 }
 
-                let lit = concrete_to_python(&self.name);
+                let lit = imp
+                    .type_var(&self.name)
+                    .unwrap_or_else(|| concrete_to_python(&self.name));
                 core_type(&lit, &self.generics, imp)
             }
         }
@@ -4,6 +4,7 @@ use std::path::PathBuf;
 
 use crate::common::position::Position;
 use crate::common::result::{format_err, WithSource};
+use crate::common::version::PythonVersion;
 use crate::generate::ast::node::Core;
 use crate::ASTTy;
 
@@ -29,6 +30,17 @@ impl UnimplementedErr {
             path: None,
         }
     }
+
+    /// Construct which cannot be expressed in the targeted Python version.
+    pub fn unsupported(ast: &ASTTy, construct: &str, required: PythonVersion) -> UnimplementedErr {
+        let msg = format!("The {construct} construct requires at least Python {required}");
+        UnimplementedErr {
+            position: ast.pos,
+            msg,
+            source: None,
+            path: None,
+        }
+    }
 }
 
 impl WithSource for UnimplementedErr {
@@ -0,0 +1,296 @@
+use std::convert::TryFrom;
+
+use itertools::Itertools;
+
+use crate::check::context::arg::generic::GenericFunctionArg;
+use crate::check::context::arg::python::CLS;
+use crate::check::context::arg::SELF;
+use crate::check::context::clss::generic::GenericClass;
+use crate::check::context::clss::python::ANY;
+use crate::check::context::field::generic::{GenericField, GenericFields};
+use crate::check::context::function;
+use crate::check::context::function::generic::GenericFunction;
+use crate::check::context::function::python::INIT;
+use crate::check::name::{unscoped, Empty};
+use crate::check::result::TypeResult;
+use crate::common::position::Position;
+use crate::generate::ast::node::Core;
+use crate::generate::convert::class::class_level_fun;
+use crate::generate::convert::state::Imports;
+use crate::generate::name::ToPy;
+use crate::parse::ast::{Node, AST};
+
+/// Generate a Python stub file from the signatures of the top-level definitions of a file.
+///
+/// Bodies are replaced by `...`, and the errors a function may raise are given as a comment.
+pub fn gen_stub(ast: &AST) -> TypeResult<Core> {
+    let statements = match &ast.node {
+        Node::Block { statements } => statements.clone(),
+        _ => vec![ast.clone()],
+    };
+
+    let imp = &mut Imports::new();
+    let mut stubs = vec![];
+    for statement in &statements {
+        match &statement.node {
+            Node::Class { .. } | Node::TypeDef { .. } | Node::TypeAlias { .. } => {
+                stubs.push(class_stub(&GenericClass::try_from(statement)?, imp))
+            }
+            Node::FunDef { .. } => {
+                stubs.append(&mut fun_stub(&GenericFunction::try_from(statement)?, imp))
+            }
+            Node::VariableDef { .. } => {
+                let fields = GenericFields::try_from(statement)?.fields;
+                let fields = fields.iter().sorted_by_key(|f| by_pos(&f.pos));
+                stubs.append(&mut fields.map(|f| field_stub(f, imp)).collect())
+            }
+            Node::Import {
+                from,
+                import,
+                alias,
+            } => stubs.push(Core::Import {
+                from: from.as_ref().map(|from| Box::from(import_id(from))),
+                import: import.iter().map(import_id).collect(),
+                alias: alias.iter().map(import_id).collect(),
+            }),
+            _ => {}
+        }
+    }
+
+    Ok(Core::Block {
+        statements: imp.imports().into_iter().chain(stubs).collect(),
+    })
+}
+
+fn class_stub(class: &GenericClass, imp: &mut Imports) -> Core {
+    let generics: Vec<_> = class
+        .name
+        .generics
+        .iter()
+        .map(|g| (g.to_string(), None))
+        .collect();
+    imp.enter_type_vars(&generics);
+    let mut parent_names: Vec<Core> = class
+        .parents
+        .iter()
+        .sorted_by_key(|parent| by_pos(&parent.pos))
+        .map(|parent| parent.name.to_py(imp))
+        .collect();
+    if !class.name.generics.is_empty() {
+        imp.add_from_import("typing", "Generic");
+        parent_names.push(Core::Type {
+            lit: String::from("Generic"),
+            generics: class.name.generics.iter().map(|g| g.to_py(imp)).collect(),
+        });
+    }
+
+    let fields = class.fields.iter().sorted_by_key(|f| by_pos(&f.pos));
+    let mut statements: Vec<Core> = fields.map(|f| field_stub(f, imp)).collect();
+
+    if class.args.iter().any(|arg| arg.name != SELF) {
+        statements.push(Core::FunDef {
+            is_async: false,
+            dec: vec![],
+            id: String::from(INIT),
+            arg: class.args.iter().map(|arg| arg_stub(arg, imp)).collect(),
+            ty: Some(Box::from(Core::None)),
+            body: Box::from(Core::Ellipsis),
+        });
+    }
+
+    let functions = class.functions.iter().filter(|f| f.name.name != INIT);
+    for function in functions.sorted_by_key(|f| by_pos(&f.pos)) {
+        let mut stubs = fun_stub(function, imp);
+        if !function.arguments.iter().any(|arg| arg.name == SELF) {
+            let class_method = function
+                .arguments
+                .first()
+                .is_some_and(|arg| arg.name == CLS);
+            stubs = stubs
+                .iter()
+                .map(|stub| class_level_fun(stub, class_method))
+                .collect();
+        }
+        statements.append(&mut stubs);
+    }
+    imp.exit_type_vars();
+
+    Core::ClassDef {
+        dec: vec![],
+        name: Box::from(Core::Id {
+            lit: class.name.name.clone(),
+        }),
+        parent_names,
+        body: Box::from(if statements.is_empty() {
+            Core::Ellipsis
+        } else {
+            Core::Block { statements }
+        }),
+    }
+}
+
+/// A function stub, preceded by a comment if the function raises.
+fn fun_stub(function: &GenericFunction, imp: &mut Imports) -> Vec<Core> {
+    let generics: Vec<_> = function
+        .generics
+        .iter()
+        .map(|generic| {
+            let bound = generic.parent.as_ref().map(|parent| parent.to_py(imp));
+            (String::from(unscoped(&generic.name.name)), bound)
+        })
+        .collect();
+    imp.enter_type_vars(&generics);
+
+    let mut stubs = vec![];
+    if !function.raises.is_empty() {
+        let raises: Vec<String> = function
+            .raises
+            .names
+            .iter()
+            .sorted()
+            .map(|e| e.to_py(imp).to_string().trim_end().to_string())
+            .collect();
+        stubs.push(Core::Comment {
+            comment: format!("raises [{}]", raises.join(", ")),
+        })
+    }
+
+    stubs.push(Core::FunDef {
+        is_async: function.is_async,
+        dec: vec![],
+        id: function.name.name.clone(),
+        arg: function
+            .arguments
+            .iter()
+            .map(|arg| arg_stub(arg, imp))
+            .collect(),
+        ty: Some(Box::from(match &function.ret_ty {
+            Some(ret_ty) if function.is_generator => {
+                function::iterator(&function::yielded(ret_ty)).to_py(imp)
+            }
+            Some(ret_ty) => ret_ty.to_py(imp),
+            None => Core::None,
+        })),
+        body: Box::from(Core::Ellipsis),
+    });
+    imp.exit_type_vars();
+    stubs
+}
+
+fn arg_stub(arg: &GenericFunctionArg, imp: &mut Imports) -> Core {
+    Core::FunArg {
+        vararg: arg.vararg,
+        var: Box::from(Core::Id {
+            lit: arg.name.clone(),
+        }),
+        ty: match &arg.ty {
+            Some(ty) if arg.name != SELF && arg.name != CLS => Some(Box::from(ty.to_py(imp))),
+            _ => None,
+        },
+        default: if arg.has_default {
+            Some(Box::from(Core::Ellipsis))
+        } else {
+            None
+        },
+    }
+}
+
+fn field_stub(field: &GenericField, imp: &mut Imports) -> Core {
+    Core::ExpressionType {
+        expr: Box::from(Core::Id {
+            lit: field.name.clone(),
+        }),
+        ty: Box::from(match &field.ty {
+            Some(ty) => ty.to_py(imp),
+            None => {
+                imp.add_from_import("typing", ANY);
+                Core::Id {
+                    lit: String::from(ANY),
+                }
+            }
+        }),
+    }
+}
+
+fn import_id(ast: &AST) -> Core {
+    Core::Id {
+        lit: match &ast.node {
+            Node::Id { lit } => lit.clone(),
+            other => other.to_string(),
+        },
+    }
+}
+
+fn by_pos(pos: &Position) -> (usize, usize) {
+    (pos.start.line, pos.start.pos)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::generate::stub::gen_stub;
+    use crate::parse::ast::AST;
+
+    fn stub(source: &str) -> String {
+        let ast = source.parse::<AST>().expect("valid source");
+        format!("{}", gen_stub(&ast).expect("valid stub"))
+    }
+
+    #[test]
+    fn function_raises_comment() {
+        let stub = stub("def f(x: Int) -> Str raise [E] => pass\n");
+        let lines: Vec<&str> = stub.lines().map(str::trim_end).collect();
+        assert_eq!(lines[0], "# raises [E]");
+        assert_eq!(lines[1], "def f(x: int) -> str:");
+        assert_eq!(lines[2].trim(), "...");
+    }
+
+    #[test]
+    fn function_no_return_type_is_none() {
+        let stub = stub("def f(x: Int?) => pass\n");
+        assert!(stub.starts_with("from typing import Optional\n"));
+        assert!(stub.contains("def f(x: Optional[int]) -> None:"));
+    }
+
+    #[test]
+    fn generic_class_and_function() {
+        let source = "class Box[A](def item: A)\ndef g[T: Box](x: T) -> T => x\n";
+        let stub = stub(source);
+        assert!(stub.starts_with("from typing import Generic, TypeVar\n"));
+        assert!(stub.contains("A = TypeVar(\"A\")"));
+        assert!(stub.contains("T = TypeVar(\"T\", bound = \"Box\")"));
+        assert!(stub.contains("class Box(Generic[A]):"));
+        assert!(stub.contains("    item: A\n"));
+        assert!(stub.contains("def __init__(self, item: A) -> None:"));
+    }
+
+    #[test]
+    fn class_without_members_has_ellipsis_body() {
+        let stub = stub("class A\n    def f(self) => pass\nclass B\n");
+        assert!(stub.contains("def f(self) -> None:"));
+        assert!(stub.contains("class B:"));
+        assert_eq!(stub.trim_end().lines().last().unwrap().trim(), "...");
+    }
+
+    #[test]
+    fn untyped_top_level_field_is_any() {
+        let stub = stub("def a := 10\ndef b: Str := \"b\"\n");
+        assert!(stub.contains("a: Any\n"));
+        assert!(stub.contains("b: str\n"));
+    }
+
+    #[test]
+    fn static_and_class_method() {
+        let source = "class A\n    def f(x: Int) -> Int => x\n    def g(cls) -> A => cls()\n";
+        let stub = stub(source);
+        assert!(stub.contains("@staticmethod\n    def f(x: int) -> int:"));
+        assert!(stub.contains("@classmethod\n    def g(cls) -> A:"));
+    }
+
+    #[test]
+    fn generator_yields_element_of_iterator() {
+        let source = "def f() -> Int =>\n    yield 1\ndef g() -> Iterator[Int] =>\n    yield 1\n";
+        let stub = stub(source);
+        assert!(stub.contains("def f() -> Iterator[int]:"));
+        assert!(stub.contains("def g() -> Iterator[int]:"));
+    }
+}
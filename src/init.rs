@@ -0,0 +1,114 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::manifest::MANIFEST;
+use crate::{SOURCE, TARGET};
+
+const MODULE: &str = "greeting.mamba";
+const TEST: &str = "test_greeting.mamba";
+const GITIGNORE: &str = ".gitignore";
+
+const MODULE_SOURCE: &str = "\
+## Sample module of the project.
+
+def greet(name: Str) -> Str => return \"Hello, {name}!\"
+
+print(greet(\"world\"))
+";
+
+const TEST_SOURCE: &str = "\
+## Tests of the sample module, which can be run with pytest after transpiling.
+from greeting import greet
+
+def test_greet() raise [Exception] =>
+    if greet(\"world\") != \"Hello, world!\" then
+        raise Exception(\"Unexpected greeting\")
+";
+
+/// Create a new project in given directory.
+///
+/// If a name is given, the project is created in a new directory with that name.
+/// Otherwise, it is created in the directory itself.
+/// The project consists of a manifest, a `.gitignore` which ignores the output directory, and a
+/// source directory with a sample module and a test of that module.
+///
+/// Returns the project directory.
+/// Fails if a manifest already exists, and never overwrites existing files.
+pub fn init(dir: &Path, name: Option<&str>) -> Result<PathBuf, String> {
+    let project = match name {
+        Some(name) => dir.join(name),
+        None => dir.to_path_buf(),
+    };
+    if project.join(MANIFEST).exists() {
+        return Err(format!(
+            "Project already exists: {}",
+            project.join(MANIFEST).display()
+        ));
+    }
+
+    let src = project.join(SOURCE);
+    fs::create_dir_all(&src).map_err(|e| format!("{}: {}", e, src.display()))?;
+
+    let manifest = format!("source = \"{SOURCE}\"\ntarget = \"{TARGET}\"\n");
+    write_new(&project.join(MANIFEST), &manifest)?;
+    write_new(&project.join(GITIGNORE), &format!("/{TARGET}/\n"))?;
+    write_new(&src.join(MODULE), MODULE_SOURCE)?;
+    write_new(&src.join(TEST), TEST_SOURCE)?;
+
+    info!("Created project '{}'", project.display());
+    Ok(project)
+}
+
+/// Write file, unless it already exists.
+fn write_new(path: &Path, content: &str) -> Result<(), String> {
+    if path.exists() {
+        warn!("Keeping existing '{}'", path.display());
+        return Ok(());
+    }
+    fs::write(path, content).map_err(|e| format!("{}: {}", e, path.display()))
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use tempfile::tempdir;
+
+    use crate::init::init;
+    use crate::manifest::Manifest;
+    use crate::{transpile_dir, Arguments};
+
+    #[test]
+    fn init_named_project_transpiles() {
+        let dir = tempdir().expect("temp dir");
+        let project = init(dir.path(), Some("service")).unwrap();
+        assert_eq!(project, dir.path().join("service"));
+
+        let manifest = Manifest::from_dir(&project).unwrap().expect("manifest");
+        let (src, target) = (manifest.source.as_deref(), manifest.target.as_deref());
+        transpile_dir(&project, src, target, &Arguments::default()).unwrap();
+
+        assert!(project.join("target").join("greeting.py").is_file());
+        assert!(project.join("target").join("test_greeting.py").is_file());
+        let gitignore = fs::read_to_string(project.join(".gitignore")).unwrap();
+        assert_eq!(gitignore, "/target/\n");
+    }
+
+    #[test]
+    fn init_in_dir_keeps_existing_files() {
+        let dir = tempdir().expect("temp dir");
+        fs::write(dir.path().join(".gitignore"), "*.log\n").unwrap();
+
+        init(dir.path(), None).unwrap();
+        let gitignore = fs::read_to_string(dir.path().join(".gitignore")).unwrap();
+        assert_eq!(gitignore, "*.log\n");
+        assert!(dir.path().join("src").join("greeting.mamba").is_file());
+    }
+
+    #[test]
+    fn init_existing_project() {
+        let dir = tempdir().expect("temp dir");
+        init(dir.path(), None).unwrap();
+        assert!(init(dir.path(), None).is_err());
+    }
+}
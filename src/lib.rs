@@ -8,11 +8,20 @@ use std::convert::TryFrom;
 use std::fs::create_dir;
 use std::path::{Path, PathBuf};
 
+use rayon::prelude::*;
+use rayon::{ThreadPool, ThreadPoolBuilder};
+
+use crate::cache::{output_hash, Cache, Entry, Fingerprint};
 use crate::check::ast::ASTTy;
 use crate::check::check;
 use crate::check::context::Context;
-use crate::check::result::TypeErr;
+use crate::check::lint::{lint, Level, Lints};
+use crate::check::result::{TypeErr, TypeResult};
 use crate::common::result::WithSource;
+use crate::common::version::PythonVersion;
+use crate::dependency::{defines, uses};
+use crate::emit::{emit, Emit, EmitFormat};
+use crate::generate::stub::gen_stub;
 use crate::generate::{gen_arguments, GenArguments};
 use crate::parse::ast::AST;
 
@@ -22,14 +31,38 @@ pub mod check;
 pub mod generate;
 pub mod parse;
 
+pub mod emit;
+pub mod init;
 pub mod io;
+pub mod manifest;
+pub mod repl;
+pub mod run;
+pub mod watch;
+
+mod cache;
+mod dependency;
 
 const TARGET: &str = "target";
 const SOURCE: &str = "src";
 
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct Arguments {
     pub annotate: bool,
+    pub stubs: bool,
+    /// Drop the build cache, so that all files are rebuilt.
+    pub clean: bool,
+    /// Number of threads used to process files, or one per logical core if 0.
+    pub jobs: usize,
+    /// Python version targeted by the output, if any.
+    pub python: Option<PythonVersion>,
+    /// Directories with Python stubs, relative to the project directory.
+    pub stub_paths: Vec<PathBuf>,
+    pub lints: Lints,
+    /// Mark each statement in the output with its line in the source, see [run].
+    pub source_map: bool,
+    /// Stages of which a dump is written next to the output.
+    pub emit: Vec<Emit>,
+    pub emit_format: EmitFormat,
 }
 
 #[cfg(test)]
@@ -51,12 +84,128 @@ mod test_util {
 /// Output directory structure reflects input directory structure.
 /// If no output given, target directory created in current directory and output
 /// stored here.
+///
+/// A build cache is kept in the output directory.
+/// Files which are unchanged, and which use no definitions which changed, are not rebuilt.
 pub fn transpile_dir(
     dir: &Path,
     src: Option<&str>,
     target: Option<&str>,
     arguments: &Arguments,
 ) -> Result<PathBuf, Vec<String>> {
+    let (src_path, out_dir) = io_paths(dir, src, target)?;
+    let (in_absolute_paths, out_absolute_paths): (Vec<PathBuf>, Vec<PathBuf>) =
+        source_files(&src_path, &out_dir)?.into_iter().unzip();
+
+    info!(
+        "Transpiling {} file {}",
+        out_absolute_paths.len(),
+        if out_absolute_paths.len() > 1 {
+            "s"
+        } else {
+            ""
+        }
+    );
+
+    let mut sources = vec![];
+    for source_path in in_absolute_paths.clone() {
+        let source = io::read_source(&source_path).map_err(|error| vec![error])?;
+        sources.push(source);
+    }
+
+    let source_pairs = sources.iter().zip(in_absolute_paths.iter());
+    let source: Vec<(String, Option<PathBuf>)> = source_pairs
+        .map(|(source, path)| (source.clone(), Some(display_path(path.clone(), &src_path))))
+        .collect();
+
+    if arguments.clean {
+        Cache::clean(&out_dir).map_err(|error| vec![error])?;
+    }
+    let cache = Cache::load(&out_dir, arguments);
+
+    let mut pipeline_arg = PipelineArguments::from(arguments);
+    pipeline_arg.stub_paths = (arguments.stub_paths.iter())
+        .map(|path| dir.join(path))
+        .collect();
+
+    let pool = thread_pool(arguments.jobs)?;
+    let asts = pool.install(|| parse(&source))?;
+    let ctx = context(&asts, &pipeline_arg)?;
+
+    let keys: Vec<String> = (out_absolute_paths.iter())
+        .map(|path| path.strip_prefix(&out_dir).unwrap_or(path))
+        .map(|path| path.to_string_lossy().to_string())
+        .collect();
+    let fingerprints: Vec<(String, Fingerprint)> = pool.install(|| {
+        (keys.par_iter().zip(&asts).zip(&source))
+            .zip(&out_absolute_paths)
+            .map(|(((key, ast), (src, _)), out_path)| {
+                (key.clone(), fingerprint(ast, src, out_path, arguments))
+            })
+            .collect()
+    });
+    let stale = cache.stale(&fingerprints);
+    info!(
+        "{} of {} files unchanged",
+        stale.iter().filter(|stale| !**stale).count(),
+        stale.len()
+    );
+
+    let outputs: Vec<_> = pool.install(|| {
+        (asts.par_iter().zip(&source).zip(&out_absolute_paths))
+            .zip(&stale)
+            .map(|(((ast, source), out_path), stale)| {
+                if !stale {
+                    return Ok(None);
+                }
+                let ast_ty = check_and_emit(ast, &ctx, &source.0, out_path, &pipeline_arg)?;
+                generate(ast, ast_ty, &ctx, source, &pipeline_arg).map(Some)
+            })
+            .collect()
+    });
+    let (outputs, errs): (Vec<_>, Vec<_>) = outputs.into_iter().partition(Result::is_ok);
+
+    let errs: Vec<String> = errs.into_iter().flat_map(Result::unwrap_err).collect();
+    if !errs.is_empty() {
+        return Err(errs);
+    }
+
+    let mut new_cache = Cache::default();
+    let outputs = outputs.into_iter().map(Result::unwrap);
+    for ((output, out_path), (key, fingerprint)) in
+        outputs.zip(out_absolute_paths).zip(fingerprints)
+    {
+        let output_hash = match output {
+            Some(output) => {
+                write_output(&output, &out_path).map_err(|error| vec![error])?;
+                output_hash(&output.python, output.stub.as_deref())
+            }
+            None => match cache.get(&key) {
+                Some(entry) => entry.output,
+                None => continue,
+            },
+        };
+        let entry = Entry {
+            source: fingerprint.source,
+            signature: fingerprint.signature,
+            output: output_hash,
+            defines: fingerprint.defines,
+        };
+        new_cache.insert(&key, entry);
+    }
+    new_cache
+        .save(&out_dir, arguments)
+        .map_err(|error| vec![error])?;
+
+    Ok(out_dir)
+}
+
+/// Get the source path and output directory, creating the output directory if it does not exist.
+fn io_paths(
+    dir: &Path,
+    src: Option<&str>,
+    target: Option<&str>,
+) -> Result<(PathBuf, PathBuf), Vec<String>> {
     let src_path = src.map_or(dir.join(SOURCE), |p| dir.join(p));
     if !src_path.is_file() && !src_path.is_dir() {
         let msg = format!(
@@ -78,94 +227,144 @@ pub fn transpile_dir(
     }
     info!("Input is '{}'", src_path.display());
     info!("Output will be stored in '{}'", out_dir.display());
+    Ok((src_path, out_dir))
+}
 
-    let relative_paths = io::relative_files(src_path.as_path()).map_err(|error| vec![error])?;
-    let in_absolute_paths = if src_path.is_dir() {
-        relative_paths
-            .iter()
-            .map(|os_string| src_path.join(os_string))
-            .collect()
-    } else {
-        vec![src_path.clone()]
-    };
-    let out_absolute_paths: Vec<PathBuf> = relative_paths
+/// Pair each `*.mamba` file under the source path with its (extensionless) output path.
+fn source_files(src_path: &Path, out_dir: &Path) -> Result<Vec<(PathBuf, PathBuf)>, Vec<String>> {
+    let relative_paths = io::relative_files(src_path).map_err(|error| vec![error])?;
+    Ok(relative_paths
         .iter()
-        .map(|os_string| out_dir.join(os_string))
-        .collect();
-
-    info!(
-        "Transpiling {} file {}",
-        out_absolute_paths.len(),
-        if out_absolute_paths.len() > 1 {
-            "s"
-        } else {
-            ""
-        }
-    );
-
-    let mut sources = vec![];
-    for source_path in in_absolute_paths.clone() {
-        let source = io::read_source(&source_path).map_err(|error| vec![error])?;
-        sources.push(source);
-    }
-
-    let source_pairs = sources.iter().zip(in_absolute_paths.iter());
-    let source_option_pairs: Vec<_> = source_pairs
-        .map(|(source, path)| (source.clone(), Some(path.clone())))
-        .collect();
-
-    let pipeline_arg = PipelineArguments::from(arguments);
-    let mamba_source = mamba_to_python(source_option_pairs.as_slice(), &src_path, &pipeline_arg)?;
+        .map(|os_string| {
+            let in_path = if src_path.is_dir() {
+                src_path.join(os_string)
+            } else {
+                src_path.to_path_buf()
+            };
+            (in_path, out_dir.join(os_string))
+        })
+        .collect())
+}
 
-    for (source, out_path) in mamba_source.iter().zip(out_absolute_paths) {
-        let out_path = out_path.with_extension("py");
-        io::write_source(source, &out_path).map_err(|error| vec![error])?;
+/// Write Python source, and stub if present, to given output path with the appropriate extension.
+fn write_output(output: &PipelineOutput, out_path: &Path) -> Result<(), String> {
+    io::write_source(&output.python, &out_path.with_extension("py"))?;
+    if let Some(stub) = &output.stub {
+        io::write_source(stub, &out_path.with_extension("pyi"))?;
     }
-
-    Ok(out_dir)
+    Ok(())
 }
 
 pub struct PipelineArguments {
     pub annotate: bool,
+    pub stubs: bool,
+    pub jobs: usize,
+    pub python: Option<PythonVersion>,
+    pub stub_paths: Vec<PathBuf>,
+    pub lints: Lints,
+    pub source_map: bool,
+    pub emit: Vec<Emit>,
+    pub emit_format: EmitFormat,
 }
 
 impl From<&Arguments> for PipelineArguments {
     fn from(arguments: &Arguments) -> Self {
         PipelineArguments {
             annotate: arguments.annotate,
+            stubs: arguments.stubs,
+            jobs: arguments.jobs,
+            python: arguments.python,
+            stub_paths: arguments.stub_paths.clone(),
+            lints: arguments.lints.clone(),
+            source_map: arguments.source_map,
+            emit: arguments.emit.clone(),
+            emit_format: arguments.emit_format,
         }
     }
 }
 
+/// Output of the pipeline for a single source.
+#[derive(Debug)]
+struct PipelineOutput {
+    python: String,
+    stub: Option<String>,
+}
+
 /// Convert mamba source to python source.
 ///
 /// For each mamba source, a path can optionally be given for display in error
 /// messages. This path is not necessary however.
 pub fn mamba_to_python(
     source: &[(String, Option<PathBuf>)],
-    source_dir: &PathBuf,
+    source_dir: &Path,
     pipeline_args: &PipelineArguments,
 ) -> Result<Vec<String>, Vec<String>> {
-    // Strip until source
-    let strip_prefix = |p: PathBuf| {
-        p.strip_prefix(source_dir)
-            .map(|p| {
-                PathBuf::from(&source_dir.iter().last().unwrap_or_else(|| "".as_ref())).join(p)
-            })
-            .unwrap_or(p)
-    };
+    let output = pipeline(source, source_dir, pipeline_args)?;
+    Ok(output.into_iter().map(|output| output.python).collect())
+}
+
+/// Run the pipeline, which also generates a stub for each source if so specified.
+fn pipeline(
+    source: &[(String, Option<PathBuf>)],
+    source_dir: &Path,
+    pipeline_args: &PipelineArguments,
+) -> Result<Vec<PipelineOutput>, Vec<String>> {
     let source: Vec<(String, Option<PathBuf>)> = source
         .iter()
-        .map(|(src, dir)| (src.clone(), dir.clone().map(strip_prefix)))
+        .map(|(src, dir)| {
+            let dir = dir.clone().map(|dir| display_path(dir, source_dir));
+            (src.clone(), dir)
+        })
         .collect();
 
-    let (asts, parse_errs): (Vec<_>, Vec<_>) = source
-        .iter()
+    let pool = thread_pool(pipeline_args.jobs)?;
+    let asts = pool.install(|| parse(&source))?;
+    let ctx = context(&asts, pipeline_args)?;
+
+    let outputs: Vec<_> = pool.install(|| {
+        (asts.par_iter().zip(&source))
+            .map(|(ast, source)| generate(ast, check(ast, &ctx), &ctx, source, pipeline_args))
+            .collect()
+    });
+    let (outputs, errs): (Vec<_>, Vec<_>) = outputs.into_iter().partition(Result::is_ok);
+
+    let errs: Vec<String> = errs.into_iter().flat_map(Result::unwrap_err).collect();
+    if !errs.is_empty() {
+        return Err(errs);
+    }
+
+    let outputs: Vec<PipelineOutput> = outputs.into_iter().map(Result::unwrap).collect();
+    trace!("Converted {} files to Python source", outputs.len());
+    Ok(outputs)
+}
+
+/// Thread pool for the stages which process each file independently.
+///
+/// Results are collected in the order of the sources, so output and diagnostics are deterministic.
+fn thread_pool(jobs: usize) -> Result<ThreadPool, Vec<String>> {
+    ThreadPoolBuilder::new()
+        .num_threads(jobs)
+        .build()
+        .map_err(|err| vec![format!("Unable to create thread pool: {err}")])
+}
+
+/// Context of all files, the Python primitives and standard library, and given stubs.
+fn context(asts: &[AST], pipeline_args: &PipelineArguments) -> Result<Context, Vec<String>> {
+    Context::try_from(asts)
+        .and_then(|ctx| ctx.into_with_stubs(&pipeline_args.stub_paths))
+        .map_err(|errs| errs.iter().map(|e| format!("{e}")).collect())
+}
+
+/// Parse each source, returning all errors if any source could not be parsed.
+fn parse(source: &[(String, Option<PathBuf>)]) -> Result<Vec<AST>, Vec<String>> {
+    let asts: Vec<_> = source
+        .par_iter()
         .map(|(src, path)| {
             src.parse::<AST>()
                 .map_err(|err| err.with_source(&Some(src.clone()), &path.clone()))
         })
-        .partition(Result::is_ok);
+        .collect();
+    let (asts, parse_errs): (Vec<_>, Vec<_>) = asts.into_iter().partition(Result::is_ok);
 
     let parse_errs: Vec<_> = parse_errs.into_iter().map(Result::unwrap_err).collect();
     if !parse_errs.is_empty() {
@@ -174,54 +373,103 @@ pub fn mamba_to_python(
 
     let asts: Vec<AST> = asts.into_iter().map(Result::unwrap).collect();
     trace!("Parsed {} files", asts.len());
+    Ok(asts)
+}
 
-    let ctx = Context::try_from(asts.as_ref())
-        .map_err(|errs| errs.iter().map(|e| format!("{e}")).collect::<Vec<String>>())?;
-    let (typed_ast, type_errs): (Vec<_>, Vec<_>) = asts
-        .iter()
-        .zip(&source)
-        .map(|(ast, (src, path))| {
-            check(ast, &ctx).map_err(|errs| {
-                errs.iter()
-                    .map(|err| err.clone().with_source(&Some(src.clone()), &path.clone()))
-                    .collect()
-            })
+/// Fingerprint of a source file, used to determine whether it must be rebuilt.
+///
+/// The stub of a file serves as its signature, as it contains all exported definitions.
+fn fingerprint(ast: &AST, source: &str, out_path: &Path, arguments: &Arguments) -> Fingerprint {
+    let signature = match gen_stub(ast) {
+        Ok(stub) => cache::hash(&format!("{stub}")),
+        Err(_) => cache::hash(source),
+    };
+
+    let python = io::read_source(&out_path.with_extension("py")).ok();
+    let output = match (python, arguments.stubs) {
+        (Some(python), true) => io::read_source(&out_path.with_extension("pyi"))
+            .ok()
+            .map(|stub| output_hash(&python, Some(&stub))),
+        (Some(python), false) => Some(output_hash(&python, None)),
+        (None, _) => None,
+    };
+
+    Fingerprint {
+        source: cache::hash(source),
+        signature,
+        output,
+        defines: defines(ast),
+        uses: uses(source),
+    }
+}
+
+/// Strip path until source directory, which is kept, for display in error messages.
+fn display_path(path: PathBuf, source_dir: &Path) -> PathBuf {
+    path.strip_prefix(source_dir)
+        .map(|p| {
+            PathBuf::from(&source_dir.iter().next_back().unwrap_or_else(|| "".as_ref())).join(p)
         })
-        .partition(Result::is_ok);
+        .unwrap_or(path)
+}
 
-    let type_errs: Vec<Vec<TypeErr>> = type_errs.into_iter().map(Result::unwrap_err).collect();
-    if !type_errs.is_empty() {
-        return Err(type_errs
-            .iter()
-            .flatten()
-            .map(|err| format!("{err}"))
-            .collect());
+/// Check a single file, and write a dump of each stage to emit next to its output path.
+///
+/// Errors of the check are part of the result, and only writing a dump fails.
+fn check_and_emit(
+    ast: &AST,
+    ctx: &Context,
+    source: &str,
+    out_path: &Path,
+    pipeline_args: &PipelineArguments,
+) -> Result<TypeResult<ASTTy>, Vec<String>> {
+    if pipeline_args.emit.is_empty() {
+        Ok(check(ast, ctx))
+    } else {
+        emit(ast, ctx, source, out_path, pipeline_args).map_err(|error| vec![error])
     }
-    let typed_ast = typed_ast
-        .into_iter()
-        .map(Result::unwrap)
-        .collect::<Vec<ASTTy>>();
+}
 
-    trace!("Checked {} files", typed_ast.len());
+/// Generate a single file from the result of its check, given a context containing the definitions
+/// of all files.
+fn generate(
+    ast: &AST,
+    ast_ty: TypeResult<ASTTy>,
+    ctx: &Context,
+    (src, path): &(String, Option<PathBuf>),
+    pipeline_args: &PipelineArguments,
+) -> Result<PipelineOutput, Vec<String>> {
+    let with_source = |errs: &[TypeErr]| -> Vec<String> {
+        errs.iter()
+            .map(|err| err.clone().with_source(&Some(src.clone()), &path.clone()))
+            .map(|err| format!("{err}"))
+            .collect()
+    };
 
-    let gen_args = GenArguments::from(pipeline_args);
-    let (py_sources, gen_errs): (Vec<_>, Vec<_>) = typed_ast
-        .iter()
-        .zip(&source)
-        .map(|(ast_ty, (src, path))| {
-            gen_arguments(ast_ty, &gen_args, &ctx)
-                .map_err(|err| err.with_source(&Some(src.clone()), &path.clone()))
-                .map(|core| format!("{core}"))
-        })
-        .partition(Result::is_ok);
+    let ast_ty: ASTTy = ast_ty.map_err(|errs| with_source(&errs))?;
 
-    let gen_errs: Vec<_> = gen_errs.into_iter().map(Result::unwrap_err).collect();
-    if !gen_errs.is_empty() {
-        return Err(gen_errs.iter().map(|err| format!("{err}")).collect());
+    let mut denied = vec![];
+    for (level, err) in lint(ast, &pipeline_args.lints) {
+        let msg = format!("{}", err.with_source(&Some(src.clone()), path));
+        match level {
+            Level::Deny => denied.push(msg),
+            _ => warn!("{msg}"),
+        }
     }
+    if !denied.is_empty() {
+        return Err(denied);
+    }
+
+    let python = gen_arguments(&ast_ty, &GenArguments::from(pipeline_args), ctx)
+        .map_err(|err| vec![format!("{}", err.with_source(&Some(src.clone()), path))])?;
 
-    let py_sources: Vec<String> = py_sources.into_iter().map(Result::unwrap).collect();
-    trace!("Converted {} files to Python source", py_sources.len());
+    let stub = if pipeline_args.stubs {
+        Some(gen_stub(ast).map_err(|errs| with_source(&errs))?)
+    } else {
+        None
+    };
 
-    Ok(py_sources)
+    Ok(PipelineOutput {
+        python: format!("{python}"),
+        stub: stub.map(|stub| format!("{stub}")),
+    })
 }
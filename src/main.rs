@@ -8,6 +8,13 @@ extern crate loggerv;
 use clap::App;
 use itertools::Itertools;
 
+use mamba::common::version::PythonVersion;
+use mamba::emit::{Emit, EmitFormat};
+use mamba::init::init;
+use mamba::manifest::Manifest;
+use mamba::repl::repl;
+use mamba::run::{run, INTERPRETER};
+use mamba::watch::watch_dir;
 use mamba::{transpile_dir, Arguments};
 
 const VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -18,9 +25,6 @@ pub fn main() -> Result<(), String> {
 
     let yaml = load_yaml!("cli.yml");
     let matches = App::from_yaml(yaml).version(VERSION).get_matches();
-    let in_path = matches.value_of("input");
-    let out_path = matches.value_of("output");
-
     loggerv::Logger::new()
         .verbosity(matches.occurrences_of("v"))
         .level(matches.is_present("level"))
@@ -30,8 +34,20 @@ pub fn main() -> Result<(), String> {
         .init()
         .unwrap();
 
-    let arguments = Arguments {
-        annotate: matches.is_present("annotate"),
+    let jobs = match matches.value_of("jobs") {
+        Some(jobs) => jobs
+            .parse::<usize>()
+            .map_err(|err| format!("Invalid number of jobs '{jobs}': {err}"))?,
+        None => 0,
+    };
+
+    let emit = match matches.values_of("emit") {
+        Some(stages) => stages.map(str::parse::<Emit>).collect::<Result<_, _>>()?,
+        None => vec![],
+    };
+    let emit_format = match matches.value_of("emit-format") {
+        Some(format) => format.parse::<EmitFormat>()?,
+        None => EmitFormat::default(),
     };
 
     info!("Mamba 🐍 {}", VERSION);
@@ -40,13 +56,84 @@ pub fn main() -> Result<(), String> {
         format!("Error while finding current directory: {err}")
     })?;
 
-    transpile_dir(&current_dir, in_path, out_path, &arguments)
-        .map_err(|errors| {
-            errors.iter().unique().for_each(|msg| eprintln!("{msg}"));
-            match errors.first() {
-                Some(msg) => msg.clone(),
-                None => String::new(),
-            }
-        })
-        .map(|_| ())
+    if let Some(init_matches) = matches.subcommand_matches("init") {
+        return init(&current_dir, init_matches.value_of("name")).map(|_| ());
+    }
+
+    // Command line arguments override the manifest
+    let manifest = Manifest::from_dir(&current_dir)?.unwrap_or_default();
+    let in_path = matches.value_of("input").or(manifest.source.as_deref());
+    let out_path = matches.value_of("output").or(manifest.target.as_deref());
+    let python = match matches.value_of("python") {
+        Some(python) => Some(python.parse::<PythonVersion>()?),
+        None => manifest.python,
+    };
+
+    let annotate = if matches.is_present("annotate") {
+        true
+    } else if matches.is_present("no-annotate") {
+        false
+    } else {
+        manifest.annotate.unwrap_or_default()
+    };
+
+    let arguments = Arguments {
+        annotate,
+        stubs: matches.is_present("stubs"),
+        clean: matches.is_present("clean"),
+        jobs,
+        python,
+        stub_paths: manifest.stub_paths,
+        lints: manifest.lints,
+        source_map: false,
+        emit,
+        emit_format,
+    };
+
+    if let Some(repl_matches) = matches.subcommand_matches("repl") {
+        let interpreter = (repl_matches.value_of("interpreter"))
+            .or(manifest.interpreter.as_deref())
+            .unwrap_or(INTERPRETER);
+        return repl(interpreter, &arguments).map_err(report);
+    }
+
+    if let Some(run_matches) = matches.subcommand_matches("run") {
+        let module = run_matches.value_of("module").unwrap_or_default();
+        let args: Vec<String> = match run_matches.values_of("args") {
+            Some(args) => args.map(String::from).collect(),
+            None => vec![],
+        };
+        let interpreter = (run_matches.value_of("interpreter"))
+            .or(manifest.interpreter.as_deref())
+            .unwrap_or(INTERPRETER);
+
+        return match run(
+            &current_dir,
+            in_path,
+            module,
+            &args,
+            interpreter,
+            &arguments,
+        ) {
+            Ok(code) => std::process::exit(code),
+            Err(errors) => Err(report(errors)),
+        };
+    }
+
+    let result = if matches.is_present("watch") {
+        watch_dir(&current_dir, in_path, out_path, &arguments)
+    } else {
+        transpile_dir(&current_dir, in_path, out_path, &arguments).map(|_| ())
+    };
+
+    result.map_err(report)
+}
+
+/// Print errors, and return the first as the error of the process.
+fn report(errors: Vec<String>) -> String {
+    errors.iter().unique().for_each(|msg| eprintln!("{msg}"));
+    match errors.first() {
+        Some(msg) => msg.clone(),
+        None => String::new(),
+    }
 }
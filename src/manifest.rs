@@ -0,0 +1,159 @@
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use toml::value::Table;
+use toml::Value;
+
+use crate::check::lint::{Level, Lints};
+use crate::common::version::PythonVersion;
+use crate::io;
+
+pub const MANIFEST: &str = "mamba.toml";
+
+/// Project manifest, which gives defaults for the command line arguments.
+///
+/// ```toml
+/// source = "src"
+/// target = "target"
+/// python = "3.10"
+/// stub-paths = ["stubs"]
+/// annotate = true
+/// interpreter = "python3.10"
+///
+/// [lint]
+/// unused-import = "deny"
+/// ```
+///
+/// All keys are optional.
+#[derive(Debug, Default, PartialEq)]
+pub struct Manifest {
+    pub source: Option<String>,
+    pub target: Option<String>,
+    pub python: Option<PythonVersion>,
+    /// Directories with Python stubs whose definitions are added to the context.
+    pub stub_paths: Vec<PathBuf>,
+    pub annotate: Option<bool>,
+    /// Python interpreter used by `mamba run` and `mamba repl`.
+    pub interpreter: Option<String>,
+    pub lints: Lints,
+}
+
+impl Manifest {
+    /// Read manifest in given directory, if present.
+    pub fn from_dir(dir: &Path) -> Result<Option<Manifest>, String> {
+        let path = dir.join(MANIFEST);
+        if !path.is_file() {
+            return Ok(None);
+        }
+
+        let source = io::read_source(&path)?;
+        let manifest = source
+            .parse::<Manifest>()
+            .map_err(|err| format!("{err}: {}", path.display()))?;
+        Ok(Some(manifest))
+    }
+}
+
+impl FromStr for Manifest {
+    type Err = String;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        let table = match source.parse::<Value>() {
+            Ok(Value::Table(table)) => table,
+            Ok(_) => return Err(String::from("Expected table in manifest")),
+            Err(err) => return Err(format!("Malformed manifest: {err}")),
+        };
+
+        let mut manifest = Manifest::default();
+        for (key, value) in &table {
+            match key.as_str() {
+                "source" => manifest.source = Some(string(key, value)?),
+                "target" => manifest.target = Some(string(key, value)?),
+                "interpreter" => manifest.interpreter = Some(string(key, value)?),
+                "python" => manifest.python = Some(string(key, value)?.parse()?),
+                "annotate" => {
+                    let annotate = value.as_bool().ok_or_else(|| expected(key, "boolean"))?;
+                    manifest.annotate = Some(annotate);
+                }
+                "stub-paths" => {
+                    let paths = value.as_array().ok_or_else(|| expected(key, "array"))?;
+                    for path in paths {
+                        manifest.stub_paths.push(PathBuf::from(string(key, path)?));
+                    }
+                }
+                "lint" => {
+                    let lints = value.as_table().ok_or_else(|| expected(key, "table"))?;
+                    manifest.lints = lints_from(lints)?;
+                }
+                other => return Err(format!("Unknown key in manifest: '{other}'")),
+            }
+        }
+
+        Ok(manifest)
+    }
+}
+
+fn lints_from(table: &Table) -> Result<Lints, String> {
+    let mut lints = Lints::default();
+    for (lint, level) in table {
+        lints.set(lint, string(lint, level)?.parse::<Level>()?)?;
+    }
+    Ok(lints)
+}
+
+fn string(key: &str, value: &Value) -> Result<String, String> {
+    value
+        .as_str()
+        .map(String::from)
+        .ok_or_else(|| expected(key, "string"))
+}
+
+fn expected(key: &str, expected: &str) -> String {
+    format!("Expected {expected} for '{key}' in manifest")
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    use crate::check::lint::Level;
+    use crate::common::version::PythonVersion;
+    use crate::manifest::Manifest;
+
+    #[test]
+    fn all_keys() {
+        let source = "source = \"lib\"\ntarget = \"build\"\npython = \"3.11\"\n\
+        stub-paths = [\"stubs\", \"vendor\"]\nannotate = true\ninterpreter = \"python3.10\"\n\n[lint]\nunused-import = \"deny\"\n";
+        let manifest = source.parse::<Manifest>().unwrap();
+
+        assert_eq!(manifest.source, Some(String::from("lib")));
+        assert_eq!(manifest.target, Some(String::from("build")));
+        assert_eq!(manifest.python, Some(PythonVersion::new(3, 11)));
+        assert_eq!(
+            manifest.stub_paths,
+            vec![PathBuf::from("stubs"), PathBuf::from("vendor")]
+        );
+        assert_eq!(manifest.annotate, Some(true));
+        assert_eq!(manifest.interpreter, Some(String::from("python3.10")));
+        assert_eq!(manifest.lints.unused_import, Level::Deny);
+    }
+
+    #[test]
+    fn empty_is_default() {
+        assert_eq!("".parse::<Manifest>().unwrap(), Manifest::default());
+    }
+
+    #[test]
+    fn unknown_key() {
+        assert!("sauce = \"src\"\n".parse::<Manifest>().is_err());
+    }
+
+    #[test]
+    fn wrong_type() {
+        assert!("annotate = \"yes\"\n".parse::<Manifest>().is_err());
+        assert!("python = \"three\"\n".parse::<Manifest>().is_err());
+        assert!("[lint]\nunused-import = \"never\"\n"
+            .parse::<Manifest>()
+            .is_err());
+    }
+}
@@ -1,5 +1,7 @@
 use std::fmt::Debug;
 
+use serde::Serialize;
+
 use crate::check::context::arg;
 use crate::check::context::function::python::INIT;
 use crate::common::position::Position;
@@ -10,7 +12,7 @@ pub mod node_op;
 
 /// Wrapper of Node, and its start end end position in the source code.
 /// The start and end positions can be used to generate useful error messages.
-#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+#[derive(PartialEq, Eq, Hash, Clone, Debug, Serialize)]
 pub struct AST {
     pub pos: Position,
     pub node: Node,
@@ -36,7 +38,7 @@ impl AST {
 
 pub type OptAST = Option<Box<AST>>;
 
-#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Serialize)]
 pub enum Node {
     Import {
         from: Option<Box<AST>>,
@@ -44,6 +46,7 @@ pub enum Node {
         alias: Vec<AST>,
     },
     Class {
+        decorators: Vec<AST>,
         ty: Box<AST>,
         args: Vec<AST>,
         parents: Vec<AST>,
@@ -71,12 +74,21 @@ pub enum Node {
     },
     FunDef {
         pure: bool,
+        is_async: bool,
+        decorators: Vec<AST>,
         id: Box<AST>,
+        generics: Vec<AST>,
         args: Vec<AST>,
         ret: OptAST,
         raises: Vec<AST>,
         body: OptAST,
     },
+    Property {
+        id: Box<AST>,
+        ty: Box<AST>,
+        getter: Box<AST>,
+        setter: OptAST,
+    },
     AnonFun {
         args: Vec<AST>,
         body: Box<AST>,
@@ -89,10 +101,14 @@ pub enum Node {
         cases: Vec<AST>,
     },
     With {
+        is_async: bool,
         resource: Box<AST>,
         alias: Option<(Box<AST>, bool, Option<Box<AST>>)>,
         expr: Box<AST>,
     },
+    Await {
+        expr: Box<AST>,
+    },
     FunctionCall {
         name: Box<AST>,
         args: Vec<AST>,
@@ -101,6 +117,10 @@ pub enum Node {
         instance: Box<AST>,
         property: Box<AST>,
     },
+    SafeCall {
+        instance: Box<AST>,
+        property: Box<AST>,
+    },
     Id {
         lit: String,
     },
@@ -194,9 +214,8 @@ pub enum Node {
     Int {
         lit: String,
     },
-    ENum {
-        num: String,
-        exp: String,
+    Imaginary {
+        lit: String,
     },
     Str {
         lit: String,
@@ -327,9 +346,25 @@ pub enum Node {
     },
     Case {
         cond: Box<AST>,
+        guard: OptAST,
         body: Box<AST>,
     },
+    PatternOr {
+        patterns: Vec<AST>,
+    },
+    PatternClass {
+        ty: Box<AST>,
+        fields: Vec<AST>,
+    },
+    PatternField {
+        id: Box<AST>,
+        pattern: Box<AST>,
+    },
+    PatternRest {
+        id: Box<AST>,
+    },
     For {
+        is_async: bool,
         expr: Box<AST>,
         col: Box<AST>,
         body: Box<AST>,
@@ -348,6 +383,12 @@ pub enum Node {
         expr: Box<AST>,
     },
     ReturnEmpty,
+    Yield {
+        expr: Box<AST>,
+    },
+    YieldFrom {
+        expr: Box<AST>,
+    },
     Underscore,
     Pass,
     Question {
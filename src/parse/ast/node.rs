@@ -1,3 +1,4 @@
+use std::cell::Cell;
 use std::fmt::{Display, Error, Formatter};
 
 use crate::common::delimit::comma_delm;
@@ -12,6 +13,10 @@ fn equal_optional(this: &Option<Box<AST>>, that: &Option<Box<AST>>) -> bool {
     }
 }
 
+fn optional(ast: &Option<Box<AST>>) -> Vec<&AST> {
+    ast.iter().map(|ast| ast.as_ref()).collect()
+}
+
 fn equal_vec(this: &[AST], other: &[AST]) -> bool {
     if this.len() != other.len() {
         false
@@ -43,10 +48,12 @@ impl Display for Node {
             }
             Node::VariableDef { .. } => String::from("variable definition"),
             Node::FunDef { .. } => String::from("function definition"),
+            Node::Property { .. } => String::from("property"),
             Node::AnonFun { .. } => String::from("anonymous function"),
             Node::Raise { .. } => String::from("raise"),
             Node::Handle { .. } => String::from("handle"),
             Node::With { .. } => String::from("with"),
+            Node::Await { .. } => String::from("await"),
             Node::FunctionCall { name, args } => {
                 format!(
                     "{}({})",
@@ -57,6 +64,9 @@ impl Display for Node {
             Node::PropertyCall { instance, property } => {
                 format!("{}.{}", instance.node, property.node)
             }
+            Node::SafeCall { instance, property } => {
+                format!("{}?.{}", instance.node, property.node)
+            }
             Node::Id { lit } => lit.clone(),
             Node::ExpressionType { .. } => String::from("expression type"),
             Node::TypeDef { .. } => String::from("type definition"),
@@ -120,7 +130,7 @@ impl Display for Node {
             Node::Block { .. } => String::from("Code block"),
             Node::Real { lit } => lit.clone(),
             Node::Int { lit } => lit.clone(),
-            Node::ENum { num, exp } => format!("{num}E{exp}"),
+            Node::Imaginary { lit } => lit.clone(),
             Node::Str { lit, .. } => format!("\"{lit}\""),
             Node::DocStr { .. } => String::from("doc string"),
             Node::Add { left, right } => format!("{} + {}", left.node, right.node),
@@ -155,12 +165,18 @@ impl Display for Node {
             Node::IfElse { el, .. } => String::from(if el.is_some() { "if" } else { "if else" }),
             Node::Match { .. } => String::from("match"),
             Node::Case { .. } => String::from("case"),
+            Node::PatternOr { .. } => String::from("or pattern"),
+            Node::PatternClass { ty, .. } => format!("{} pattern", ty.node),
+            Node::PatternField { id, .. } => format!("{} field pattern", id.node),
+            Node::PatternRest { id } => format!("{}{}", Token::Mul, id.node),
             Node::For { .. } => String::from("for loop"),
             Node::In { left, right } => format!("{} {} {}", left.node, Token::In, right.node),
             Node::While { .. } => String::from("while loop"),
             Node::Break => format!("{}", Token::Break),
             Node::Continue => format!("{}", Token::Continue),
             Node::Return { .. } | Node::ReturnEmpty => String::from("return"),
+            Node::Yield { .. } => String::from("yield"),
+            Node::YieldFrom { .. } => String::from("yield from"),
             Node::Underscore => format!("{}", Token::Underscore),
             Node::Pass => format!("{}", Token::Pass),
             Node::Question { .. } => String::from("ternary operator"),
@@ -186,11 +202,13 @@ impl Node {
                 alias: alias.iter().map(|a| a.map(mapping)).collect(),
             },
             Node::Class {
+                decorators,
                 ty,
                 args,
                 parents,
                 body,
             } => Node::Class {
+                decorators: decorators.iter().map(|d| d.map(mapping)).collect(),
                 ty: Box::from(ty.map(mapping)),
                 args: args.iter().map(|a| a.map(mapping)).collect(),
                 parents: parents.iter().map(|p| p.map(mapping)).collect(),
@@ -224,19 +242,36 @@ impl Node {
             },
             Node::FunDef {
                 pure,
+                is_async,
+                decorators,
                 id,
+                generics,
                 args: fun_args,
                 ret: ret_ty,
                 raises,
                 body,
             } => Node::FunDef {
                 pure,
+                is_async,
+                decorators: decorators.iter().map(|d| d.map(mapping)).collect(),
                 id: Box::from(id.map(mapping)),
+                generics: generics.iter().map(|g| g.map(mapping)).collect(),
                 args: fun_args.iter().map(|a| a.map(mapping)).collect(),
                 ret: ret_ty.map(|r| Box::from(r.map(mapping))),
                 raises: raises.iter().map(|r| r.map(mapping)).collect(),
                 body: body.map(|b| Box::from(b.map(mapping))),
             },
+            Node::Property {
+                id,
+                ty,
+                getter,
+                setter,
+            } => Node::Property {
+                id: Box::from(id.map(mapping)),
+                ty: Box::from(ty.map(mapping)),
+                getter: Box::from(getter.map(mapping)),
+                setter: setter.map(|s| Box::from(s.map(mapping))),
+            },
             Node::AnonFun { args, body } => Node::AnonFun {
                 args: args.iter().map(|a| a.map(mapping)).collect(),
                 body: Box::from(body.map(mapping)),
@@ -252,10 +287,12 @@ impl Node {
                 cases: cases.iter().map(|c| c.map(mapping)).collect(),
             },
             Node::With {
+                is_async,
                 resource,
                 alias,
                 expr,
             } => Node::With {
+                is_async,
                 resource: Box::from(resource.map(mapping)),
                 alias: alias.map(|(resource, alias, expr)| {
                     (
@@ -266,6 +303,9 @@ impl Node {
                 }),
                 expr: Box::from(expr.map(mapping)),
             },
+            Node::Await { expr } => Node::Await {
+                expr: Box::from(expr.map(mapping)),
+            },
             Node::FunctionCall { name, args } => Node::FunctionCall {
                 name: Box::from(name.map(mapping)),
                 args: args.iter().map(|a| a.map(mapping)).collect(),
@@ -274,6 +314,10 @@ impl Node {
                 instance: Box::from(instance.map(mapping)),
                 property: Box::from(property.map(mapping)),
             },
+            Node::SafeCall { instance, property } => Node::SafeCall {
+                instance: Box::from(instance.map(mapping)),
+                property: Box::from(property.map(mapping)),
+            },
             Node::ExpressionType { expr, mutable, ty } => Node::ExpressionType {
                 expr: Box::from(expr.map(mapping)),
                 mutable,
@@ -475,11 +519,32 @@ impl Node {
                 cond: Box::from(cond.map(mapping)),
                 cases: cases.iter().map(|c| c.map(mapping)).collect(),
             },
-            Node::Case { cond, body } => Node::Case {
+            Node::Case { cond, guard, body } => Node::Case {
                 cond: Box::from(cond.map(mapping)),
+                guard: guard.map(|guard| Box::from(guard.map(mapping))),
                 body: Box::from(body.map(mapping)),
             },
-            Node::For { expr, col, body } => Node::For {
+            Node::PatternOr { patterns } => Node::PatternOr {
+                patterns: patterns.iter().map(|p| p.map(mapping)).collect(),
+            },
+            Node::PatternClass { ty, fields } => Node::PatternClass {
+                ty: Box::from(ty.map(mapping)),
+                fields: fields.iter().map(|f| f.map(mapping)).collect(),
+            },
+            Node::PatternField { id, pattern } => Node::PatternField {
+                id: Box::from(id.map(mapping)),
+                pattern: Box::from(pattern.map(mapping)),
+            },
+            Node::PatternRest { id } => Node::PatternRest {
+                id: Box::from(id.map(mapping)),
+            },
+            Node::For {
+                is_async,
+                expr,
+                col,
+                body,
+            } => Node::For {
+                is_async,
                 expr: Box::from(expr.map(mapping)),
                 col: Box::from(col.map(mapping)),
                 body: Box::from(body.map(mapping)),
@@ -495,6 +560,12 @@ impl Node {
             Node::Return { expr } => Node::Return {
                 expr: Box::from(expr.map(mapping)),
             },
+            Node::Yield { expr } => Node::Yield {
+                expr: Box::from(expr.map(mapping)),
+            },
+            Node::YieldFrom { expr } => Node::YieldFrom {
+                expr: Box::from(expr.map(mapping)),
+            },
             Node::Question { left, right } => Node::Question {
                 left: Box::from(left.map(mapping)),
                 right: Box::from(right.map(mapping)),
@@ -507,6 +578,220 @@ impl Node {
         }
     }
 
+    /// Direct children of node, in the order in which they appear in the source.
+    pub fn children(&self) -> Vec<&AST> {
+        match self {
+            Node::Import {
+                from,
+                import,
+                alias,
+            } => optional(from)
+                .into_iter()
+                .chain(import)
+                .chain(alias)
+                .collect(),
+            Node::Class {
+                decorators,
+                ty,
+                args,
+                parents,
+                body,
+            } => (decorators
+                .iter()
+                .chain([ty.as_ref()])
+                .chain(args)
+                .chain(parents))
+            .chain(optional(body))
+            .collect(),
+            Node::Generic { id, isa } => [id.as_ref()].into_iter().chain(optional(isa)).collect(),
+            Node::Parent { ty, args } => [ty.as_ref()].into_iter().chain(args).collect(),
+            Node::VariableDef {
+                var,
+                ty,
+                expr,
+                forward,
+                ..
+            } => ([var.as_ref()]
+                .into_iter()
+                .chain(optional(ty))
+                .chain(optional(expr)))
+            .chain(forward)
+            .collect(),
+            Node::FunDef {
+                decorators,
+                id,
+                generics,
+                args,
+                ret,
+                raises,
+                body,
+                ..
+            } => (decorators
+                .iter()
+                .chain([id.as_ref()])
+                .chain(generics)
+                .chain(args))
+            .chain(optional(ret))
+            .chain(raises)
+            .chain(optional(body))
+            .collect(),
+            Node::Property {
+                id,
+                ty,
+                getter,
+                setter,
+            } => [id.as_ref(), ty, getter]
+                .into_iter()
+                .chain(optional(setter))
+                .collect(),
+            Node::AnonFun { args, body } => args.iter().chain([body.as_ref()]).collect(),
+            Node::Handle {
+                expr_or_stmt,
+                cases,
+            } => [expr_or_stmt.as_ref()].into_iter().chain(cases).collect(),
+            Node::With {
+                resource,
+                alias,
+                expr,
+                ..
+            } => {
+                let alias = match alias {
+                    Some((alias, _, ty)) => {
+                        [alias.as_ref()].into_iter().chain(optional(ty)).collect()
+                    }
+                    None => vec![],
+                };
+                [resource.as_ref()]
+                    .into_iter()
+                    .chain(alias)
+                    .chain([expr.as_ref()])
+                    .collect()
+            }
+            Node::FunctionCall { name, args } => [name.as_ref()].into_iter().chain(args).collect(),
+            Node::PropertyCall { instance, property } | Node::SafeCall { instance, property } => {
+                vec![instance, property]
+            }
+            Node::ExpressionType { expr, ty, .. } => {
+                [expr.as_ref()].into_iter().chain(optional(ty)).collect()
+            }
+            Node::TypeDef { ty, isa, body } => [ty.as_ref()]
+                .into_iter()
+                .chain(optional(isa))
+                .chain(optional(body))
+                .collect(),
+            Node::TypeAlias {
+                ty,
+                isa,
+                conditions,
+            } => [ty.as_ref(), isa].into_iter().chain(conditions).collect(),
+            Node::TypeTup { types } | Node::TypeUnion { types } => types.iter().collect(),
+            Node::Type { id, generics } => [id.as_ref()].into_iter().chain(generics).collect(),
+            Node::TypeFun { args, ret_ty } => args.iter().chain([ret_ty.as_ref()]).collect(),
+            Node::Condition { cond, el } => {
+                [cond.as_ref()].into_iter().chain(optional(el)).collect()
+            }
+            Node::FunArg {
+                var, ty, default, ..
+            } => [var.as_ref()]
+                .into_iter()
+                .chain(optional(ty))
+                .chain(optional(default))
+                .collect(),
+            Node::Dict { elements } => elements.iter().flat_map(|(k, v)| [k, v]).collect(),
+            Node::DictBuilder {
+                from,
+                to,
+                conditions,
+            } => [from.as_ref(), to].into_iter().chain(conditions).collect(),
+            Node::SetBuilder { item, conditions } | Node::ListBuilder { item, conditions } => {
+                [item.as_ref()].into_iter().chain(conditions).collect()
+            }
+            Node::Set { elements }
+            | Node::List { elements }
+            | Node::Tuple { elements }
+            | Node::Block {
+                statements: elements,
+            }
+            | Node::Str {
+                expressions: elements,
+                ..
+            }
+            | Node::PatternOr { patterns: elements } => elements.iter().collect(),
+            Node::Range { from, to, step, .. } | Node::Slice { from, to, step, .. } => {
+                [from.as_ref(), to]
+                    .into_iter()
+                    .chain(optional(step))
+                    .collect()
+            }
+            Node::Index { item, range } => vec![item, range],
+            Node::Reassign { left, right, .. }
+            | Node::Add { left, right }
+            | Node::Sub { left, right }
+            | Node::Mul { left, right }
+            | Node::Div { left, right }
+            | Node::FDiv { left, right }
+            | Node::Mod { left, right }
+            | Node::Pow { left, right }
+            | Node::BAnd { left, right }
+            | Node::BOr { left, right }
+            | Node::BXOr { left, right }
+            | Node::BLShift { left, right }
+            | Node::BRShift { left, right }
+            | Node::Le { left, right }
+            | Node::Ge { left, right }
+            | Node::Leq { left, right }
+            | Node::Geq { left, right }
+            | Node::Is { left, right }
+            | Node::IsN { left, right }
+            | Node::Eq { left, right }
+            | Node::Neq { left, right }
+            | Node::IsA { left, right }
+            | Node::IsNA { left, right }
+            | Node::And { left, right }
+            | Node::Or { left, right }
+            | Node::In { left, right }
+            | Node::Question { left, right } => vec![left, right],
+            Node::Raise { error: expr }
+            | Node::Await { expr }
+            | Node::AddU { expr }
+            | Node::SubU { expr }
+            | Node::Sqrt { expr }
+            | Node::BOneCmpl { expr }
+            | Node::Not { expr }
+            | Node::Return { expr }
+            | Node::Yield { expr }
+            | Node::YieldFrom { expr }
+            | Node::QuestionOp { expr }
+            | Node::PatternRest { id: expr } => vec![expr],
+            Node::IfElse { cond, then, el } => [cond.as_ref(), then]
+                .into_iter()
+                .chain(optional(el))
+                .collect(),
+            Node::Match { cond, cases } => [cond.as_ref()].into_iter().chain(cases).collect(),
+            Node::Case { cond, guard, body } => [cond.as_ref()]
+                .into_iter()
+                .chain(optional(guard))
+                .chain([body.as_ref()])
+                .collect(),
+            Node::PatternClass { ty, fields } => [ty.as_ref()].into_iter().chain(fields).collect(),
+            Node::PatternField { id, pattern } => vec![id, pattern],
+            Node::For {
+                expr, col, body, ..
+            } => vec![expr, col, body],
+            Node::While { cond, body } => vec![cond, body],
+            Node::Id { .. }
+            | Node::Real { .. }
+            | Node::Int { .. }
+            | Node::Imaginary { .. }
+            | Node::DocStr { .. }
+            | Node::Break
+            | Node::Continue
+            | Node::ReturnEmpty
+            | Node::Underscore
+            | Node::Pass => vec![],
+        }
+    }
+
     pub fn same_value(&self, other: &Node) -> bool {
         match (&self, &other) {
             (
@@ -523,19 +808,22 @@ impl Node {
             ) => lf == rf && equal_vec(li, ri) && equal_vec(la, ra),
             (
                 Node::Class {
+                    decorators: ld,
                     ty: lt,
                     args: la,
                     parents: lp,
                     body: lb,
                 },
                 Node::Class {
+                    decorators: rd,
                     ty: rt,
                     args: ra,
                     parents: rp,
                     body: rb,
                 },
             ) => {
-                lt.same_value(rt)
+                equal_vec(ld, rd)
+                    && lt.same_value(rt)
                     && equal_vec(la, ra)
                     && equal_vec(lp, rp)
                     && equal_optional(lb, rb)
@@ -583,7 +871,10 @@ impl Node {
             (
                 Node::FunDef {
                     pure: lpu,
+                    is_async: lasync,
+                    decorators: ld,
                     id: li,
+                    generics: lg,
                     args: la,
                     ret: lret,
                     raises: lraise,
@@ -591,7 +882,10 @@ impl Node {
                 },
                 Node::FunDef {
                     pure: rpu,
+                    is_async: rasync,
+                    decorators: rd,
                     id: ri,
+                    generics: rg,
                     args: ra,
                     ret: rret,
                     raises: rraise,
@@ -599,12 +893,34 @@ impl Node {
                 },
             ) => {
                 lpu == rpu
+                    && lasync == rasync
+                    && equal_vec(ld, rd)
                     && li.same_value(ri)
+                    && equal_vec(lg, rg)
                     && equal_vec(la, ra)
                     && equal_optional(lret, rret)
                     && equal_vec(lraise, rraise)
                     && equal_optional(lb, rb)
             }
+            (
+                Node::Property {
+                    id: li,
+                    ty: lt,
+                    getter: lg,
+                    setter: ls,
+                },
+                Node::Property {
+                    id: ri,
+                    ty: rt,
+                    getter: rg,
+                    setter: rs,
+                },
+            ) => {
+                li.same_value(ri)
+                    && lt.same_value(rt)
+                    && lg.same_value(rg)
+                    && equal_optional(ls, rs)
+            }
             (Node::AnonFun { args: la, body: lb }, Node::AnonFun { args: ra, body: rb }) => {
                 equal_vec(la, ra) && lb.same_value(rb)
             }
@@ -621,17 +937,20 @@ impl Node {
             ) => les.same_value(res) && equal_vec(lc, rc),
             (
                 Node::With {
+                    is_async: las,
                     resource: lr,
                     alias: Some((la, lmut, lty)),
                     expr: le,
                 },
                 Node::With {
+                    is_async: ras,
                     resource: rr,
                     alias: Some((ra, rmut, rty)),
                     expr: re,
                 },
             ) => {
-                lr.same_value(rr)
+                las == ras
+                    && lr.same_value(rr)
                     && la.same_value(ra)
                     && lmut == rmut
                     && equal_optional(lty, rty)
@@ -639,16 +958,19 @@ impl Node {
             }
             (
                 Node::With {
+                    is_async: las,
                     resource: lr,
                     alias: None,
                     expr: le,
                 },
                 Node::With {
+                    is_async: ras,
                     resource: rr,
                     alias: None,
                     expr: re,
                 },
-            ) => lr.same_value(rr) && le.same_value(re),
+            ) => las == ras && lr.same_value(rr) && le.same_value(re),
+            (Node::Await { expr: le }, Node::Await { expr: re }) => le.same_value(re),
             (
                 Node::FunctionCall { name: ln, args: la },
                 Node::FunctionCall { name: rn, args: ra },
@@ -663,6 +985,16 @@ impl Node {
                     property: rp,
                 },
             ) => li.same_value(ri) && lp.same_value(rp),
+            (
+                Node::SafeCall {
+                    instance: li,
+                    property: lp,
+                },
+                Node::SafeCall {
+                    instance: ri,
+                    property: rp,
+                },
+            ) => li.same_value(ri) && lp.same_value(rp),
             (Node::Id { lit: l }, Node::Id { lit: r }) => l == r,
             (
                 Node::ExpressionType {
@@ -787,9 +1119,7 @@ impl Node {
             (Node::Block { statements: l }, Node::Block { statements: r }) => equal_vec(l, r),
             (Node::Real { lit: l }, Node::Real { lit: r }) => l == r,
             (Node::Int { lit: l }, Node::Int { lit: r }) => l == r,
-            (Node::ENum { num: ln, exp: le }, Node::ENum { num: rn, exp: re }) => {
-                ln == rn && le == re
-            }
+            (Node::Imaginary { lit: l }, Node::Imaginary { lit: r }) => l == r,
             (
                 Node::Str {
                     lit: l,
@@ -1068,21 +1398,50 @@ impl Node {
                     cases: rc,
                 },
             ) => lco.same_value(rco) && equal_vec(lc, rc),
-            (Node::Case { cond: lc, body: lb }, Node::Case { cond: rc, body: rb }) => {
-                lc.same_value(rc) && lb.same_value(rb)
+            (
+                Node::Case {
+                    cond: lc,
+                    guard: lg,
+                    body: lb,
+                },
+                Node::Case {
+                    cond: rc,
+                    guard: rg,
+                    body: rb,
+                },
+            ) => lc.same_value(rc) && equal_optional(lg, rg) && lb.same_value(rb),
+            (Node::PatternOr { patterns: lp }, Node::PatternOr { patterns: rp }) => {
+                equal_vec(lp, rp)
             }
+            (
+                Node::PatternClass { ty: lt, fields: lf },
+                Node::PatternClass { ty: rt, fields: rf },
+            ) => lt.same_value(rt) && equal_vec(lf, rf),
+            (
+                Node::PatternField {
+                    id: li,
+                    pattern: lp,
+                },
+                Node::PatternField {
+                    id: ri,
+                    pattern: rp,
+                },
+            ) => li.same_value(ri) && lp.same_value(rp),
+            (Node::PatternRest { id: li }, Node::PatternRest { id: ri }) => li.same_value(ri),
             (
                 Node::For {
+                    is_async: las,
                     expr: le,
                     col: lc,
                     body: lb,
                 },
                 Node::For {
+                    is_async: ras,
                     expr: re,
                     col: rc,
                     body: rb,
                 },
-            ) => le.same_value(re) && lc.same_value(rc) && lb.same_value(rb),
+            ) => las == ras && le.same_value(re) && lc.same_value(rc) && lb.same_value(rb),
             (
                 Node::In {
                     left: ll,
@@ -1097,6 +1456,10 @@ impl Node {
                 lc.same_value(rc) && lb.same_value(rb)
             }
             (Node::Return { expr: left }, Node::Return { expr: right }) => left.same_value(right),
+            (Node::Yield { expr: left }, Node::Yield { expr: right }) => left.same_value(right),
+            (Node::YieldFrom { expr: left }, Node::YieldFrom { expr: right }) => {
+                left.same_value(right)
+            }
             (
                 Node::Question {
                     left: ll,
@@ -1116,6 +1479,70 @@ impl Node {
         }
     }
 
+    /// True if node contains a yield, ignoring nested functions and classes.
+    ///
+    /// A function whose body contains a yield is a generator.
+    pub fn contains_yield(&self) -> bool {
+        let found = Cell::new(false);
+        let _ = self.map(&|node| match node {
+            Node::FunDef { .. } | Node::AnonFun { .. } | Node::Class { .. } => Node::Pass,
+            Node::Yield { .. } | Node::YieldFrom { .. } => {
+                found.set(true);
+                node.clone()
+            }
+            _ => node.clone(),
+        });
+        found.get()
+    }
+
+    /// If node is a class which is a data class, whether it is frozen.
+    ///
+    /// A class is a data class if it has inline arguments which are all fields without mutable
+    /// defaults, has no parents, and does not define fields in its body.
+    /// It is frozen if all its fields are immutable.
+    pub fn data_class(&self) -> Option<bool> {
+        let Node::Class {
+            args,
+            parents,
+            body,
+            ..
+        } = self
+        else {
+            return None;
+        };
+        if args.is_empty() || !parents.is_empty() {
+            return None;
+        }
+
+        let mut frozen = true;
+        for arg in args {
+            let Node::VariableDef { mutable, expr, .. } = &arg.node else {
+                return None;
+            };
+            let immutable_default = expr.as_ref().map_or(true, |expr| match &expr.node {
+                Node::Int { .. } | Node::Real { .. } | Node::Imaginary { .. } => true,
+                Node::Str { expressions, .. } => expressions.is_empty(),
+                Node::Id { lit } => ["True", "False", "None"].contains(&lit.as_str()),
+                _ => false,
+            });
+            if !immutable_default {
+                return None;
+            }
+            frozen = frozen && !*mutable;
+        }
+
+        match body.as_ref().map(|body| &body.node) {
+            Some(Node::Block { statements })
+                if statements
+                    .iter()
+                    .any(|stmt| matches!(stmt.node, Node::VariableDef { .. })) =>
+            {
+                None
+            }
+            _ => Some(frozen),
+        }
+    }
+
     /// True if node is an expression with certainty.
     ///
     /// If False, then it might still be an expression if for instance it is a function call.
@@ -1124,6 +1551,7 @@ impl Node {
         match &self {
             Node::AnonFun { .. }
             | Node::PropertyCall { .. }
+            | Node::SafeCall { .. }
             | Node::Id { .. }
             | Node::Set { .. }
             | Node::SetBuilder { .. }
@@ -1135,7 +1563,7 @@ impl Node {
             | Node::Index { .. }
             | Node::Real { .. }
             | Node::Int { .. }
-            | Node::ENum { .. }
+            | Node::Imaginary { .. }
             | Node::Str { .. }
             | Node::Match { .. }
             | Node::Underscore
@@ -1259,6 +1687,7 @@ mod test {
     fn for_ast_map() {
         let pos = Position::new(CaretPos::new(3, 403), CaretPos::new(324, 673));
         let node = Node::For {
+            is_async: false,
             expr: Box::new(AST::new(
                 pos,
                 Node::Id {
@@ -1280,6 +1709,7 @@ mod test {
         };
 
         let new_node = Node::For {
+            is_async: false,
             expr: Box::new(AST::new(
                 pos,
                 Node::Id {
@@ -1457,6 +1887,7 @@ mod test {
     #[test]
     fn class_equal_value() {
         let node = Node::Class {
+            decorators: vec![],
             ty: Box::new(AST::new(Position::invisible(), Node::Continue)),
             args: vec![AST::new(Position::invisible(), Node::ReturnEmpty)],
             parents: vec![AST::new(Position::invisible(), Node::Pass)],
@@ -1512,7 +1943,10 @@ mod test {
         });
         two_ast!(Node::FunDef {
             pure: false,
+            is_async: false,
+            decorators: vec![],
             id: first.clone(),
+            generics: vec![*third.clone()],
             args: vec![*second.clone()],
             ret: Some(third.clone()),
             raises: vec![*first.clone(), *second.clone()],
@@ -1555,6 +1989,7 @@ mod test {
             expr_or_stmt: second.clone()
         });
         two_ast!(Node::With {
+            is_async: false,
             resource: first.clone(),
             alias: Some((second.clone(), false, Some(third.clone()))),
             expr: Box::from(AST::new(Position::invisible(), Node::Pass))
@@ -1574,6 +2009,10 @@ mod test {
             instance: first.clone(),
             property: second.clone()
         });
+        two_ast!(Node::SafeCall {
+            instance: first.clone(),
+            property: second.clone()
+        });
     }
 
     #[test]
@@ -1652,9 +2091,8 @@ mod test {
             lit: String::from("sdfdf")
         });
         two_ast!(Node::Id { lit: "True".to_string() });
-        two_ast!(Node::ENum {
-            num: String::from("werw"),
-            exp: String::from("reter")
+        two_ast!(Node::Imaginary {
+            lit: String::from("werw")
         });
         two_ast!(Node::Str {
             lit: String::from("yuk"),
@@ -1903,8 +2341,21 @@ mod test {
         });
         two_ast!(Node::Case {
             cond: cond.clone(),
+            guard: Some(third.clone()),
             body: body.clone()
         });
+        two_ast!(Node::PatternOr {
+            patterns: vec![*cond.clone(), *body.clone()]
+        });
+        two_ast!(Node::PatternClass {
+            ty: cond.clone(),
+            fields: vec![*body.clone(), *third.clone()]
+        });
+        two_ast!(Node::PatternField {
+            id: cond.clone(),
+            pattern: body.clone()
+        });
+        two_ast!(Node::PatternRest { id: cond.clone() });
         two_ast!(Node::Range {
             from: cond.clone(),
             to: body.clone(),
@@ -1912,6 +2363,7 @@ mod test {
             step: Some(third.clone())
         });
         two_ast!(Node::For {
+            is_async: false,
             expr: cond.clone(),
             col: body.clone(),
             body: third.clone()
@@ -1943,6 +2395,37 @@ mod test {
         });
     }
 
+    #[test]
+    fn yield_equal_value() {
+        two_ast!(Node::Yield {
+            expr: Box::from(AST::new(Position::invisible(), Node::Continue))
+        });
+        two_ast!(Node::YieldFrom {
+            expr: Box::from(AST::new(Position::invisible(), Node::Continue))
+        });
+    }
+
+    #[test]
+    fn contains_yield_ignores_nested_function() {
+        let yield_stmt = AST::new(
+            Position::invisible(),
+            Node::Yield {
+                expr: Box::from(AST::new(Position::invisible(), Node::Pass)),
+            },
+        );
+        let nested = AST::new(
+            Position::invisible(),
+            Node::AnonFun {
+                args: vec![],
+                body: Box::from(yield_stmt.clone()),
+            },
+        );
+
+        let block = |statements| Node::Block { statements };
+        assert!(block(vec![yield_stmt]).contains_yield());
+        assert!(!block(vec![nested]).contains_yield());
+    }
+
     #[test]
     fn question_equal_value() {
         two_ast!(Node::QuestionOp {
@@ -2027,6 +2510,11 @@ mod test {
             property: second.clone()
         }
         .is_expression());
+        assert!(Node::SafeCall {
+            instance: first.clone(),
+            property: second.clone()
+        }
+        .is_expression());
         assert!(Node::Id {
             lit: String::from("s")
         }
@@ -2068,9 +2556,8 @@ mod test {
             lit: String::from("3")
         }
         .is_expression());
-        assert!(Node::ENum {
-            num: String::from("4"),
-            exp: String::from("4")
+        assert!(Node::Imaginary {
+            lit: String::from("4j")
         }
         .is_expression());
         assert!(Node::Str {
@@ -1,9 +1,11 @@
 use std::fmt::{Display, Error, Formatter};
 
+use serde::Serialize;
+
 use crate::check::context::function::python::{ADD, DIV, EQ, FDIV, GE, LE, MOD, MUL, POW, SUB};
 use crate::check::context::function::SQRT;
 
-#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Serialize)]
 pub enum NodeOp {
     Assign,
     Add,
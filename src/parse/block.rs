@@ -1,6 +1,7 @@
 use crate::parse::ast::Node;
 use crate::parse::ast::AST;
 use crate::parse::class::{parse_class, parse_type_def};
+use crate::parse::definition::parse_decorated;
 use crate::parse::expr_or_stmt::parse_expr_or_stmt;
 use crate::parse::iterator::LexIterator;
 use crate::parse::lex::token::Token;
@@ -28,6 +29,10 @@ pub fn parse_statements(it: &mut LexIterator) -> ParseResult<Vec<AST>> {
                 statements.push(*it.parse(&parse_class, "file", start)?);
                 Ok(())
             }
+            Token::At => {
+                statements.push(*it.parse(&parse_decorated, "file", start)?);
+                Ok(())
+            }
             Token::DocStr(doc_str) => {
                 let end = it.eat(&Token::DocStr(doc_str.clone()), "statements")?;
                 let node = Node::DocStr {
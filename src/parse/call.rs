@@ -38,7 +38,22 @@ pub fn parse_call(pre: &AST, it: &mut LexIterator) -> ParseResult {
                     instance: Box::from(pre.clone()),
                     property: property.clone(),
                 };
-                Ok(Box::from(AST::new(pre.pos.union(property.pos), node)))
+                Ok(Box::from(safe_call_receiver(AST::new(
+                    pre.pos.union(property.pos),
+                    node,
+                ))))
+            }
+            Token::QuestionPoint => {
+                it.eat(&Token::QuestionPoint, "safe call")?;
+                let property = it.parse(&parse_inner_expression, "safe call", pre.pos)?;
+                let node = Node::SafeCall {
+                    instance: Box::from(pre.clone()),
+                    property: property.clone(),
+                };
+                Ok(Box::from(safe_call_receiver(AST::new(
+                    pre.pos.union(property.pos),
+                    node,
+                ))))
             }
             Token::LRBrack => {
                 it.eat(&Token::LRBrack, "direct call")?;
@@ -51,16 +66,54 @@ pub fn parse_call(pre: &AST, it: &mut LexIterator) -> ParseResult {
                 Ok(Box::from(AST::new(pre.pos.union(end), node)))
             }
             _ => Err(Box::from(expected_one_of(
-                &[Token::Point, Token::LRBrack],
+                &[Token::Point, Token::QuestionPoint, Token::LRBrack],
                 ast,
                 "function call",
             ))),
         },
-        &[Token::Point, Token::LRBrack],
+        &[Token::Point, Token::QuestionPoint, Token::LRBrack],
         "function call",
     )
 }
 
+/// Make the entire call before a safe call its receiver.
+///
+/// As properties are parsed right-associative, in `a.b?.c` the safe call is nested within the
+/// property of `a`.
+/// We move it outwards so that the instance of a safe call is what may be None.
+fn safe_call_receiver(ast: AST) -> AST {
+    let (instance, property, safe) = match &ast.node {
+        Node::PropertyCall { instance, property } => (instance, property, false),
+        Node::SafeCall { instance, property } => (instance, property, true),
+        _ => return ast,
+    };
+    let Node::SafeCall {
+        instance: inner,
+        property: inner_property,
+    } = &property.node
+    else {
+        return ast;
+    };
+
+    let receiver = if safe {
+        Node::SafeCall {
+            instance: instance.clone(),
+            property: inner.clone(),
+        }
+    } else {
+        Node::PropertyCall {
+            instance: instance.clone(),
+            property: inner.clone(),
+        }
+    };
+    let receiver = safe_call_receiver(AST::new(instance.pos.union(inner.pos), receiver));
+    let node = Node::SafeCall {
+        instance: Box::from(receiver),
+        property: inner_property.clone(),
+    };
+    AST::new(ast.pos, node)
+}
+
 fn parse_arguments(it: &mut LexIterator) -> ParseResult<Vec<AST>> {
     let start = it.start_pos("arguments")?;
     let mut arguments = vec![];
@@ -248,6 +301,71 @@ mod test {
         );
     }
 
+    #[test]
+    fn safe_call_verify() {
+        let source = String::from("a.b?.c");
+        let statements = parse_direct(&source).unwrap();
+
+        let (instance, property) = match &statements.first().expect("script empty.").node {
+            Node::SafeCall { instance, property } => (instance.clone(), property.clone()),
+            other => panic!("first element script was not safe call {:?}", other),
+        };
+
+        match &instance.node {
+            Node::PropertyCall { instance, property } => {
+                assert_eq!(
+                    instance.node,
+                    Node::Id {
+                        lit: String::from("a")
+                    }
+                );
+                assert_eq!(
+                    property.node,
+                    Node::Id {
+                        lit: String::from("b")
+                    }
+                );
+            }
+            other => panic!("safe call receiver not property call {:?}", other),
+        }
+        assert_eq!(
+            property.node,
+            Node::Id {
+                lit: String::from("c")
+            }
+        );
+    }
+
+    #[test]
+    fn nested_safe_call_verify() {
+        let source = String::from("a?.b?.c()");
+        let statements = parse_direct(&source).unwrap();
+
+        let (instance, property) = match &statements.first().expect("script empty.").node {
+            Node::SafeCall { instance, property } => (instance.clone(), property.clone()),
+            other => panic!("first element script was not safe call {:?}", other),
+        };
+
+        match &instance.node {
+            Node::SafeCall { instance, property } => {
+                assert_eq!(
+                    instance.node,
+                    Node::Id {
+                        lit: String::from("a")
+                    }
+                );
+                assert_eq!(
+                    property.node,
+                    Node::Id {
+                        lit: String::from("b")
+                    }
+                );
+            }
+            other => panic!("safe call receiver not safe call {:?}", other),
+        }
+        assert!(matches!(property.node, Node::FunctionCall { .. }));
+    }
+
     #[test]
     fn direct_call_missing_closing_bracket() {
         let source = String::from("a(b");
@@ -56,6 +56,7 @@ pub fn parse_class(it: &mut LexIterator) -> ParseResult {
     };
 
     let node = Node::Class {
+        decorators: vec![],
         ty,
         args,
         parents,
@@ -87,7 +88,7 @@ pub fn parse_parent(it: &mut LexIterator) -> ParseResult {
                     Token::Str(String::new(), vec![]),
                     Token::Int(String::new()),
                     Token::Real(String::new()),
-                    Token::ENum(String::new(), String::new()),
+                    Token::Imaginary(String::new()),
                 ],
                 lex,
                 "parent arguments",
@@ -302,6 +303,7 @@ mod test {
                     args,
                     parents,
                     body,
+                    ..
                 } => (ty.clone(), args.clone(), parents.clone(), body.clone()),
                 other => panic!("Was not class: {:?}.", other),
             },
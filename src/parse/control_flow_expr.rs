@@ -4,9 +4,9 @@ use crate::parse::expr_or_stmt::parse_expr_or_stmt;
 use crate::parse::iterator::LexIterator;
 use crate::parse::lex::token::Token;
 use crate::parse::operation::parse_expression;
+use crate::parse::pattern::parse_pattern;
 use crate::parse::result::expected_one_of;
 use crate::parse::result::ParseResult;
-use crate::parse::ty::parse_type;
 
 pub fn parse_cntrl_flow_expr(it: &mut LexIterator) -> ParseResult {
     it.peek_or_err(
@@ -77,34 +77,19 @@ pub fn parse_match_cases(it: &mut LexIterator) -> ParseResult<Vec<AST>> {
 
 fn parse_match_case(it: &mut LexIterator) -> ParseResult {
     let start = it.start_pos("match case")?;
-    let cond = it.parse(&parse_expression_maybe_type, "match case", start)?;
+    let cond = it.parse(&parse_pattern, "match case", start)?;
+    let guard = it.parse_if(&Token::If, &parse_expression, "match case guard", start)?;
     it.eat(&Token::BTo, "match case")?;
     let body = it.parse(&parse_expr_or_stmt, "match case", start)?;
 
     let node = Node::Case {
         cond,
+        guard,
         body: body.clone(),
     };
     Ok(Box::from(AST::new(start.union(body.pos), node)))
 }
 
-fn parse_expression_maybe_type(it: &mut LexIterator) -> ParseResult {
-    let start = it.start_pos("expression maybe type")?;
-    let mutable = it.eat_if(&Token::Fin).is_none();
-
-    let expr = it.parse(&parse_expression, "expression maybe type", start)?;
-    let ty = it.parse_if(
-        &Token::DoublePoint,
-        &parse_type,
-        "expression maybe type",
-        start,
-    )?;
-    let end = ty.clone().map_or(expr.pos, |t| t.pos);
-
-    let node = Node::ExpressionType { expr, mutable, ty };
-    Ok(Box::from(AST::new(start.union(end), node)))
-}
-
 #[cfg(test)]
 mod test {
     use crate::parse::ast::{Node, AST};
@@ -166,6 +151,7 @@ mod test {
                         Node::Case {
                             cond: cond1,
                             body: expr1,
+                            ..
                         },
                     ..
                 },
@@ -174,6 +160,7 @@ mod test {
                         Node::Case {
                             cond: cond2,
                             body: expr2,
+                            ..
                         },
                     ..
                 },
@@ -56,6 +56,7 @@ fn parse_for(it: &mut LexIterator) -> ParseResult {
     let body = it.parse(&parse_expr_or_stmt, "for statement", start)?;
 
     let node = Node::For {
+        is_async: false,
         expr,
         col,
         body: body.clone(),
@@ -76,7 +77,9 @@ mod test {
         let statements = parse_direct(&source).unwrap();
 
         let (expr, collection, body) = match &statements.first().expect("script empty.").node {
-            Node::For { expr, col, body } => (expr.clone(), col.clone(), body.clone()),
+            Node::For {
+                expr, col, body, ..
+            } => (expr.clone(), col.clone(), body.clone()),
             _ => panic!("first element script was not for."),
         };
 
@@ -106,7 +109,9 @@ mod test {
         let statements = parse_direct(&source).unwrap();
 
         let (expr, col, body) = match &statements.first().expect("script empty.").node {
-            Node::For { expr, col, body } => (expr.clone(), col.clone(), body.clone()),
+            Node::For {
+                expr, col, body, ..
+            } => (expr.clone(), col.clone(), body.clone()),
             _ => panic!("first element script was not foreach."),
         };
 
@@ -160,7 +165,9 @@ mod test {
         let statements = parse_direct(&source).unwrap();
 
         let (expr, col, body) = match &statements.first().expect("script empty.").node {
-            Node::For { expr, col, body } => (expr.clone(), col.clone(), body.clone()),
+            Node::For {
+                expr, col, body, ..
+            } => (expr.clone(), col.clone(), body.clone()),
             _ => panic!("first element script was not foreach."),
         };
 
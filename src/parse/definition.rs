@@ -1,16 +1,63 @@
 use crate::parse::ast::node_op::NodeOp;
 use crate::parse::ast::Node;
-use crate::parse::ast::AST;
+use crate::parse::ast::{OptAST, AST};
+use crate::parse::class::parse_class;
 use crate::parse::expr_or_stmt::parse_expr_or_stmt;
 use crate::parse::iterator::LexIterator;
 use crate::parse::lex::token::Token;
 use crate::parse::operation::parse_expression;
 use crate::parse::result::custom;
+use crate::parse::result::expected_one_of;
 use crate::parse::result::ParseResult;
+use crate::parse::statement::parse_async;
 use crate::parse::ty::parse_expression_type;
+use crate::parse::ty::parse_generics;
 use crate::parse::ty::parse_id;
 use crate::parse::ty::parse_type;
 
+const GET: &str = "get";
+const SET: &str = "set";
+
+/// Parse decorators, followed by the function or class which they decorate.
+pub fn parse_decorated(it: &mut LexIterator) -> ParseResult {
+    let start = it.start_pos("decorator")?;
+    let mut decorators = vec![];
+    while it.eat_if(&Token::At).is_some() {
+        decorators.push(*it.parse(&parse_expression, "decorator", start)?);
+        it.eat(&Token::NL, "decorator")?;
+        it.eat_while(&Token::NL);
+    }
+
+    let expected = [Token::At, Token::Def, Token::Async, Token::Class];
+    let ast = it.peek_or_err(
+        &|it, lex| match lex.token {
+            Token::Def => parse_definition(it),
+            Token::Async => parse_async(it),
+            Token::Class => parse_class(it),
+            _ => Err(Box::from(expected_one_of(&expected, lex, "decorator"))),
+        },
+        &expected,
+        "decorator",
+    )?;
+
+    let mut node = ast.node;
+    match &mut node {
+        Node::FunDef {
+            decorators: decs, ..
+        }
+        | Node::Class {
+            decorators: decs, ..
+        } => *decs = decorators,
+        _ => {
+            return Err(Box::from(custom(
+                "Only a function or class can be decorated",
+                ast.pos,
+            )))
+        }
+    }
+    Ok(Box::from(AST::new(start.union(ast.pos), node)))
+}
+
 pub fn parse_definition(it: &mut LexIterator) -> ParseResult {
     let start = it.start_pos("definition")?;
     it.eat(&Token::Def, "definition")?;
@@ -84,7 +131,7 @@ fn parse_var_or_fun_def(it: &mut LexIterator, pure: bool) -> ParseResult {
         }
         Node::ExpressionType { expr, ty, mutable } if ty.is_none() => it.peek(
             &|it, lex| match lex.token {
-                Token::LRBrack => parse_fun_def(&id, pure, it),
+                Token::LRBrack | Token::LSBrack => parse_fun_def(&id, pure, it),
                 _ if !pure => parse_variable_def_id(&id, it),
                 _ => {
                     let msg = format!("Definition cannot have {} identifier", Token::Pure);
@@ -111,6 +158,13 @@ fn parse_var_or_fun_def(it: &mut LexIterator, pure: bool) -> ParseResult {
 
 fn parse_fun_def(id: &AST, pure: bool, it: &mut LexIterator) -> ParseResult {
     let start = it.start_pos("function definition")?;
+    let generics = if it.eat_if(&Token::LSBrack).is_some() {
+        let generics = it.parse_vec(&parse_generics, "function generics", start)?;
+        it.eat(&Token::RSBrack, "function generics")?;
+        generics
+    } else {
+        vec![]
+    };
     let fun_args = it.parse_vec(&parse_fun_args, "function definition", start)?;
 
     let id = match &id.node {
@@ -147,6 +201,9 @@ fn parse_fun_def(id: &AST, pure: bool, it: &mut LexIterator) -> ParseResult {
     let node = Node::FunDef {
         id,
         pure,
+        is_async: false,
+        decorators: vec![],
+        generics,
         args: fun_args,
         ret: ret_ty,
         raises,
@@ -155,6 +212,112 @@ fn parse_fun_def(id: &AST, pure: bool, it: &mut LexIterator) -> ParseResult {
     Ok(Box::from(AST::new(start.union(end), node)))
 }
 
+/// Parse a property, which has a getter and optionally a setter.
+///
+/// The getter and setter are desugared to function definitions, where the getter takes an
+/// immutable `self` and the setter takes `self` and the new value.
+pub fn parse_property(it: &mut LexIterator) -> ParseResult {
+    let start = it.start_pos("property")?;
+    it.eat(&Token::Property, "property")?;
+    let id = it.parse(&parse_id, "property", start)?;
+    it.eat(&Token::DoublePoint, "property")?;
+    let ty = it.parse(&parse_type, "property", start)?;
+
+    let self_arg = |mutable: bool| {
+        let node = Node::FunArg {
+            vararg: false,
+            mutable,
+            var: Box::from(AST::new(id.pos, Node::new_self())),
+            ty: None,
+            default: None,
+        };
+        AST::new(id.pos, node)
+    };
+    let fun_def = |args: Vec<AST>, ret: OptAST, body: AST| {
+        let node = Node::FunDef {
+            id: id.clone(),
+            pure: false,
+            is_async: false,
+            decorators: vec![],
+            generics: vec![],
+            args,
+            ret,
+            raises: vec![],
+            body: Some(Box::from(body.clone())),
+        };
+        AST::new(id.pos.union(body.pos), node)
+    };
+
+    if it.eat_if(&Token::BTo).is_some() {
+        let body = it.parse(&parse_expr_or_stmt, "property getter", start)?;
+        let getter = fun_def(vec![self_arg(false)], Some(ty.clone()), *body);
+        let node = Node::Property {
+            id: id.clone(),
+            ty,
+            getter: Box::from(getter.clone()),
+            setter: None,
+        };
+        return Ok(Box::from(AST::new(start.union(getter.pos), node)));
+    }
+
+    it.eat_while(&Token::NL);
+    it.eat(&Token::Indent, "property")?;
+    it.eat_while(&Token::NL);
+    let get = Token::Id(String::from(GET));
+    if !it.peek_if(&|lex| lex.token == get) {
+        let msg = format!("Expected '{GET}' in property");
+        return Err(Box::from(custom(&msg, it.start_pos("property getter")?)));
+    }
+    let get_pos = it.eat(&get, "property getter")?;
+    it.eat(&Token::BTo, "property getter")?;
+    let body = it.parse(&parse_expr_or_stmt, "property getter", start)?;
+    let getter = fun_def(vec![self_arg(false)], Some(ty.clone()), *body);
+    it.eat_while(&Token::NL);
+
+    let setter = if it.peek_if(&|lex| lex.token == Token::Id(String::from(SET))) {
+        let set_pos = it.eat(&Token::Id(String::from(SET)), "property setter")?;
+        it.eat(&Token::LRBrack, "property setter")?;
+        let arg = it.parse(&parse_fun_arg, "property setter", start)?;
+        it.eat(&Token::RRBrack, "property setter")?;
+        let arg = match arg.node {
+            Node::FunArg {
+                vararg: false,
+                mutable,
+                var,
+                ty: arg_ty,
+                default: None,
+            } => AST::new(
+                arg.pos,
+                Node::FunArg {
+                    vararg: false,
+                    mutable,
+                    var,
+                    ty: Some(arg_ty.unwrap_or_else(|| ty.clone())),
+                    default: None,
+                },
+            ),
+            _ => return Err(Box::from(custom("Setter takes a single argument", arg.pos))),
+        };
+        it.eat(&Token::BTo, "property setter")?;
+        let body = it.parse(&parse_expr_or_stmt, "property setter", start)?;
+        let setter = fun_def(vec![self_arg(true), arg], None, *body);
+        it.eat_while(&Token::NL);
+        Some(Box::from(AST::new(set_pos.union(setter.pos), setter.node)))
+    } else {
+        None
+    };
+
+    let end = it.eat(&Token::Dedent, "property")?;
+    let getter = AST::new(get_pos.union(getter.pos), getter.node);
+    let node = Node::Property {
+        id,
+        ty,
+        getter: Box::from(getter),
+        setter,
+    };
+    Ok(Box::from(AST::new(start.union(end), node)))
+}
+
 pub fn parse_raises(it: &mut LexIterator) -> ParseResult<Vec<AST>> {
     let start = it.eat(&Token::LSBrack, "raises")?;
     let mut raises: Vec<AST> = Vec::new();
@@ -614,6 +777,54 @@ mod test {
         }
     }
 
+    #[test]
+    fn function_generic_definition_verify() {
+        let source = String::from("def f[T, U: Num](x: T) -> U => d");
+        let ast = parse_direct(&source).unwrap();
+
+        let generics = match &ast.first().expect("script empty.").node {
+            Node::FunDef { generics, .. } => generics.clone(),
+            other => panic!("Expected function definition but was {:?}.", other),
+        };
+
+        assert_eq!(generics.len(), 2);
+        match (&generics[0].node, &generics[1].node) {
+            (
+                Node::Generic { id: t, isa: None },
+                Node::Generic {
+                    id: u,
+                    isa: Some(isa),
+                },
+            ) => {
+                assert_eq!(
+                    t.node,
+                    Node::Id {
+                        lit: String::from("T")
+                    }
+                );
+                assert_eq!(
+                    u.node,
+                    Node::Id {
+                        lit: String::from("U")
+                    }
+                );
+                assert_eq!(
+                    isa.node,
+                    Node::Id {
+                        lit: String::from("Num")
+                    }
+                );
+            }
+            other => panic!("Expected two generics but was {:?}.", other),
+        }
+    }
+
+    #[test]
+    fn function_generic_no_closing_bracket() {
+        let source = String::from("def f[T(x: T) => d");
+        parse_direct(&source).unwrap_err();
+    }
+
     #[test]
     fn function_pure_definition_verify() {
         let source = String::from("def pure f() => d");
@@ -766,6 +977,84 @@ mod test {
         source.parse::<AST>().unwrap_err();
     }
 
+    #[test]
+    fn decorated_function_and_class() {
+        let source = String::from("@a\n@b(10)\ndef f() => pass\n\n@c\nclass C\n");
+        let asts = parse_direct(&source).expect("valid AST");
+        assert_eq!(asts.len(), 2);
+
+        let Node::FunDef { decorators, .. } = &asts[0].node else {
+            panic!("Expected function definition, was: {:?}", asts[0].node)
+        };
+        assert_eq!(decorators.len(), 2);
+        assert_eq!(
+            decorators[0].node,
+            Node::Id {
+                lit: String::from("a")
+            }
+        );
+        let Node::FunctionCall { args, .. } = &decorators[1].node else {
+            panic!("Expected function call, was: {:?}", decorators[1].node)
+        };
+        assert_eq!(args.len(), 1);
+
+        let Node::Class { decorators, .. } = &asts[1].node else {
+            panic!("Expected class, was: {:?}", asts[1].node)
+        };
+        assert_eq!(decorators.len(), 1);
+    }
+
+    #[test]
+    fn decorated_variable() {
+        let source = String::from("@a\ndef x := 10");
+        source.parse::<AST>().unwrap_err();
+    }
+
+    #[test]
+    fn property_with_getter_and_setter() {
+        let source = String::from("property a: Int\n    get => 10\n    set(x) => print(x)\n");
+        let asts = parse_direct(&source).expect("valid AST");
+
+        let Node::Property {
+            id, getter, setter, ..
+        } = &asts[0].node
+        else {
+            panic!("Expected property, was: {:?}", asts[0].node)
+        };
+        assert_eq!(
+            id.node,
+            Node::Id {
+                lit: String::from("a")
+            }
+        );
+        let Node::FunDef { args, ret, .. } = &getter.node else {
+            panic!("Expected function definition, was: {:?}", getter.node)
+        };
+        assert!(matches!(args[0].node, Node::FunArg { mutable: false, .. }));
+        assert!(ret.is_some());
+
+        let setter = setter.clone().expect("setter");
+        let Node::FunDef { args, ret, .. } = &setter.node else {
+            panic!("Expected function definition, was: {:?}", setter.node)
+        };
+        assert_eq!(args.len(), 2);
+        assert!(matches!(&args[1].node, Node::FunArg { ty: Some(_), .. }));
+        assert!(ret.is_none());
+    }
+
+    #[test]
+    fn property_only_getter() {
+        let source = String::from("property a: Int => 10");
+        let asts = parse_direct(&source).expect("valid AST");
+        assert!(matches!(asts[0].node, Node::Property { setter: None, .. }));
+    }
+
+    #[test]
+    fn property_without_getter() {
+        let source = String::from("property a: Int\n    set(x) => print(x)\n");
+        source.parse::<AST>().unwrap_err();
+    }
+
     #[test]
     fn function_definitions() -> ParseResult<()> {
         let source = resource_content(true, &["function"], "definition.mamba");
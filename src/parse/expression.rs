@@ -32,9 +32,10 @@ pub fn parse_inner_expression(it: &mut LexIterator) -> ParseResult {
         Token::Underscore,
         Token::Real(String::new()),
         Token::Int(String::new()),
-        Token::ENum(String::new(), String::new()),
+        Token::Imaginary(String::new()),
         Token::Not,
         Token::Sqrt,
+        Token::Await,
         Token::Add,
         Token::Id(String::new()),
         Token::Sub,
@@ -66,16 +67,9 @@ pub fn parse_inner_expression(it: &mut LexIterator) -> ParseResult {
                 };
                 Ok(Box::from(AST::new(start.union(end), node)))
             }
-            Token::ENum(num, exp) => {
-                let end = it.eat(&Token::ENum(num.clone(), exp.clone()), "factor")?;
-                let node = Node::ENum {
-                    num: num.to_string(),
-                    exp: exp.to_string(),
-                };
-                Ok(Box::from(AST::new(start.union(end), node)))
-            }
+            Token::Imaginary(imaginary) => literal!(it, imaginary.to_string(), Imaginary),
 
-            Token::Not | Token::Sqrt | Token::Add | Token::Sub | Token::BOneCmpl => {
+            Token::Not | Token::Sqrt | Token::Await | Token::Add | Token::Sub | Token::BOneCmpl => {
                 parse_expression(it)
             }
 
@@ -102,7 +96,7 @@ fn parse_underscore(it: &mut LexIterator) -> ParseResult {
 fn parse_post_expr(pre: &AST, it: &mut LexIterator) -> ParseResult {
     it.peek(
         &|it, lex| match lex.token {
-            Token::LRBrack | Token::Point => {
+            Token::LRBrack | Token::Point | Token::QuestionPoint => {
                 let res = parse_call(pre, it)?;
                 parse_post_expr(&res, it)
             }
@@ -144,9 +138,10 @@ pub fn is_start_expression_exclude_unary(tp: &Lex) -> bool {
             | Token::BSlash
             | Token::Real(_)
             | Token::Int(_)
-            | Token::ENum(..)
+            | Token::Imaginary(_)
             | Token::Str(..)
             | Token::Not
+            | Token::Await
             | Token::Id(_)
     )
 }
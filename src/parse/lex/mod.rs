@@ -121,6 +121,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn question_point() {
+        let source = String::from("a?.b ? c");
+        let tokens = tokenize(&source).unwrap();
+        assert_eq!(
+            tokens.iter().map(|l| l.token.clone()).collect_vec(),
+            vec![
+                Token::Id(String::from("a")),
+                Token::QuestionPoint,
+                Token::Id(String::from("b")),
+                Token::Question,
+                Token::Id(String::from("c")),
+                Token::Eof,
+            ]
+        );
+    }
+
     #[test]
     fn operators() {
         let source = String::from("+ - * / ^ mod sqrt i");
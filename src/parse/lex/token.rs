@@ -1,9 +1,11 @@
 use std::cmp::max;
 use std::fmt;
 
+use serde::Serialize;
+
 use crate::common::position::{CaretPos, Position};
 
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Debug, Clone, Serialize)]
 pub struct Lex {
     pub pos: Position,
     pub token: Token,
@@ -25,12 +27,13 @@ impl Lex {
     }
 }
 
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Debug, Clone, Serialize)]
 pub enum Token {
     From,
     Type,
     Class,
     Pure,
+    Property,
     IsA,
 
     As,
@@ -42,6 +45,7 @@ pub enum Token {
     DoublePoint,
     Vararg,
     BSlash,
+    At,
 
     Id(String),
     Fin,
@@ -57,7 +61,7 @@ pub enum Token {
 
     Real(String),
     Int(String),
-    ENum(String, String),
+    Imaginary(String),
     Str(String, Vec<Vec<Lex>>),
     DocStr(String),
 
@@ -123,9 +127,13 @@ pub enum Token {
     Continue,
     Break,
     Ret,
+    Yield,
     With,
+    Async,
+    Await,
 
     Question,
+    QuestionPoint,
     Handle,
 
     Pass,
@@ -146,7 +154,7 @@ impl Token {
             (Token::Int(_), Token::Int(_)) => true,
             (Token::Str(..), Token::Str(..)) => true,
             (Token::DocStr(_), Token::DocStr(_)) => true,
-            (Token::ENum(..), Token::ENum(..)) => true,
+            (Token::Imaginary(_), Token::Imaginary(_)) => true,
             _ => left == right,
         }
     }
@@ -157,6 +165,7 @@ impl fmt::Display for Token {
         match self.clone() {
             Token::From => write!(f, "from"),
             Token::Pure => write!(f, "pure"),
+            Token::Property => write!(f, "property"),
             Token::Type => write!(f, "type"),
             Token::Class => write!(f, "class"),
             Token::IsA => write!(f, "isa"),
@@ -170,6 +179,7 @@ impl fmt::Display for Token {
             Token::DoublePoint => write!(f, ":"),
             Token::Vararg => write!(f, "vararg"),
             Token::BSlash => write!(f, "\\"),
+            Token::At => write!(f, "@"),
 
             Token::Fin => write!(f, "fin"),
             Token::Assign => write!(f, ":="),
@@ -185,7 +195,7 @@ impl fmt::Display for Token {
             Token::Id(id) => write!(f, "{id}"),
             Token::Real(real) => write!(f, "{real}"),
             Token::Int(int) => write!(f, "{int}"),
-            Token::ENum(base, exp) => write!(f, "{base}E{exp}"),
+            Token::Imaginary(imaginary) => write!(f, "{imaginary}"),
             Token::Str(string, _) => write!(f, "\"{string}\""),
             Token::DocStr(docstr) => write!(f, "##{docstr}"),
 
@@ -247,10 +257,14 @@ impl fmt::Display for Token {
             Token::Continue => write!(f, "continue"),
             Token::Break => write!(f, "break"),
             Token::Ret => write!(f, "return"),
+            Token::Yield => write!(f, "yield"),
             Token::Do => write!(f, "do"),
             Token::With => write!(f, "with"),
+            Token::Async => write!(f, "async"),
+            Token::Await => write!(f, "await"),
 
             Token::Question => write!(f, "?"),
+            Token::QuestionPoint => write!(f, "?."),
 
             Token::Handle => write!(f, "handle"),
             Token::Raise => write!(f, "raise"),
@@ -77,6 +77,7 @@ pub fn into_tokens(c: char, it: &mut Peekable<Chars>, state: &mut State) -> LexR
             _ => create(state, Token::Div),
         },
         '\\' => create(state, Token::BSlash),
+        '@' => create(state, Token::At),
         '^' => match it.peek() {
             Some('=') => next_and_create(it, state, Token::PowAssign),
             _ => create(state, Token::Pow),
@@ -100,50 +101,63 @@ pub fn into_tokens(c: char, it: &mut Peekable<Chars>, state: &mut State) -> LexR
                 Err(LexErr::new(state.pos, None, &msg))
             }
         },
-        '?' => create(state, Token::Question),
+        '?' => match it.peek() {
+            Some('.') => next_and_create(it, state, Token::QuestionPoint),
+            _ => create(state, Token::Question),
+        },
         '0'..='9' => {
             let mut number = c.to_string();
-            let mut exp = String::new();
-            let mut float = false;
-            let mut e_num = false;
-
-            while let Some(&c) = it.peek() {
-                match c {
-                    '0'..='9' if !e_num => {
-                        number.push(c);
-                        it.next();
-                    }
-                    '0'..='9' if e_num => {
-                        exp.push(c);
-                        it.next();
-                    }
-                    'E' if e_num => break,
-                    'E' => {
-                        e_num = true;
-                        it.next();
-                    }
-                    '.' if float || e_num => break,
-                    '.' => {
-                        {
-                            // Check if not range by peeking ahead extra char
-                            let mut it = it.clone();
-                            it.next();
-                            if let Some('.') = it.peek() {
-                                break;
-                            }
-                        }
+            let radix = match (c, it.peek()) {
+                ('0', Some('x' | 'X')) => Some(16),
+                ('0', Some('o' | 'O')) => Some(8),
+                ('0', Some('b' | 'B')) => Some(2),
+                _ => None,
+            };
+
+            if let Some(radix) = radix {
+                number.extend(it.next());
+                digits(it, &mut number, radix);
+                let trailing = it.peek().is_some_and(|c| c.is_ascii_alphanumeric());
+                return if number.len() == 2 || trailing {
+                    let msg = format!("invalid digit in literal {number}");
+                    Err(LexErr::new(state.pos, None, &msg))
+                } else {
+                    create(state, Token::Int(number))
+                };
+            }
 
-                        number.push(c);
-                        float = true;
-                        it.next();
-                    }
-                    _ => break,
+            digits(it, &mut number, 10);
+            let mut float = false;
+            if let Some('.') = it.peek() {
+                // Check if not range by peeking ahead extra char
+                let mut ahead = it.clone();
+                ahead.next();
+                if ahead.peek() != Some(&'.') {
+                    number.extend(it.next());
+                    digits(it, &mut number, 10);
+                    float = true;
+                }
+            }
+            if let Some('e' | 'E') = it.peek() {
+                // Only an exponent if followed by (signed) digits
+                let mut ahead = it.clone();
+                let mut exp: String = ahead.next().into_iter().collect();
+                if let Some('+' | '-') = ahead.peek() {
+                    exp.extend(ahead.next());
+                }
+                if let Some('0'..='9') = ahead.peek() {
+                    *it = ahead;
+                    number.push_str(&exp);
+                    digits(it, &mut number, 10);
+                    float = true;
                 }
             }
+
             create(
                 state,
-                if e_num {
-                    Token::ENum(number, exp)
+                if let Some('j' | 'J') = it.peek() {
+                    number.extend(it.next());
+                    Token::Imaginary(number)
                 } else if float {
                     Token::Real(number)
                 } else {
@@ -245,6 +259,22 @@ fn next_and_create(
     create(state, token)
 }
 
+/// Append digits of the given radix, and any `_` separating them, to the number.
+fn digits(it: &mut Peekable<Chars>, number: &mut String, radix: u32) {
+    while let Some(&c) = it.peek() {
+        let separator = c == '_' && {
+            let mut ahead = it.clone();
+            ahead.next();
+            ahead.peek().is_some_and(|c| c.is_digit(radix))
+        };
+        if !c.is_digit(radix) && !separator {
+            break;
+        }
+        number.push(c);
+        it.next();
+    }
+}
+
 fn create(state: &mut State, token: Token) -> LexResult<Vec<Lex>> {
     Ok(state.token(token))
 }
@@ -257,6 +287,7 @@ fn as_op_or_id(string: String) -> Token {
         "type" => Token::Type,
         "class" => Token::Class,
         "pure" => Token::Pure,
+        "property" => Token::Property,
         "as" => Token::As,
 
         "import" => Token::Import,
@@ -286,9 +317,12 @@ fn as_op_or_id(string: String) -> Token {
         "continue" => Token::Continue,
         "break" => Token::Break,
         "return" => Token::Ret,
+        "yield" => Token::Yield,
         "then" => Token::Then,
         "do" => Token::Do,
         "with" => Token::With,
+        "async" => Token::Async,
+        "await" => Token::Await,
 
         "in" => Token::In,
 
@@ -384,10 +418,74 @@ mod test {
         let tokens = tokenize(&source)
             .map_err(|e| e.into_with_source(&Some(String::from(source)), &None))?;
 
-        assert_eq!(
-            tokens[0].token,
-            Token::ENum(String::from("3"), String::from("4"))
-        );
+        assert_eq!(tokens[0].token, Token::Real(String::from("3E4")));
+        Ok(())
+    }
+
+    #[test]
+    fn e_number_lower_case_signed() -> Result<(), LexErr> {
+        let sources = vec!["1.5e-3", "2E+4", "3e4"];
+
+        for source in sources {
+            let tokens = tokenize(&source)
+                .map_err(|e| e.into_with_source(&Some(String::from(source)), &None))?;
+
+            assert_eq!(tokens[0].token, Token::Real(String::from(source)));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn e_not_followed_by_digit() -> Result<(), LexErr> {
+        let source = "3 else";
+        let tokens = tokenize(&source)
+            .map_err(|e| e.into_with_source(&Some(String::from(source)), &None))?;
+
+        assert_eq!(tokens[0].token, Token::Int(String::from("3")));
+        assert_eq!(tokens[1].token, Token::Else);
+        Ok(())
+    }
+
+    #[test]
+    fn radix_int() -> Result<(), LexErr> {
+        let sources = vec!["0xFF", "0Xa_b", "0o17", "0b1010_1010"];
+
+        for source in sources {
+            let tokens = tokenize(&source)
+                .map_err(|e| e.into_with_source(&Some(String::from(source)), &None))?;
+
+            assert_eq!(tokens[0].token, Token::Int(String::from(source)));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn radix_int_invalid_digit() {
+        assert!(tokenize("0b102").is_err());
+        assert!(tokenize("0o8").is_err());
+        assert!(tokenize("0x").is_err());
+    }
+
+    #[test]
+    fn digit_separators() -> Result<(), LexErr> {
+        let source = "1_000_000.000_1";
+        let tokens = tokenize(&source)
+            .map_err(|e| e.into_with_source(&Some(String::from(source)), &None))?;
+
+        assert_eq!(tokens[0].token, Token::Real(String::from(source)));
+        Ok(())
+    }
+
+    #[test]
+    fn imaginary() -> Result<(), LexErr> {
+        let sources = vec!["3j", "1.5J", "1e-2j"];
+
+        for source in sources {
+            let tokens = tokenize(&source)
+                .map_err(|e| e.into_with_source(&Some(String::from(source)), &None))?;
+
+            assert_eq!(tokens[0].token, Token::Imaginary(String::from(source)));
+        }
         Ok(())
     }
 
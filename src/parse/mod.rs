@@ -24,6 +24,7 @@ mod expr_or_stmt;
 mod expression;
 mod lex;
 mod operation;
+mod pattern;
 mod statement;
 mod ty;
 
@@ -59,7 +60,12 @@ impl FromStr for AST {
     }
 }
 
-#[cfg(test)]
+/// Tokens of input, including comments.
+pub(crate) fn tokens(input: &str) -> Result<Vec<Lex>, Box<ParseErr>> {
+    tokenize(input).map_err(|err| Box::from(ParseErr::from(err)))
+}
+
+/// Parse input and give its top-level statements.
 pub fn parse_direct(input: &str) -> ParseResult<Vec<AST>> {
     match AST::from_str(input)?.node {
         Node::Block { statements } => Ok(statements),
@@ -70,6 +70,16 @@ fn parse_level_6(it: &mut LexIterator) -> ParseResult {
             Token::Leq => bin_op!(it, parse_level_6, Leq, arithmetic.clone(), "less, equal"),
             Token::Eq => bin_op!(it, parse_level_6, Eq, arithmetic.clone(), "equal"),
             Token::Neq => bin_op!(it, parse_level_6, Neq, arithmetic.clone(), "not equal"),
+            Token::Is if it.peek_if_followed_by(&Token::Is, &Token::Not) => {
+                it.eat(&Token::Is, "operation")?;
+                it.eat(&Token::Not, "operation")?;
+                let right = it.parse(&parse_level_6, "is not", start)?;
+                let node = Node::IsN {
+                    left: arithmetic.clone(),
+                    right: right.clone(),
+                };
+                Ok(Box::from(AST::new(start.union(right.pos), node)))
+            }
             Token::Is => bin_op!(it, parse_level_6, Is, arithmetic.clone(), "is"),
             Token::IsA => bin_op!(it, parse_level_6, IsA, arithmetic.clone(), "is a"),
             Token::In => bin_op!(it, parse_level_6, In, arithmetic.clone(), "in"),
@@ -199,6 +209,8 @@ fn parse_level_2(it: &mut LexIterator) -> ParseResult {
         un_op!(it, parse_expression, Sqrt, Sqrt, "square root")
     } else if it.eat_if(&Token::Not).is_some() {
         un_op!(it, parse_expression, Not, Not, "not")
+    } else if it.eat_if(&Token::Await).is_some() {
+        un_op!(it, parse_level_2, Await, Await, "await")
     } else if it.eat_if(&Token::BOneCmpl).is_some() {
         un_op!(
             it,
@@ -420,9 +432,8 @@ mod test {
         );
         assert_eq!(
             right.node,
-            Node::ENum {
-                num: String::from("3"),
-                exp: String::from("10")
+            Node::Real {
+                lit: String::from("3E10")
             }
         );
     }
@@ -447,6 +458,29 @@ mod test {
         );
     }
 
+    #[test]
+    fn is_not_verify() {
+        let source = String::from("p is not None");
+        let ast = parse_direct(&source).unwrap();
+
+        let (left, right) = match &ast.first().expect("script empty.").node {
+            Node::IsN { left, right } => (left.clone(), right.clone()),
+            other => panic!("first element script was not is not, but was: {other:?}"),
+        };
+        assert_eq!(
+            left.node,
+            Node::Id {
+                lit: String::from("p")
+            }
+        );
+        assert_eq!(
+            right.node,
+            Node::Id {
+                lit: String::from("None")
+            }
+        );
+    }
+
     #[test]
     fn isa_verify() {
         let source = String::from("lizard isa animal");
@@ -0,0 +1,402 @@
+use crate::parse::ast::Node;
+use crate::parse::ast::AST;
+use crate::parse::iterator::LexIterator;
+use crate::parse::lex::token::Token;
+use crate::parse::result::{custom, expected_one_of, ParseResult};
+use crate::parse::ty::parse_type;
+
+/// Parse a pattern of a match arm.
+///
+/// A pattern is either a single pattern, or multiple alternatives separated by `or`.
+pub fn parse_pattern(it: &mut LexIterator) -> ParseResult {
+    let start = it.start_pos("pattern")?;
+    let first = it.parse(&parse_single_pattern, "pattern", start)?;
+    if !it.peek_if(&|lex| lex.token == Token::Or) {
+        return Ok(first);
+    }
+
+    let mut patterns = vec![*first];
+    while it.eat_if(&Token::Or).is_some() {
+        patterns.push(*it.parse(&parse_single_pattern, "or pattern", start)?);
+    }
+
+    let end = patterns.last().map_or(start, |p| p.pos);
+    let node = Node::PatternOr { patterns };
+    Ok(Box::from(AST::new(start.union(end), node)))
+}
+
+fn parse_single_pattern(it: &mut LexIterator) -> ParseResult {
+    let start = it.start_pos("pattern")?;
+    macro_rules! literal {
+        ($it:expr, $factor:expr, $ast:ident) => {{
+            let end = $it.eat(&Token::$ast($factor.clone()), "literal pattern")?;
+            let node = Node::$ast { lit: $factor };
+            Ok(Box::from(AST::new(start.union(end), node)))
+        }};
+    }
+    let expected = [
+        Token::Underscore,
+        Token::Fin,
+        Token::Id(String::new()),
+        Token::LRBrack,
+        Token::LSBrack,
+        Token::Real(String::new()),
+        Token::Int(String::new()),
+        Token::Imaginary(String::new()),
+        Token::Str(String::new(), vec![]),
+        Token::Sub,
+    ];
+
+    it.peek_or_err(
+        &|it, lex| match &lex.token {
+            Token::Underscore if it.peek_if_followed_by(&lex.token, &Token::DoublePoint) => {
+                parse_capture_pattern(it)
+            }
+            Token::Underscore => {
+                let end = it.eat(&Token::Underscore, "wildcard pattern")?;
+                Ok(Box::from(AST::new(end, Node::Underscore)))
+            }
+            Token::Id(id) if ["True", "False", "None"].contains(&id.as_str()) => {
+                let end = it.eat(&Token::Id(id.clone()), "literal pattern")?;
+                Ok(Box::from(AST::new(end, Node::Id { lit: id.clone() })))
+            }
+            Token::Id(_) if it.peek_if_followed_by(&lex.token, &Token::LRBrack) => {
+                parse_class_pattern(it)
+            }
+            Token::Id(_) if it.peek_if_followed_by(&lex.token, &Token::Point) => {
+                parse_value_pattern(it)
+            }
+            Token::Id(_) | Token::Fin => parse_capture_pattern(it),
+            Token::LRBrack => parse_tuple_pattern(it),
+            Token::LSBrack => parse_list_pattern(it),
+
+            Token::Real(real) => literal!(it, real.to_string(), Real),
+            Token::Int(int) => literal!(it, int.to_string(), Int),
+            Token::Imaginary(imaginary) => literal!(it, imaginary.to_string(), Imaginary),
+            Token::Str(string, tokens) => {
+                let end = it.eat(
+                    &Token::Str(string.clone(), tokens.clone()),
+                    "literal pattern",
+                )?;
+                if !tokens.is_empty() {
+                    let msg = "String interpolation is not allowed in a pattern";
+                    return Err(Box::from(custom(msg, start.union(end))));
+                }
+
+                let node = Node::Str {
+                    lit: string.clone(),
+                    expressions: vec![],
+                };
+                Ok(Box::from(AST::new(start.union(end), node)))
+            }
+            Token::Sub => {
+                it.eat(&Token::Sub, "negative literal pattern")?;
+                let expr = it.parse(&parse_single_pattern, "negative literal pattern", start)?;
+                match expr.node {
+                    Node::Int { .. } | Node::Real { .. } | Node::Imaginary { .. } => {
+                        let node = Node::SubU { expr: expr.clone() };
+                        Ok(Box::from(AST::new(start.union(expr.pos), node)))
+                    }
+                    _ => {
+                        let msg = format!("Expected number in negative pattern, was {}", expr.node);
+                        Err(Box::from(custom(&msg, expr.pos)))
+                    }
+                }
+            }
+
+            _ => Err(Box::from(expected_one_of(&expected, lex, "pattern"))),
+        },
+        &expected,
+        "pattern",
+    )
+}
+
+/// Parse an identifier, which captures the value it is matched against.
+///
+/// The identifier may be preceded by `fin`, and followed by a type.
+/// If followed by a type, the identifier may also be an underscore.
+fn parse_capture_pattern(it: &mut LexIterator) -> ParseResult {
+    let start = it.start_pos("capture pattern")?;
+    let mutable = it.eat_if(&Token::Fin).is_none();
+
+    let expr = if let Some(end) = it.eat_if(&Token::Underscore) {
+        Box::from(AST::new(end, Node::Underscore))
+    } else {
+        it.parse(&parse_pattern_id, "capture pattern", start)?
+    };
+    let ty = it.parse_if(&Token::DoublePoint, &parse_type, "capture pattern", start)?;
+    let end = ty.clone().map_or(expr.pos, |t| t.pos);
+
+    let node = Node::ExpressionType { expr, mutable, ty };
+    Ok(Box::from(AST::new(start.union(end), node)))
+}
+
+/// Parse a dotted name, such as `Color.Red`, which is compared to the value it is matched against.
+fn parse_value_pattern(it: &mut LexIterator) -> ParseResult {
+    let start = it.start_pos("value pattern")?;
+    let mut value = it.parse(&parse_pattern_id, "value pattern", start)?;
+    while it.eat_if(&Token::Point).is_some() {
+        let property = it.parse(&parse_pattern_id, "value pattern", start)?;
+        let node = Node::PropertyCall {
+            instance: value,
+            property: property.clone(),
+        };
+        value = Box::from(AST::new(start.union(property.pos), node));
+    }
+    Ok(value)
+}
+
+fn parse_pattern_id(it: &mut LexIterator) -> ParseResult {
+    it.peek_or_err(
+        &|it, lex| match &lex.token {
+            Token::Id(id) => {
+                let end = it.eat(&Token::Id(id.clone()), "identifier")?;
+                Ok(Box::from(AST::new(end, Node::Id { lit: id.clone() })))
+            }
+            _ => Err(Box::from(expected_one_of(
+                &[Token::Id(String::new())],
+                lex,
+                "identifier",
+            ))),
+        },
+        &[Token::Id(String::new())],
+        "identifier",
+    )
+}
+
+fn parse_tuple_pattern(it: &mut LexIterator) -> ParseResult {
+    let start = it.start_pos("tuple pattern")?;
+    it.eat(&Token::LRBrack, "tuple pattern")?;
+
+    let mut elements = vec![];
+    it.peek_while_not_token(&Token::RRBrack, &mut |it, _| {
+        elements.push(*it.parse(&parse_pattern, "tuple pattern", start)?);
+        it.eat_if(&Token::Comma);
+        Ok(())
+    })?;
+    let end = it.eat(&Token::RRBrack, "tuple pattern")?;
+
+    Ok(Box::from(if elements.len() == 1 {
+        elements[0].clone()
+    } else {
+        AST::new(start.union(end), Node::Tuple { elements })
+    }))
+}
+
+/// Parse a list pattern, where at most one element may be a rest pattern, such as `*rest`.
+fn parse_list_pattern(it: &mut LexIterator) -> ParseResult {
+    let start = it.start_pos("list pattern")?;
+    it.eat(&Token::LSBrack, "list pattern")?;
+
+    let mut elements: Vec<AST> = vec![];
+    it.peek_while_not_token(&Token::RSBrack, &mut |it, lex| {
+        let element = if lex.token == Token::Mul {
+            let rest = it.parse(&parse_rest_pattern, "list pattern", start)?;
+            if elements
+                .iter()
+                .any(|e| matches!(e.node, Node::PatternRest { .. }))
+            {
+                let msg = "List pattern can only have one rest pattern";
+                return Err(Box::from(custom(msg, rest.pos)));
+            }
+            rest
+        } else {
+            it.parse(&parse_pattern, "list pattern", start)?
+        };
+
+        elements.push(*element);
+        it.eat_if(&Token::Comma);
+        Ok(())
+    })?;
+    let end = it.eat(&Token::RSBrack, "list pattern")?;
+
+    let node = Node::List { elements };
+    Ok(Box::from(AST::new(start.union(end), node)))
+}
+
+fn parse_rest_pattern(it: &mut LexIterator) -> ParseResult {
+    let start = it.eat(&Token::Mul, "rest pattern")?;
+    let id = if let Some(end) = it.eat_if(&Token::Underscore) {
+        Box::from(AST::new(end, Node::Underscore))
+    } else {
+        it.parse(&parse_pattern_id, "rest pattern", start)?
+    };
+
+    let node = Node::PatternRest { id: id.clone() };
+    Ok(Box::from(AST::new(start.union(id.pos), node)))
+}
+
+/// Parse class pattern, such as `Point(x = 0, y = py)`.
+fn parse_class_pattern(it: &mut LexIterator) -> ParseResult {
+    let start = it.start_pos("class pattern")?;
+    let ty = it.parse(&parse_pattern_id, "class pattern", start)?;
+    it.eat(&Token::LRBrack, "class pattern")?;
+
+    let mut fields = vec![];
+    it.peek_while_not_token(&Token::RRBrack, &mut |it, _| {
+        fields.push(*it.parse(&parse_field_pattern, "class pattern", start)?);
+        it.eat_if(&Token::Comma);
+        Ok(())
+    })?;
+    let end = it.eat(&Token::RRBrack, "class pattern")?;
+
+    let node = Node::PatternClass { ty, fields };
+    Ok(Box::from(AST::new(start.union(end), node)))
+}
+
+fn parse_field_pattern(it: &mut LexIterator) -> ParseResult {
+    let start = it.start_pos("field pattern")?;
+    let id = it.parse(&parse_pattern_id, "field pattern", start)?;
+    it.eat(&Token::Eq, "field pattern")?;
+    let pattern = it.parse(&parse_pattern, "field pattern", start)?;
+
+    let node = Node::PatternField {
+        id,
+        pattern: pattern.clone(),
+    };
+    Ok(Box::from(AST::new(start.union(pattern.pos), node)))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::parse::ast::Node;
+    use crate::parse::parse_direct;
+
+    fn first_case(source: &str) -> Node {
+        let statements = parse_direct(source).unwrap();
+        let Node::Match { cases, .. } = &statements.first().expect("script empty.").node else {
+            panic!("first element script was not match.")
+        };
+        cases.first().expect("no cases").node.clone()
+    }
+
+    #[test]
+    fn tuple_pattern_verify() {
+        let case = first_case("match a\n    (0, b) => b");
+        let Node::Case { cond, .. } = case else {
+            panic!("Expected case, was {case:?}")
+        };
+        let Node::Tuple { elements } = &cond.node else {
+            panic!("Expected tuple pattern, was {:?}", cond.node)
+        };
+
+        assert_eq!(elements.len(), 2);
+        assert_eq!(
+            elements[0].node,
+            Node::Int {
+                lit: String::from("0")
+            }
+        );
+        assert!(matches!(elements[1].node, Node::ExpressionType { .. }));
+    }
+
+    #[test]
+    fn list_with_rest_pattern_verify() {
+        let case = first_case("match a\n    [first, *rest] => first");
+        let Node::Case { cond, .. } = case else {
+            panic!("Expected case, was {case:?}")
+        };
+        let Node::List { elements } = &cond.node else {
+            panic!("Expected list pattern, was {:?}", cond.node)
+        };
+
+        assert_eq!(elements.len(), 2);
+        let Node::PatternRest { id } = &elements[1].node else {
+            panic!("Expected rest pattern, was {:?}", elements[1].node)
+        };
+        assert_eq!(
+            id.node,
+            Node::Id {
+                lit: String::from("rest")
+            }
+        );
+    }
+
+    #[test]
+    fn list_with_two_rest_pattern() {
+        let source = "match a\n    [*first, *rest] => first";
+        parse_direct(source).unwrap_err();
+    }
+
+    #[test]
+    fn value_pattern_verify() {
+        let case = first_case("match a\n    Color.Red => 0");
+        let Node::Case { cond, .. } = case else {
+            panic!("Expected case, was {case:?}")
+        };
+        let Node::PropertyCall { instance, property } = &cond.node else {
+            panic!("Expected value pattern, was {:?}", cond.node)
+        };
+
+        assert_eq!(instance.node.to_string(), "Color");
+        assert_eq!(property.node.to_string(), "Red");
+    }
+
+    #[test]
+    fn class_pattern_verify() {
+        let case = first_case("match a\n    Point(x = 0, y = py) => py");
+        let Node::Case { cond, .. } = case else {
+            panic!("Expected case, was {case:?}")
+        };
+        let Node::PatternClass { ty, fields } = &cond.node else {
+            panic!("Expected class pattern, was {:?}", cond.node)
+        };
+
+        assert_eq!(
+            ty.node,
+            Node::Id {
+                lit: String::from("Point")
+            }
+        );
+        assert_eq!(fields.len(), 2);
+        let Node::PatternField { id, pattern } = &fields[0].node else {
+            panic!("Expected field pattern, was {:?}", fields[0].node)
+        };
+        assert_eq!(
+            id.node,
+            Node::Id {
+                lit: String::from("x")
+            }
+        );
+        assert_eq!(
+            pattern.node,
+            Node::Int {
+                lit: String::from("0")
+            }
+        );
+    }
+
+    #[test]
+    fn or_pattern_with_guard_verify() {
+        let case = first_case("match a\n    1 or 2 if b => c");
+        let Node::Case { cond, guard, .. } = case else {
+            panic!("Expected case, was {case:?}")
+        };
+        let Node::PatternOr { patterns } = &cond.node else {
+            panic!("Expected or pattern, was {:?}", cond.node)
+        };
+
+        assert_eq!(patterns.len(), 2);
+        assert_eq!(
+            guard.expect("Expected guard").node,
+            Node::Id {
+                lit: String::from("b")
+            }
+        );
+    }
+
+    #[test]
+    fn negative_literal_pattern_verify() {
+        let case = first_case("match a\n    -1 => c");
+        let Node::Case { cond, .. } = case else {
+            panic!("Expected case, was {case:?}")
+        };
+        assert!(matches!(cond.node, Node::SubU { .. }));
+    }
+
+    #[test]
+    fn interpolated_string_pattern() {
+        let source = "match a\n    \"{b}\" => c";
+        parse_direct(source).unwrap_err();
+    }
+}
@@ -2,7 +2,7 @@ use crate::parse::ast::node_op::NodeOp;
 use crate::parse::ast::Node;
 use crate::parse::ast::AST;
 use crate::parse::control_flow_stmt::parse_cntrl_flow_stmt;
-use crate::parse::definition::parse_definition;
+use crate::parse::definition::{parse_definition, parse_property};
 use crate::parse::expr_or_stmt::parse_expr_or_stmt;
 use crate::parse::iterator::LexIterator;
 use crate::parse::lex::token::{Lex, Token};
@@ -27,18 +27,24 @@ pub fn parse_statement(it: &mut LexIterator) -> ParseResult {
                 Ok(Box::from(AST::new(lex.pos.union(error.pos), node)))
             }
             Token::Def => parse_definition(it),
+            Token::Property => parse_property(it),
             Token::With => parse_with(it),
             Token::For | Token::While => parse_cntrl_flow_stmt(it),
             Token::Ret => parse_return(it),
+            Token::Yield => parse_yield(it),
+            Token::Async => parse_async(it),
             _ => Err(Box::from(expected_one_of(
                 &[
                     Token::Pass,
                     Token::Raise,
                     Token::Def,
+                    Token::Property,
                     Token::With,
                     Token::For,
                     Token::While,
                     Token::Ret,
+                    Token::Yield,
+                    Token::Async,
                 ],
                 lex,
                 "statement",
@@ -48,15 +54,81 @@ pub fn parse_statement(it: &mut LexIterator) -> ParseResult {
             Token::Pass,
             Token::Raise,
             Token::Def,
+            Token::Property,
             Token::With,
             Token::For,
             Token::While,
             Token::Ret,
+            Token::Yield,
+            Token::Async,
         ],
         "statement",
     )
 }
 
+/// Parse an async function definition, for loop or with statement.
+pub fn parse_async(it: &mut LexIterator) -> ParseResult {
+    let start = it.start_pos("async")?;
+    it.eat(&Token::Async, "async")?;
+
+    let expected = [Token::Def, Token::For, Token::With];
+    let ast = it.peek_or_err(
+        &|it, lex| match lex.token {
+            Token::Def => parse_definition(it),
+            Token::For => parse_cntrl_flow_stmt(it),
+            Token::With => parse_resources(it, true),
+            _ => Err(Box::from(expected_one_of(&expected, lex, "async"))),
+        },
+        &expected,
+        "async",
+    )?;
+
+    let node = match ast.node {
+        Node::FunDef {
+            pure,
+            decorators,
+            id,
+            generics,
+            args,
+            ret,
+            raises,
+            body,
+            ..
+        } => Node::FunDef {
+            pure,
+            is_async: true,
+            decorators,
+            id,
+            generics,
+            args,
+            ret,
+            raises,
+            body,
+        },
+        Node::For {
+            expr, col, body, ..
+        } => Node::For {
+            is_async: true,
+            expr,
+            col,
+            body,
+        },
+        Node::With {
+            resource,
+            alias,
+            expr,
+            ..
+        } => Node::With {
+            is_async: true,
+            resource,
+            alias,
+            expr,
+        },
+        _ => return Err(Box::from(custom("Only a function can be async", ast.pos))),
+    };
+    Ok(Box::from(AST::new(start.union(ast.pos), node)))
+}
+
 pub fn parse_import(it: &mut LexIterator) -> ParseResult {
     let start = it.start_pos("import")?;
     let from = if it.peek_if(&|lex| lex.token == Token::From) {
@@ -170,29 +242,50 @@ pub fn parse_reassignment(pre: &AST, it: &mut LexIterator) -> ParseResult {
 }
 
 pub fn parse_with(it: &mut LexIterator) -> ParseResult {
+    parse_resources(it, false)
+}
+
+/// Parse a with statement, which may have multiple resources separated by commas.
+///
+/// Resources are nested, so `with a as x, b as y do c` is the same as
+/// `with a as x do with b as y do c`.
+fn parse_resources(it: &mut LexIterator, is_async: bool) -> ParseResult {
     let start = it.start_pos("with")?;
     it.eat(&Token::With, "with")?;
-    let resource = it.parse(&parse_expression, "with", start)?;
 
-    let alias = it.parse_if(&Token::As, &parse_expression_type, "with id", start)?;
-    let alias = if let Some(alias) = &alias {
-        match alias.node.clone() {
-            Node::ExpressionType { expr, mutable, ty } => Some((expr, mutable, ty)),
-            _ => return Err(Box::from(custom("Expected expression type", alias.pos))),
+    let mut resources = vec![];
+    loop {
+        let resource = it.parse(&parse_expression, "with", start)?;
+        let alias = it.parse_if(&Token::As, &parse_expression_type, "with id", start)?;
+        let alias = if let Some(alias) = &alias {
+            match alias.node.clone() {
+                Node::ExpressionType { expr, mutable, ty } => Some((expr, mutable, ty)),
+                _ => return Err(Box::from(custom("Expected expression type", alias.pos))),
+            }
+        } else {
+            None
+        };
+
+        resources.push((resource, alias));
+        if it.eat_if(&Token::Comma).is_none() {
+            break;
         }
-    } else {
-        None
-    };
+    }
 
     it.eat(&Token::Do, "with")?;
-    let expr = it.parse(&parse_expr_or_stmt, "with", start)?;
+    let mut expr = it.parse(&parse_expr_or_stmt, "with", start)?;
 
-    let node = Node::With {
-        resource,
-        alias,
-        expr: expr.clone(),
-    };
-    Ok(Box::from(AST::new(start.union(expr.pos), node)))
+    for (i, (resource, alias)) in resources.into_iter().enumerate().rev() {
+        let pos = if i == 0 { start } else { resource.pos };
+        let node = Node::With {
+            is_async,
+            resource,
+            alias,
+            expr: expr.clone(),
+        };
+        expr = Box::from(AST::new(pos.union(expr.pos), node));
+    }
+    Ok(expr)
 }
 
 pub fn parse_return(it: &mut LexIterator) -> ParseResult {
@@ -216,10 +309,28 @@ pub fn parse_return(it: &mut LexIterator) -> ParseResult {
     )))
 }
 
+pub fn parse_yield(it: &mut LexIterator) -> ParseResult {
+    let start = it.start_pos("yield")?;
+    it.eat(&Token::Yield, "yield")?;
+
+    if it.eat_if(&Token::From).is_some() {
+        let expr = it.parse(&parse_expression, "yield from", start)?;
+        let node = Node::YieldFrom { expr: expr.clone() };
+        return Ok(Box::from(AST::new(start.union(expr.pos), node)));
+    }
+
+    let expr = it.parse(&parse_expression, "yield", start)?;
+    Ok(Box::from(AST::new(
+        start.union(expr.pos),
+        Node::Yield { expr },
+    )))
+}
+
 pub fn is_start_statement(tp: &Token) -> bool {
     matches!(
         tp,
         Token::Def
+            | Token::Property
             | Token::Fin
             | Token::For
             | Token::While
@@ -227,6 +338,8 @@ pub fn is_start_statement(tp: &Token) -> bool {
             | Token::Raise
             | Token::With
             | Token::Ret
+            | Token::Yield
+            | Token::Async
     )
 }
 
@@ -260,6 +373,33 @@ mod test {
         );
     }
 
+    #[test]
+    fn parse_yield_and_yield_from() {
+        let source = String::from("yield 20\nyield from a");
+        let asts = parse_direct(&source).expect("valid AST");
+
+        assert_eq!(asts.len(), 2);
+        let Node::Yield { expr } = &asts[0].node else {
+            panic!("Expected yield, was: {:?}", asts[0].node)
+        };
+        assert_eq!(
+            expr.node,
+            Node::Int {
+                lit: String::from("20")
+            }
+        );
+
+        let Node::YieldFrom { expr } = &asts[1].node else {
+            panic!("Expected yield from, was: {:?}", asts[1].node)
+        };
+        assert_eq!(
+            expr.node,
+            Node::Id {
+                lit: String::from("a")
+            }
+        );
+    }
+
     #[test]
     fn parse_reassignment() {
         let source = String::from("a := 1");
@@ -322,4 +462,95 @@ mod test {
         );
         assert_eq!(*op, NodeOp::Assign);
     }
+
+    #[test]
+    fn parse_async_fun_def_with_await() {
+        let source = String::from("async def f() => await g()");
+        let asts = parse_direct(&source).expect("valid AST");
+
+        assert_eq!(asts.len(), 1);
+        let Node::FunDef { is_async, body, .. } = &asts[0].node else {
+            panic!("Expected function definition, was {:?}", asts[0].node)
+        };
+        assert!(is_async);
+
+        let body = body.as_ref().expect("function body");
+        let Node::Await { expr } = &body.node else {
+            panic!("Expected await, was {:?}", body.node)
+        };
+        assert!(matches!(expr.node, Node::FunctionCall { .. }));
+    }
+
+    #[test]
+    fn parse_async_for_and_with() {
+        let source = String::from("async for a in b do c\nasync with d as e do f");
+        let asts = parse_direct(&source).expect("valid AST");
+
+        assert_eq!(asts.len(), 2);
+        assert!(matches!(asts[0].node, Node::For { is_async: true, .. }));
+        assert!(matches!(asts[1].node, Node::With { is_async: true, .. }));
+    }
+
+    #[test]
+    fn parse_with_multiple_resources() {
+        let source = String::from("with a as b, c, d as e do f");
+        let asts = parse_direct(&source).expect("valid AST");
+
+        let Node::With {
+            resource,
+            alias: Some((alias, ..)),
+            expr,
+            ..
+        } = &asts[0].node
+        else {
+            panic!("Expected with, was {:?}", asts[0].node)
+        };
+        assert_eq!(resource.node.to_string(), "a");
+        assert_eq!(alias.node.to_string(), "b");
+
+        let Node::With {
+            resource,
+            alias: None,
+            expr,
+            ..
+        } = &expr.node
+        else {
+            panic!("Expected with, was {:?}", expr.node)
+        };
+        assert_eq!(resource.node.to_string(), "c");
+
+        let Node::With {
+            resource,
+            alias: Some((alias, ..)),
+            expr,
+            ..
+        } = &expr.node
+        else {
+            panic!("Expected with, was {:?}", expr.node)
+        };
+        assert_eq!(resource.node.to_string(), "d");
+        assert_eq!(alias.node.to_string(), "e");
+        assert_eq!(expr.node.to_string(), "f");
+    }
+
+    #[test]
+    fn parse_async_with_multiple_resources() {
+        let source = String::from("async with a as b, c as d do e");
+        let asts = parse_direct(&source).expect("valid AST");
+
+        let Node::With {
+            is_async: true,
+            expr,
+            ..
+        } = &asts[0].node
+        else {
+            panic!("Expected async with, was {:?}", asts[0].node)
+        };
+        assert!(matches!(expr.node, Node::With { is_async: true, .. }));
+    }
+
+    #[test]
+    fn parse_async_variable_def() {
+        assert!(parse_direct("async def a := 1").is_err());
+    }
 }
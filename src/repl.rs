@@ -0,0 +1,431 @@
+use std::io::{stdin, stdout, BufRead, BufReader, Write};
+use std::path::PathBuf;
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+
+use crate::check::ast::{ASTTy, NodeTy};
+use crate::check::check_defined;
+use crate::check::context::Context;
+use crate::check::result::TypeErr;
+use crate::check::Defined;
+use crate::common::position::Position;
+use crate::common::result::WithSource;
+use crate::generate::ast::node::Core;
+use crate::generate::{gen_arguments, GenArguments};
+use crate::parse::ast::{Node, AST};
+use crate::parse::parse_direct;
+use crate::parse::result::ParseErr;
+use crate::{Arguments, PipelineArguments};
+
+const PROMPT: &str = ">>> ";
+const CONTINUE: &str = "... ";
+const QUIT: &str = ":quit";
+/// Path shown in errors.
+const PATH: &str = "<repl>";
+
+/// Line after each entry sent to the interpreter.
+const END: &str = "\u{1}end";
+/// Line which precedes the value of an expression printed by the interpreter.
+const VALUE: &str = "\u{1}value ";
+/// Line printed by the interpreter after it ran an entry.
+const DONE: &str = "\u{1}done";
+
+/// Runs each entry in a scope kept between entries.
+///
+/// If the entry is an expression, its value is printed on a line preceded by [VALUE].
+/// Tracebacks, without the frame of the driver itself, are printed to standard error.
+const DRIVER: &str = r#"
+import sys, traceback
+scope = {"__name__": "__main__"}
+while True:
+    lines = []
+    line = sys.stdin.readline()
+    while line and line != "\x01end\n":
+        lines.append(line)
+        line = sys.stdin.readline()
+    if not line:
+        break
+    source = "".join(lines)
+    try:
+        try:
+            code = compile(source, "<repl>", "eval")
+        except SyntaxError:
+            exec(compile(source, "<repl>", "exec"), scope)
+        else:
+            value = eval(code, scope)
+            if value is not None:
+                print("\x01value " + repr(value))
+    except SystemExit:
+        raise
+    except BaseException:
+        kind, value, tb = sys.exc_info()
+        traceback.print_exception(kind, value, tb.tb_next)
+    sys.stderr.flush()
+    print("\x01done", flush=True)
+"#;
+
+/// Read entries from standard input until [QUIT] or end of input.
+///
+/// Entries which end with an open block continue on the next lines until an empty line.
+pub fn repl(interpreter: &str, arguments: &Arguments) -> Result<(), Vec<String>> {
+    let mut repl = Repl::new(interpreter, arguments)?;
+    println!("Mamba 🐍 {} ({QUIT} to exit)", env!("CARGO_PKG_VERSION"));
+
+    let mut lines = stdin().lock().lines();
+    loop {
+        let mut entry = String::new();
+        loop {
+            print!("{}", if entry.is_empty() { PROMPT } else { CONTINUE });
+            stdout().flush().map_err(|e| vec![e.to_string()])?;
+            match lines.next() {
+                Some(line) => {
+                    entry.push_str(&line.map_err(|e| vec![e.to_string()])?);
+                    entry.push('\n');
+                }
+                None => {
+                    println!();
+                    return Ok(());
+                }
+            }
+            if !is_incomplete(&entry) {
+                break;
+            }
+        }
+
+        match entry.trim() {
+            "" => continue,
+            QUIT => return Ok(()),
+            _ => {}
+        }
+
+        match repl.eval(&entry) {
+            Ok(Some(value)) => println!("{value}"),
+            Ok(None) => {}
+            Err(errs) => errs.iter().for_each(|err| eprintln!("{err}")),
+        }
+    }
+}
+
+/// Session which keeps the definitions of all entries which were checked, and a Python
+/// interpreter which ran them.
+///
+/// Each entry is checked on its own, in a context with the classes and functions of previous
+/// entries, and an environment with their variables.
+/// Only the entry itself is run.
+pub struct Repl {
+    /// Primitives, standard library, and definitions of previous entries.
+    ctx: Context,
+    /// Top-level variables of previous entries, with their types.
+    defined: Vec<Defined>,
+    gen_args: GenArguments,
+    interpreter: Option<Interpreter>,
+}
+
+/// Checked entry.
+#[derive(Debug, PartialEq)]
+pub struct Checked {
+    pub python: String,
+    /// Type of the last statement of the entry, if any.
+    pub ty: Option<String>,
+}
+
+impl Repl {
+    pub fn new(interpreter: &str, arguments: &Arguments) -> Result<Repl, Vec<String>> {
+        let mut repl = Repl::without_interpreter(arguments)?;
+        repl.interpreter = Some(Interpreter::new(interpreter)?);
+        Ok(repl)
+    }
+
+    /// Session which checks entries, but does not run them.
+    pub fn without_interpreter(arguments: &Arguments) -> Result<Repl, Vec<String>> {
+        let base = Context::default()
+            .into_with_primitives()
+            .and_then(Context::into_with_std_lib)
+            .map_err(|errs| errs.iter().map(|e| format!("{e}")).collect::<Vec<_>>())?;
+
+        Ok(Repl {
+            ctx: base,
+            defined: vec![],
+            gen_args: GenArguments::from(&PipelineArguments::from(arguments)),
+            interpreter: None,
+        })
+    }
+
+    /// Check and run entry, giving its value and type if it has a value.
+    pub fn eval(&mut self, entry: &str) -> Result<Option<String>, Vec<String>> {
+        let checked = self.check(entry)?;
+        let value = match &mut self.interpreter {
+            Some(interpreter) => interpreter.run(&checked.python)?,
+            None => None,
+        };
+
+        Ok(value.map(|value| match checked.ty {
+            Some(ty) => format!("{value}: {ty}"),
+            None => value,
+        }))
+    }
+
+    /// Check entry and generate Python for it.
+    ///
+    /// If successful, the definitions of the entry are added to the session.
+    /// Errors are shown relative to the start of the entry.
+    pub fn check(&mut self, entry: &str) -> Result<Checked, Vec<String>> {
+        let entry = match entry.ends_with('\n') {
+            true => String::from(entry),
+            false => format!("{entry}\n"),
+        };
+        let statements = parse_direct(&entry).map_err(|err| parse_errs(*err, &entry))?;
+        let pos = match (statements.first(), statements.last()) {
+            (Some(first), Some(last)) => first.pos.union(last.pos),
+            _ => Position::invisible(),
+        };
+        let block = AST::new(pos, Node::Block { statements });
+
+        let user =
+            Context::user(std::slice::from_ref(&block)).map_err(|errs| type_errs(errs, &entry))?;
+        let ctx = user.union(&self.ctx);
+        let ast_ty =
+            check_defined(&block, &self.defined, &ctx).map_err(|errs| type_errs(errs, &entry))?;
+
+        let statements = match &ast_ty.node {
+            NodeTy::Block { statements } => statements.clone(),
+            _ => vec![],
+        };
+        let ty = statements
+            .last()
+            .and_then(|last| match (&last.node, &last.ty) {
+                (_, Some(ty)) => Some(ty.clone()),
+                // Variable of a previous entry is not part of the entry itself
+                (NodeTy::Id { lit }, None) => (self.defined.iter())
+                    .find(|(_, name, _)| name == lit)
+                    .map(|(_, _, ty)| ty.clone()),
+                _ => None,
+            });
+        let python = gen_arguments(&ast_ty, &self.gen_args, &ctx).map_err(|err| {
+            let path = Some(PathBuf::from(PATH));
+            vec![format!("{}", err.with_source(&Some(entry.clone()), &path))]
+        })?;
+
+        for statement in &statements {
+            if let NodeTy::VariableDef { mutable, var, .. } = &statement.node {
+                define(*mutable, var, &mut self.defined);
+            }
+        }
+        self.ctx = ctx;
+        Ok(Checked {
+            python: match python {
+                Core::Block { statements } if statements.is_empty() => String::new(),
+                python => format!("{python}"),
+            },
+            ty: ty.map(|ty| format!("{ty}")),
+        })
+    }
+}
+
+/// Add variables of a definition, which shadow earlier variables with the same name.
+fn define(mutable: bool, var: &ASTTy, defined: &mut Vec<Defined>) {
+    match (&var.node, &var.ty) {
+        (NodeTy::Id { lit }, Some(ty)) => {
+            defined.retain(|(_, name, _)| name != lit);
+            defined.push((mutable, lit.clone(), ty.clone()));
+        }
+        (NodeTy::Tuple { elements }, _) => {
+            elements.iter().for_each(|e| define(mutable, e, defined))
+        }
+        (
+            NodeTy::ExpressionType {
+                expr,
+                mutable: var_mut,
+                ..
+            },
+            _,
+        ) => define(mutable && *var_mut, expr, defined),
+        _ => {}
+    }
+}
+
+fn parse_errs(err: ParseErr, source: &str) -> Vec<String> {
+    let path = Some(PathBuf::from(PATH));
+    vec![format!(
+        "{}",
+        err.with_source(&Some(String::from(source)), &path)
+    )]
+}
+
+fn type_errs(errs: Vec<TypeErr>, source: &str) -> Vec<String> {
+    let path = Some(PathBuf::from(PATH));
+    (errs.into_iter())
+        .map(|err| format!("{}", err.with_source(&Some(String::from(source)), &path)))
+        .collect()
+}
+
+/// Whether an entry needs more lines.
+///
+/// An entry needs more lines if it ends before the end of an expression or the start of a block.
+/// A block, which consists of indented lines, only ends at an empty line.
+/// As classes do not have a token which opens their body, a class is also a block.
+fn is_incomplete(entry: &str) -> bool {
+    let lines: Vec<&str> = entry.lines().collect();
+    let (first, last) = match (lines.first(), lines.last()) {
+        (Some(first), Some(last)) => (first, last),
+        _ => return false,
+    };
+
+    if last.trim().is_empty() {
+        false
+    } else if first.trim_start().starts_with("class ")
+        || lines[1..].iter().any(|line| line.starts_with([' ', '\t']))
+    {
+        true
+    } else {
+        match parse_direct(entry) {
+            Ok(_) => false,
+            Err(err) => {
+                err.pos.start.line >= lines.len() && err.pos.start.pos > last.chars().count()
+            }
+        }
+    }
+}
+
+/// Python interpreter which runs the [DRIVER].
+struct Interpreter {
+    child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+}
+
+impl Interpreter {
+    fn new(interpreter: &str) -> Result<Interpreter, Vec<String>> {
+        let mut child = Command::new(interpreter)
+            .arg("-u")
+            .arg("-c")
+            .arg(DRIVER)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .map_err(|e| vec![format!("Unable to run '{interpreter}': {e}")])?;
+
+        let stdin = child.stdin.take();
+        let stdout = child.stdout.take().map(BufReader::new);
+        match (stdin, stdout) {
+            (Some(stdin), Some(stdout)) => Ok(Interpreter {
+                child,
+                stdin,
+                stdout,
+            }),
+            _ => Err(vec![format!("Unable to communicate with '{interpreter}'")]),
+        }
+    }
+
+    /// Run Python source, giving the value if it is an expression which is not `None`.
+    ///
+    /// Any other output is printed as it arrives.
+    fn run(&mut self, python: &str) -> Result<Option<String>, Vec<String>> {
+        let exited = |e: std::io::Error| vec![format!("Interpreter exited: {e}")];
+        writeln!(self.stdin, "{}\n{END}", python.trim_end()).map_err(exited)?;
+        self.stdin.flush().map_err(exited)?;
+
+        let mut value = None;
+        loop {
+            let mut line = String::new();
+            if self.stdout.read_line(&mut line).map_err(exited)? == 0 {
+                return Err(vec![String::from("Interpreter exited")]);
+            }
+
+            let line = line.trim_end_matches('\n');
+            if let Some(output) = line.strip_suffix(DONE) {
+                if !output.is_empty() {
+                    println!("{output}");
+                }
+                return Ok(value);
+            }
+            match line.strip_prefix(VALUE) {
+                Some(repr) => value = Some(String::from(repr)),
+                None => println!("{line}"),
+            }
+        }
+    }
+}
+
+impl Drop for Interpreter {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::repl::{is_incomplete, Repl};
+    use crate::run::INTERPRETER;
+    use crate::Arguments;
+
+    #[test]
+    fn keep_definitions_of_previous_entries() {
+        let mut repl = Repl::without_interpreter(&Arguments::default()).unwrap();
+        let checked = repl.check("def x := 10").unwrap();
+        assert_eq!(checked.python.trim_end(), "x = 10");
+
+        let checked = repl
+            .check("def f(a: Int) -> Int =>\n    return a + x\n\n")
+            .unwrap();
+        assert!(checked.python.starts_with("def f(a)"), "{}", checked.python);
+
+        let checked = repl.check("f(x) * 2").unwrap();
+        assert_eq!(checked.python.trim_end(), "f(x) * 2");
+        assert_eq!(checked.ty, Some(String::from("Int")));
+    }
+
+    #[test]
+    fn failed_entry_not_kept() {
+        let mut repl = Repl::without_interpreter(&Arguments::default()).unwrap();
+        assert!(repl.check("y + 1").is_err());
+        assert!(repl.check("def y := (1,").is_err());
+        assert!(repl.check("def y := 1").is_ok());
+        assert!(repl.check("y + 1").is_ok());
+    }
+
+    #[test]
+    fn shadow_variable_of_previous_entry() {
+        let mut repl = Repl::without_interpreter(&Arguments::default()).unwrap();
+        assert!(repl.check("def x := 10").is_ok());
+        assert!(repl.check("def x := \"a\"").is_ok());
+
+        let checked = repl.check("x + \"b\"").unwrap();
+        assert_eq!(checked.ty, Some(String::from("Str")));
+        let checked = repl.check("x").unwrap();
+        assert_eq!(checked.ty, Some(String::from("Str")));
+    }
+
+    #[test]
+    fn errors_relative_to_entry() {
+        let mut repl = Repl::without_interpreter(&Arguments::default()).unwrap();
+        assert!(repl.check("def x := 10").is_ok());
+        assert!(repl.check("def y := 20").is_ok());
+
+        let errs = repl.check("x + z").unwrap_err();
+        assert!(errs[0].contains("<repl>:1:5"), "{}", errs[0]);
+    }
+
+    #[test]
+    fn incomplete_entries() {
+        assert!(!is_incomplete("def x := 10\n"));
+        assert!(is_incomplete("def x := (1,\n"));
+        assert!(is_incomplete("def f(x: Int) -> Int =>\n"));
+        assert!(is_incomplete("def f(x: Int) -> Int =>\n    return x\n"));
+        assert!(!is_incomplete("def f(x: Int) -> Int =>\n    return x\n\n"));
+        assert!(is_incomplete("class A\n"));
+    }
+
+    #[test]
+    fn run_entries_in_interpreter() {
+        let mut repl = Repl::new(INTERPRETER, &Arguments::default()).unwrap();
+        assert_eq!(repl.eval("def x := 20").unwrap(), None);
+        assert_eq!(repl.eval("x + 1").unwrap(), Some(String::from("21: Int")));
+        assert_eq!(repl.eval("x").unwrap(), Some(String::from("20: Int")));
+        assert_eq!(
+            repl.eval("\"a{x}\"").unwrap(),
+            Some(String::from("'a20': Str"))
+        );
+        assert_eq!(repl.eval("x / 0").unwrap(), None);
+    }
+}
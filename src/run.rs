@@ -0,0 +1,264 @@
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use crate::cache::hash;
+use crate::generate::LINE_MARKER;
+use crate::{io, transpile_dir, Arguments, SOURCE};
+
+#[cfg(windows)]
+pub const INTERPRETER: &str = "python";
+#[cfg(not(windows))]
+pub const INTERPRETER: &str = "python3";
+
+/// Transpile and run given module with the interpreter, passing it the given arguments.
+///
+/// The module is a path relative to the source directory, either with dots as separators, such
+/// as `pkg.main`, or as a file, such as `pkg/main.mamba`.
+/// Output is stored in a directory, specific to the project, in the temporary directory of the
+/// system, so that the build cache is kept between runs.
+/// Frames of Python tracebacks which refer to generated files are rewritten to refer to the
+/// Mamba source.
+///
+/// Returns the exit code of the interpreter.
+pub fn run(
+    dir: &Path,
+    src: Option<&str>,
+    module: &str,
+    args: &[String],
+    interpreter: &str,
+    arguments: &Arguments,
+) -> Result<i32, Vec<String>> {
+    let run_dir = run_dir(dir);
+    let arguments = Arguments {
+        source_map: true,
+        ..arguments.clone()
+    };
+    let out_dir = transpile_dir(dir, src, Some(&run_dir.to_string_lossy()), &arguments)?;
+    let out_dir =
+        fs::canonicalize(&out_dir).map_err(|e| vec![format!("{e}: {}", out_dir.display())])?;
+
+    let script = module_path(&out_dir, module);
+    if !script.is_file() {
+        return Err(vec![format!("Module does not exist: {module}")]);
+    }
+
+    let mut python_path = vec![out_dir.clone()];
+    if let Some(paths) = env::var_os("PYTHONPATH") {
+        python_path.extend(env::split_paths(&paths));
+    }
+    let python_path =
+        env::join_paths(python_path).map_err(|e| vec![format!("Unable to set PYTHONPATH: {e}")])?;
+
+    info!("Running '{}' with {interpreter}", script.display());
+    let mut child = Command::new(interpreter)
+        .arg(&script)
+        .args(args)
+        .env("PYTHONPATH", python_path)
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| vec![format!("Unable to run '{interpreter}': {e}")])?;
+
+    let mut traceback = Traceback::new(&out_dir, &dir.join(src.unwrap_or(SOURCE)));
+    if let Some(stderr) = child.stderr.take() {
+        for line in BufReader::new(stderr).lines() {
+            let line =
+                line.map_err(|e| vec![format!("Unable to read from '{interpreter}': {e}")])?;
+            if let Some(line) = traceback.rewrite(&line) {
+                eprintln!("{line}");
+            }
+        }
+    }
+
+    let status = child
+        .wait()
+        .map_err(|e| vec![format!("Unable to run '{interpreter}': {e}")])?;
+    Ok(status.code().unwrap_or(1))
+}
+
+/// Output directory of given project when running.
+fn run_dir(dir: &Path) -> PathBuf {
+    let dir = fs::canonicalize(dir).unwrap_or_else(|_| dir.to_path_buf());
+    env::temp_dir().join(format!("mamba-run-{:016x}", hash(&dir.to_string_lossy())))
+}
+
+fn module_path(out_dir: &Path, module: &str) -> PathBuf {
+    let module = match module.strip_suffix(".mamba") {
+        Some(file) => PathBuf::from(file),
+        None => module.split('.').collect(),
+    };
+    out_dir.join(module).with_extension("py")
+}
+
+/// For each line of the Python source, the line in the source of the statement it belongs to.
+///
+/// Lines before the first statement belong to line 0.
+fn source_map(python: &str) -> Vec<usize> {
+    let mut line = 0;
+    (python.lines())
+        .map(|python_line| {
+            let comment = python_line.trim_start().strip_prefix("# ");
+            if let Some(marker) = comment.and_then(|c| c.strip_prefix(LINE_MARKER)) {
+                line = marker.trim().parse().unwrap_or(line);
+            }
+            line
+        })
+        .collect()
+}
+
+/// What the line following a traceback frame is expected to be.
+enum Expect {
+    Frame,
+    /// Python source of a rewritten frame, replaced by given Mamba source.
+    Source(String),
+    /// Carets which point at the Python source of a rewritten frame.
+    Carets,
+}
+
+/// Rewrites Python tracebacks line by line.
+struct Traceback {
+    out_dir: PathBuf,
+    src_path: PathBuf,
+    /// Each generated file, if it and its source could be read.
+    files: HashMap<PathBuf, Option<Generated>>,
+    expect: Expect,
+}
+
+struct Generated {
+    source_map: Vec<usize>,
+    /// Lines of the Mamba source.
+    lines: Vec<String>,
+}
+
+impl Traceback {
+    fn new(out_dir: &Path, src_path: &Path) -> Traceback {
+        Traceback {
+            out_dir: out_dir.to_path_buf(),
+            src_path: src_path.to_path_buf(),
+            files: HashMap::new(),
+            expect: Expect::Frame,
+        }
+    }
+
+    /// Rewrite line of standard error, or drop it if [None].
+    fn rewrite(&mut self, line: &str) -> Option<String> {
+        match std::mem::replace(&mut self.expect, Expect::Frame) {
+            Expect::Source(source) if line.starts_with("    ") => {
+                self.expect = Expect::Carets;
+                return Some(format!("    {}", source.trim()));
+            }
+            Expect::Carets
+                if !line.trim().is_empty() && line.trim().chars().all(|c| "^~ ".contains(c)) =>
+            {
+                return None;
+            }
+            _ => {}
+        }
+
+        match self.frame(line) {
+            Some((frame, source)) => {
+                self.expect = Expect::Source(source);
+                Some(frame)
+            }
+            None => Some(String::from(line)),
+        }
+    }
+
+    /// If line is a frame in a generated file, the frame in the Mamba source and its source line.
+    fn frame(&mut self, line: &str) -> Option<(String, String)> {
+        let rest = line.strip_prefix("  File \"")?;
+        let (path, rest) = rest.split_once("\", line ")?;
+        let (number, rest) = rest.split_at(rest.find(',').unwrap_or(rest.len()));
+        let number: usize = number.parse().ok()?;
+
+        let path = PathBuf::from(path);
+        let relative = path.strip_prefix(&self.out_dir).ok()?;
+        let mamba = match self.src_path.is_dir() {
+            true => self.src_path.join(relative).with_extension("mamba"),
+            false => self.src_path.clone(),
+        };
+
+        let file = self.files.entry(path.clone()).or_insert_with(|| {
+            let python = io::read_source(&path).ok()?;
+            let source = io::read_source(&mamba).ok()?;
+            Some(Generated {
+                source_map: source_map(&python),
+                lines: source.lines().map(String::from).collect(),
+            })
+        });
+        let file = file.as_ref()?;
+        let mamba_line = *file.source_map.get(number.checked_sub(1)?)?;
+        let source = file.lines.get(mamba_line.checked_sub(1)?)?;
+
+        let frame = format!("  File \"{}\", line {mamba_line}{rest}", mamba.display());
+        Some((frame, source.clone()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+    use std::path::Path;
+
+    use tempfile::tempdir;
+
+    use crate::run::{module_path, source_map, Traceback};
+
+    #[test]
+    fn source_map_of_markers() {
+        let python = "import a\n# mamba-line: 3\ndef f(): \n    # mamba-line: 4\n    return 1\n";
+        assert_eq!(source_map(python), vec![0, 3, 3, 4, 4]);
+    }
+
+    #[test]
+    fn module_as_dotted_name_or_file() {
+        let out_dir = Path::new("target");
+        let expected = out_dir.join("pkg").join("main.py");
+        assert_eq!(module_path(out_dir, "pkg.main"), expected);
+        assert_eq!(module_path(out_dir, "pkg/main.mamba"), expected);
+    }
+
+    #[test]
+    fn rewrite_frames_of_generated_files() {
+        let dir = tempdir().expect("temp dir");
+        let (src, out) = (dir.path().join("src"), dir.path().join("out"));
+        fs::create_dir(&src).unwrap();
+        fs::create_dir(&out).unwrap();
+        fs::write(src.join("main.mamba"), "def x := 1\n\nprint(x / 0)\n").unwrap();
+        let python = "# mamba-line: 1\nx = 1\n# mamba-line: 3\nprint(x / 0)\n";
+        fs::write(out.join("main.py"), python).unwrap();
+
+        let stderr = vec![
+            String::from("Traceback (most recent call last):"),
+            format!(
+                "  File \"{}\", line 4, in <module>",
+                out.join("main.py").display()
+            ),
+            String::from("    print(x / 0)"),
+            String::from("          ~~^~~"),
+            String::from("  File \"/usr/lib/other.py\", line 4, in f"),
+            String::from("    other()"),
+            String::from("ZeroDivisionError: division by zero"),
+        ];
+        let mut traceback = Traceback::new(&out, &src);
+        let rewritten: Vec<String> = stderr.iter().flat_map(|l| traceback.rewrite(l)).collect();
+
+        assert_eq!(
+            rewritten,
+            vec![
+                String::from("Traceback (most recent call last):"),
+                format!(
+                    "  File \"{}\", line 3, in <module>",
+                    src.join("main.mamba").display()
+                ),
+                String::from("    print(x / 0)"),
+                String::from("  File \"/usr/lib/other.py\", line 4, in f"),
+                String::from("    other()"),
+                String::from("ZeroDivisionError: division by zero"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,324 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread::sleep;
+use std::time::{Duration, SystemTime};
+
+use itertools::Itertools;
+
+use crate::check::context::Context;
+use crate::common::result::WithSource;
+use crate::dependency::{defines, uses};
+use crate::parse::ast::AST;
+use crate::{
+    check_and_emit, display_path, generate, io, io_paths, source_files, write_output, Arguments,
+    PipelineArguments,
+};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Watch the source directory and rebuild whenever a `*.mamba` file changes.
+///
+/// Input and output follow the same rules as [crate::transpile_dir].
+/// Diagnostics are printed after each rebuild.
+/// Only returns if the source directory can no longer be read.
+pub fn watch_dir(
+    dir: &Path,
+    src: Option<&str>,
+    target: Option<&str>,
+    arguments: &Arguments,
+) -> Result<(), Vec<String>> {
+    let mut watch = Watch::new(dir, src, target, arguments)?;
+    info!("Watching '{}' for changes", watch.src_path.display());
+
+    loop {
+        if let Some(rebuild) = watch.poll()? {
+            rebuild
+                .errors
+                .iter()
+                .unique()
+                .for_each(|msg| eprintln!("{msg}"));
+            info!(
+                "Rebuilt {} of {} files, {} error(s)",
+                rebuild.built.len(),
+                watch.files.len(),
+                rebuild.errors.len()
+            );
+        }
+        sleep(POLL_INTERVAL);
+    }
+}
+
+/// Result of a rebuild.
+#[derive(Debug)]
+pub struct Rebuild {
+    /// Source files for which output was written.
+    pub built: Vec<PathBuf>,
+    pub errors: Vec<String>,
+}
+
+/// Keeps parsed files and the context of the definitions of all files in memory between
+/// rebuilds, so that only changed files and the files that depend on them are checked again.
+pub struct Watch {
+    src_path: PathBuf,
+    out_dir: PathBuf,
+    pipeline_args: PipelineArguments,
+    /// Primitives and standard library, which never change.
+    base: Context,
+    /// Definitions of all files.
+    user: Context,
+    files: HashMap<PathBuf, WatchedFile>,
+}
+
+struct WatchedFile {
+    out_path: PathBuf,
+    /// Modification time of the source when it was last built or failed to parse.
+    ///
+    /// [None] if it was not built since it last changed, such as when the last rebuild failed.
+    modified: Option<SystemTime>,
+    source: String,
+    /// Last AST which parsed successfully, so a syntax error does not affect other files.
+    ast: Option<AST>,
+    defines: HashSet<String>,
+    uses: HashSet<String>,
+}
+
+impl Watch {
+    /// Create watch, which builds all files on first [Watch::poll].
+    pub fn new(
+        dir: &Path,
+        src: Option<&str>,
+        target: Option<&str>,
+        arguments: &Arguments,
+    ) -> Result<Watch, Vec<String>> {
+        let (src_path, out_dir) = io_paths(dir, src, target)?;
+        let stub_paths: Vec<PathBuf> = arguments.stub_paths.iter().map(|p| dir.join(p)).collect();
+        let base = Context::default()
+            .into_with_primitives()
+            .and_then(Context::into_with_std_lib)
+            .and_then(|base| base.into_with_stubs(&stub_paths))
+            .map_err(|errs| errs.iter().map(|e| format!("{e}")).collect::<Vec<_>>())?;
+
+        Ok(Watch {
+            src_path,
+            out_dir,
+            pipeline_args: PipelineArguments::from(arguments),
+            base,
+            user: Context::default(),
+            files: HashMap::new(),
+        })
+    }
+
+    /// Check source directory for changes.
+    ///
+    /// If any file was added, changed or removed, rebuild those files and all files which use
+    /// a name defined in them, as well as files which were not built since they last changed.
+    /// Return [None] if nothing changed.
+    pub fn poll(&mut self) -> Result<Option<Rebuild>, Vec<String>> {
+        let (mut changed, mut pending, mut errors) = (vec![], vec![], vec![]);
+        let mut read: HashMap<PathBuf, SystemTime> = HashMap::new();
+        let mut names: HashSet<String> = HashSet::new();
+
+        let current = source_files(&self.src_path, &self.out_dir)?;
+        let removed: Vec<PathBuf> = (self.files.keys())
+            .filter(|path| !current.iter().any(|(in_path, _)| in_path == *path))
+            .cloned()
+            .collect();
+        for path in removed {
+            if let Some(file) = self.files.remove(&path) {
+                info!("Removed '{}'", path.display());
+                let _ = fs::remove_file(file.out_path.with_extension("py"));
+                let _ = fs::remove_file(file.out_path.with_extension("pyi"));
+                names.extend(file.defines);
+            }
+        }
+
+        for (in_path, out_path) in current {
+            let modified = fs::metadata(&in_path)
+                .and_then(|metadata| metadata.modified())
+                .map_err(|e| vec![format!("{}: {}", e, in_path.display())])?;
+            if matches!(self.files.get(&in_path), Some(file) if file.modified == Some(modified)) {
+                continue;
+            }
+
+            let source = io::read_source(&in_path).map_err(|error| vec![error])?;
+            read.insert(in_path.clone(), modified);
+            if let Some(file) = self.files.get_mut(&in_path) {
+                if file.source == source {
+                    if file.modified.is_some() {
+                        file.modified = Some(modified); // Touched but not changed
+                    } else {
+                        pending.push(in_path.clone()); // Not built since it last changed
+                    }
+                    continue;
+                }
+            }
+
+            let file = self.files.entry(in_path.clone()).or_insert(WatchedFile {
+                out_path,
+                modified: None,
+                source: String::new(),
+                ast: None,
+                defines: HashSet::new(),
+                uses: HashSet::new(),
+            });
+            file.modified = None;
+
+            names.extend(file.defines.clone());
+            match source.parse::<AST>() {
+                Ok(ast) => {
+                    file.defines = defines(&ast);
+                    file.ast = Some(ast);
+                    changed.push(in_path.clone());
+                }
+                Err(err) => {
+                    let path = Some(display_path(in_path.clone(), &self.src_path));
+                    errors.push(format!("{}", err.with_source(&Some(source.clone()), &path)));
+                    file.modified = Some(modified); // Not reported again until it changes
+                }
+            }
+            file.uses = uses(&source);
+            file.source = source;
+            names.extend(file.defines.clone());
+        }
+
+        if changed.is_empty() && names.is_empty() && errors.is_empty() {
+            return Ok(None);
+        }
+        changed.append(&mut pending);
+
+        let asts: Vec<AST> = self.files.values().flat_map(|f| f.ast.clone()).collect();
+        self.user = match Context::user(&asts) {
+            Ok(user) => user,
+            Err(errs) => {
+                errors.append(&mut errs.iter().map(|e| format!("{e}")).collect());
+                return Ok(Some(Rebuild {
+                    built: vec![],
+                    errors,
+                }));
+            }
+        };
+        let ctx = self.user.clone().union(&self.base);
+
+        let dependents = (self.files.iter())
+            .filter(|(path, file)| !changed.contains(path) && !file.uses.is_disjoint(&names))
+            .map(|(path, _)| path.clone())
+            .collect::<Vec<PathBuf>>();
+
+        let mut built = vec![];
+        for path in changed.into_iter().chain(dependents).sorted() {
+            let file = &self.files[&path];
+            let ast = match &file.ast {
+                Some(ast) => ast,
+                None => continue,
+            };
+
+            let source = (
+                file.source.clone(),
+                Some(display_path(path.clone(), &self.src_path)),
+            );
+            let ast_ty = check_and_emit(ast, &ctx, &source.0, &file.out_path, &self.pipeline_args)?;
+            let result = generate(ast, ast_ty, &ctx, &source, &self.pipeline_args);
+            let file = self.files.get_mut(&path).expect("watched file");
+            match result {
+                Ok(output) => {
+                    write_output(&output, &file.out_path).map_err(|error| vec![error])?;
+                    file.modified = read.get(&path).cloned().or(file.modified);
+                    built.push(path);
+                }
+                Err(mut errs) => {
+                    errors.append(&mut errs);
+                    file.modified = None;
+                }
+            }
+        }
+
+        Ok(Some(Rebuild { built, errors }))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+    use std::fs::File;
+    use std::path::Path;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use tempfile::tempdir;
+
+    use crate::watch::Watch;
+    use crate::Arguments;
+
+    static MODIFIED: AtomicU64 = AtomicU64::new(1);
+
+    /// Write source with a distinct modification time, as writes may be faster than its resolution.
+    fn write(dir: &Path, file: &str, source: &str) {
+        let path = dir.join("src").join(file);
+        fs::write(&path, source).expect("write source");
+        let modified = UNIX_EPOCH + Duration::from_secs(MODIFIED.fetch_add(1, Ordering::SeqCst));
+        let file = File::options().write(true).open(&path).unwrap();
+        file.set_modified(modified).expect("set modified");
+    }
+
+    fn built(watch: &mut Watch) -> Vec<String> {
+        let rebuild = watch.poll().expect("poll").expect("rebuild");
+        assert!(rebuild.errors.is_empty(), "{:?}", rebuild.errors);
+        (rebuild.built.iter())
+            .map(|p| p.file_name().unwrap().to_string_lossy().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn rebuild_changed_and_dependents() {
+        let dir = tempdir().expect("temp dir");
+        fs::create_dir(dir.path().join("src")).unwrap();
+        write(dir.path(), "a.mamba", "def f(x: Int) -> Int => x + 1\n");
+        write(dir.path(), "b.mamba", "def y := f(10)\n");
+        write(dir.path(), "c.mamba", "def z := 20\n");
+
+        let mut watch = Watch::new(dir.path(), None, None, &Arguments::default()).unwrap();
+        assert_eq!(built(&mut watch), vec!["a.mamba", "b.mamba", "c.mamba"]);
+        assert!(watch.poll().unwrap().is_none());
+        assert!(dir.path().join("target").join("b.py").exists());
+
+        write(dir.path(), "c.mamba", "def z := 30\n");
+        assert_eq!(built(&mut watch), vec!["c.mamba"]);
+
+        write(dir.path(), "a.mamba", "def f(x: Int) -> Int => x + 2\n");
+        assert_eq!(built(&mut watch), vec!["a.mamba", "b.mamba"]);
+    }
+
+    #[test]
+    fn report_errors_and_keep_watching() {
+        let dir = tempdir().expect("temp dir");
+        fs::create_dir(dir.path().join("src")).unwrap();
+        write(dir.path(), "a.mamba", "def f(x: Int) -> Int => x\n");
+        write(dir.path(), "b.mamba", "def y := f(10)\n");
+
+        let mut watch = Watch::new(dir.path(), None, None, &Arguments::default()).unwrap();
+        assert_eq!(built(&mut watch), vec!["a.mamba", "b.mamba"]);
+
+        write(dir.path(), "a.mamba", "def g(x: Int) -> Int => x\n");
+        let rebuild = watch.poll().unwrap().expect("rebuild");
+        assert_eq!(rebuild.errors.len(), 1, "{:?}", rebuild.errors);
+
+        write(dir.path(), "b.mamba", "def y := g(10)\n");
+        assert_eq!(built(&mut watch), vec!["b.mamba"]);
+    }
+
+    #[test]
+    fn remove_output_of_removed_file() {
+        let dir = tempdir().expect("temp dir");
+        fs::create_dir(dir.path().join("src")).unwrap();
+        write(dir.path(), "a.mamba", "def x := 10\n");
+
+        let mut watch = Watch::new(dir.path(), None, None, &Arguments::default()).unwrap();
+        assert_eq!(built(&mut watch), vec!["a.mamba"]);
+        assert!(dir.path().join("target").join("a.py").exists());
+
+        fs::remove_file(dir.path().join("src").join("a.mamba")).unwrap();
+        assert!(built(&mut watch).is_empty());
+        assert!(!dir.path().join("target").join("a.py").exists());
+    }
+}
@@ -20,3 +20,15 @@ fn calls_wrong_type() {
     let source = resource_content(false, &["type", "call"], "calls_wrong_type.mamba");
     check_all(&[source.parse::<AST>().unwrap()]).unwrap_err();
 }
+
+#[test]
+fn safe_call_not_nullable() {
+    let source = resource_content(false, &["type", "call"], "safe_call_not_nullable.mamba");
+    check_all(&[source.parse::<AST>().unwrap()]).unwrap_err();
+}
+
+#[test]
+fn safe_call_undefined_field() {
+    let source = resource_content(false, &["type", "call"], "safe_call_undefined_field.mamba");
+    check_all(&[source.parse::<AST>().unwrap()]).unwrap_err();
+}
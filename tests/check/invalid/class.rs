@@ -161,3 +161,71 @@ fn wrong_generic_type() {
     let source = resource_content(false, &["type", "class"], "wrong_generic_type.mamba");
     check_all(&[source.parse::<AST>().unwrap()]).unwrap_err();
 }
+
+#[test]
+fn reassign_property_without_setter() {
+    let source = resource_content(
+        false,
+        &["type", "class"],
+        "reassign_property_without_setter.mamba",
+    );
+    check_all(&[source.parse::<AST>().unwrap()]).unwrap_err();
+}
+
+#[test]
+fn property_getter_mutates_self() {
+    let source = resource_content(
+        false,
+        &["type", "class"],
+        "property_getter_mutates_self.mamba",
+    );
+    check_all(&[source.parse::<AST>().unwrap()]).unwrap_err();
+}
+
+#[test]
+fn call_on_class_with_self() {
+    let source = resource_content(false, &["type", "class"], "call_on_class_with_self.mamba");
+    check_all(&[source.parse::<AST>().unwrap()]).unwrap_err();
+}
+
+#[test]
+fn static_method_uses_self() {
+    let source = resource_content(false, &["type", "class"], "static_method_uses_self.mamba");
+    check_all(&[source.parse::<AST>().unwrap()]).unwrap_err();
+}
+
+#[test]
+fn reassign_data_class_fin_field() {
+    let source = resource_content(
+        false,
+        &["type", "class"],
+        "reassign_data_class_fin_field.mamba",
+    );
+    check_all(&[source.parse::<AST>().unwrap()]).unwrap_err();
+}
+
+#[test]
+fn compare_data_class_with_int() {
+    let source = resource_content(
+        false,
+        &["type", "class"],
+        "compare_data_class_with_int.mamba",
+    );
+    check_all(&[source.parse::<AST>().unwrap()]).unwrap_err();
+}
+
+#[test]
+fn fin_instance_mutating_call() {
+    let source = resource_content(
+        false,
+        &["type", "class"],
+        "fin_instance_mutating_call.mamba",
+    );
+    check_all(&[source.parse::<AST>().unwrap()]).unwrap_err();
+}
+
+#[test]
+fn cls_outside_class() {
+    let source = resource_content(false, &["type", "class"], "cls_outside_class.mamba");
+    check_all(&[source.parse::<AST>().unwrap()]).unwrap_err();
+}
@@ -219,3 +219,27 @@ fn set_not_subscriptable() {
     );
     check_all(&[source.parse::<AST>().unwrap()]).unwrap_err();
 }
+
+#[test]
+fn fin_arg_pop() {
+    let source = resource_content(false, &["type", "collection"], "fin_arg_pop.mamba");
+    check_all(&[source.parse::<AST>().unwrap()]).unwrap_err();
+}
+
+#[test]
+fn fin_dict_update() {
+    let source = resource_content(false, &["type", "collection"], "fin_dict_update.mamba");
+    check_all(&[source.parse::<AST>().unwrap()]).unwrap_err();
+}
+
+#[test]
+fn fin_list_append() {
+    let source = resource_content(false, &["type", "collection"], "fin_list_append.mamba");
+    check_all(&[source.parse::<AST>().unwrap()]).unwrap_err();
+}
+
+#[test]
+fn fin_set_add() {
+    let source = resource_content(false, &["type", "collection"], "fin_set_add.mamba");
+    check_all(&[source.parse::<AST>().unwrap()]).unwrap_err();
+}
@@ -83,6 +83,46 @@ fn not_integer() {
     check_all(&[source.parse::<AST>().unwrap()]).unwrap_err();
 }
 
+#[test]
+fn match_class_pattern_undefined_field() {
+    let source = resource_content(
+        false,
+        &["type", "control_flow"],
+        "match_class_pattern_undefined_field.mamba",
+    );
+    check_all(&[source.parse::<AST>().unwrap()]).unwrap_err();
+}
+
+#[test]
+fn match_class_pattern_wrong_type() {
+    let source = resource_content(
+        false,
+        &["type", "control_flow"],
+        "match_class_pattern_wrong_type.mamba",
+    );
+    check_all(&[source.parse::<AST>().unwrap()]).unwrap_err();
+}
+
+#[test]
+fn match_or_pattern_different_vars() {
+    let source = resource_content(
+        false,
+        &["type", "control_flow"],
+        "match_or_pattern_different_vars.mamba",
+    );
+    check_all(&[source.parse::<AST>().unwrap()]).unwrap_err();
+}
+
+#[test]
+fn match_tuple_pattern_wrong_type() {
+    let source = resource_content(
+        false,
+        &["type", "control_flow"],
+        "match_tuple_pattern_wrong_type.mamba",
+    );
+    check_all(&[source.parse::<AST>().unwrap()]).unwrap_err();
+}
+
 #[test]
 fn or_float() {
     let source = resource_content(false, &["type", "control_flow"], "or_float.mamba");
@@ -118,3 +158,113 @@ fn variable_defined_in_else() {
     );
     check_all(&[source.parse::<AST>().unwrap()]).unwrap_err();
 }
+
+#[test]
+fn use_before_assign_if_no_else() {
+    let source = resource_content(
+        false,
+        &["type", "control_flow"],
+        "use_before_assign_if_no_else.mamba",
+    );
+    check_all(&[source.parse::<AST>().unwrap()]).unwrap_err();
+}
+
+#[test]
+fn use_before_assign_in_loop() {
+    let source = resource_content(
+        false,
+        &["type", "control_flow"],
+        "use_before_assign_in_loop.mamba",
+    );
+    check_all(&[source.parse::<AST>().unwrap()]).unwrap_err();
+}
+
+#[test]
+fn use_before_assign_match_arm() {
+    let source = resource_content(
+        false,
+        &["type", "control_flow"],
+        "use_before_assign_match_arm.mamba",
+    );
+    check_all(&[source.parse::<AST>().unwrap()]).unwrap_err();
+}
+
+#[test]
+fn use_before_assign_handle_arm() {
+    let source = resource_content(
+        false,
+        &["type", "control_flow"],
+        "use_before_assign_handle_arm.mamba",
+    );
+    check_all(&[source.parse::<AST>().unwrap()]).unwrap_err();
+}
+
+#[test]
+fn nullable_not_narrowed_in_else() {
+    let source = resource_content(
+        false,
+        &["type", "control_flow"],
+        "nullable_not_narrowed_in_else.mamba",
+    );
+    check_all(&[source.parse::<AST>().unwrap()]).unwrap_err();
+}
+
+#[test]
+fn nullable_not_narrowed_without_exit() {
+    let source = resource_content(
+        false,
+        &["type", "control_flow"],
+        "nullable_not_narrowed_without_exit.mamba",
+    );
+    check_all(&[source.parse::<AST>().unwrap()]).unwrap_err();
+}
+
+#[test]
+fn nullable_not_narrowed_in_or() {
+    let source = resource_content(
+        false,
+        &["type", "control_flow"],
+        "nullable_not_narrowed_in_or.mamba",
+    );
+    check_all(&[source.parse::<AST>().unwrap()]).unwrap_err();
+}
+
+#[test]
+fn field_of_mutable_not_narrowed() {
+    let source = resource_content(
+        false,
+        &["type", "control_flow"],
+        "field_of_mutable_not_narrowed.mamba",
+    );
+    check_all(&[source.parse::<AST>().unwrap()]).unwrap_err();
+}
+
+#[test]
+fn nullable_widened_after_reassign() {
+    let source = resource_content(
+        false,
+        &["type", "control_flow"],
+        "nullable_widened_after_reassign.mamba",
+    );
+    check_all(&[source.parse::<AST>().unwrap()]).unwrap_err();
+}
+
+#[test]
+fn async_for_outside_async() {
+    let source = resource_content(
+        false,
+        &["type", "control_flow"],
+        "async_for_outside_async.mamba",
+    );
+    check_all(&[source.parse::<AST>().unwrap()]).unwrap_err();
+}
+
+#[test]
+fn for_tuple_wrong_element() {
+    let source = resource_content(
+        false,
+        &["type", "control_flow"],
+        "for_tuple_wrong_element.mamba",
+    );
+    check_all(&[source.parse::<AST>().unwrap()]).unwrap_err();
+}
@@ -221,3 +221,23 @@ fn list_not_a_tuple() {
     let source = resource_content(false, &["type", "definition"], "list_not_a_tuple.mamba");
     check_all(&[source.parse::<AST>().unwrap()]).unwrap_err();
 }
+
+#[test]
+fn nested_tuple_wrong_element() {
+    let source = resource_content(
+        false,
+        &["type", "definition"],
+        "nested_tuple_wrong_element.mamba",
+    );
+    check_all(&[source.parse::<AST>().unwrap()]).unwrap_err();
+}
+
+#[test]
+fn nested_tuple_wrong_size() {
+    let source = resource_content(
+        false,
+        &["type", "definition"],
+        "nested_tuple_wrong_size.mamba",
+    );
+    check_all(&[source.parse::<AST>().unwrap()]).unwrap_err();
+}
@@ -36,3 +36,38 @@ fn with_not_expression() {
     let source = resource_content(false, &["type", "error"], "with_not_expression.mamba");
     check_all(&[source.parse::<AST>().unwrap()]).unwrap_err();
 }
+
+#[test]
+fn with_no_exit() {
+    let source = resource_content(false, &["type", "error"], "with_no_exit.mamba");
+    let err = check_all(&[source.parse::<AST>().unwrap()]).unwrap_err();
+
+    assert_eq!(err.len(), 1);
+    assert_eq!(
+        err[0].msg,
+        "MyResource does not define __exit__(MyResource, None, None, None)"
+    );
+    assert_eq!(err[0].pos.map(|pos| pos.start.line), Some(6));
+}
+
+#[test]
+fn with_not_context_manager() {
+    let source = resource_content(false, &["type", "error"], "with_not_context_manager.mamba");
+    check_all(&[source.parse::<AST>().unwrap()]).unwrap_err();
+}
+
+#[test]
+fn with_multiple_wrong_type() {
+    let source = resource_content(false, &["type", "error"], "with_multiple_wrong_type.mamba");
+    check_all(&[source.parse::<AST>().unwrap()]).unwrap_err();
+}
+
+#[test]
+fn async_with_sync_context_manager() {
+    let source = resource_content(
+        false,
+        &["type", "error"],
+        "async_with_sync_context_manager.mamba",
+    );
+    check_all(&[source.parse::<AST>().unwrap()]).unwrap_err();
+}
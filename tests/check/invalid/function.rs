@@ -45,6 +45,16 @@ fn no_enough_arg_with_default() {
     check_all(&[source.parse::<AST>().unwrap()]).unwrap_err();
 }
 
+#[test]
+fn anon_fun_tuple_wrong_arity() {
+    let source = resource_content(
+        false,
+        &["type", "function"],
+        "anon_fun_tuple_wrong_arity.mamba",
+    );
+    check_all(&[source.parse::<AST>().unwrap()]).unwrap_err();
+}
+
 #[test]
 fn arg_no_type() {
     let source = resource_content(false, &["type", "function"], "arg_no_type.mamba");
@@ -177,3 +187,93 @@ fn call_mut_function() {
     let source = resource_content(false, &["type", "function"], "call_mut_function.mamba");
     check_all(&[source.parse::<AST>().unwrap()]).unwrap_err();
 }
+
+#[test]
+fn generic_bound_violated() {
+    let source = resource_content(false, &["type", "function"], "generic_bound_violated.mamba");
+    check_all(&[source.parse::<AST>().unwrap()]).unwrap_err();
+}
+
+#[test]
+fn generic_outside_function() {
+    let source = resource_content(
+        false,
+        &["type", "function"],
+        "generic_outside_function.mamba",
+    );
+    check_all(&[source.parse::<AST>().unwrap()]).unwrap_err();
+}
+
+#[test]
+fn generic_different_types() {
+    let source = resource_content(
+        false,
+        &["type", "function"],
+        "generic_different_types.mamba",
+    );
+    check_all(&[source.parse::<AST>().unwrap()]).unwrap_err();
+}
+
+#[test]
+fn generic_field_not_in_bound() {
+    let source = resource_content(
+        false,
+        &["type", "function"],
+        "generic_field_not_in_bound.mamba",
+    );
+    check_all(&[source.parse::<AST>().unwrap()]).unwrap_err();
+}
+
+#[test]
+fn generic_inferred_wrong_type() {
+    let source = resource_content(
+        false,
+        &["type", "function"],
+        "generic_inferred_wrong_type.mamba",
+    );
+    check_all(&[source.parse::<AST>().unwrap()]).unwrap_err();
+}
+
+#[test]
+fn await_outside_async() {
+    let source = resource_content(false, &["type", "function"], "await_outside_async.mamba");
+    check_all(&[source.parse::<AST>().unwrap()]).unwrap_err();
+}
+
+#[test]
+fn await_wrong_type() {
+    let source = resource_content(false, &["type", "function"], "await_wrong_type.mamba");
+    check_all(&[source.parse::<AST>().unwrap()]).unwrap_err();
+}
+
+#[test]
+fn yield_wrong_type() {
+    let source = resource_content(false, &["type", "function"], "yield_wrong_type.mamba");
+    check_all(&[source.parse::<AST>().unwrap()]).unwrap_err();
+}
+
+#[test]
+fn yield_without_return_type() {
+    let source = resource_content(
+        false,
+        &["type", "function"],
+        "yield_without_return_type.mamba",
+    );
+    check_all(&[source.parse::<AST>().unwrap()]).unwrap_err();
+}
+
+#[test]
+fn decorator_wrong_return() {
+    let source = resource_content(false, &["type", "function"], "decorator_wrong_return.mamba");
+    check_all(&[source.parse::<AST>().unwrap()]).unwrap_err();
+}
+
+#[test]
+fn decorator_method_without_self() {
+    let source = resource_content(
+        false,
+        &["type", "function"],
+        "decorator_method_without_self.mamba",
+    );
+    check_all(&[source.parse::<AST>().unwrap()]).unwrap_err();
+}
@@ -104,7 +104,27 @@ pub fn delete_dir(resource_path: &String) -> Result<(), Box<dyn std::error::Erro
 
 #[allow(dead_code)] // It is actually used
 pub fn python_src_to_stmts(python_src: &String) -> Vec<Statement> {
+    // The parser stops at annotations without a value, such as dataclass fields.
+    let python_src: String = python_src
+        .lines()
+        .map(|line| format!("{}\n", with_value(line)))
+        .collect();
     python_parser::file_input(python_parser::make_strspan(python_src.as_ref()))
         .unwrap()
         .1
 }
+
+fn with_value(line: &str) -> String {
+    let trimmed = line.trim_end();
+    match trimmed.trim_start().split_once(": ") {
+        Some((name, ty))
+            if name.chars().all(|c| c.is_alphanumeric() || c == '_')
+                && !name.is_empty()
+                && !ty.contains('=')
+                && !trimmed.ends_with(':') =>
+        {
+            format!("{trimmed} = None")
+        }
+        _ => String::from(line),
+    }
+}
@@ -105,6 +105,96 @@ fn transpile_src_in_dir_custom_target() -> Result<(), Box<dyn std::error::Error>
     del_res
 }
 
+#[test]
+fn transpile_with_manifest() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::main_binary()?;
+    cmd.current_dir(resource_path(true, &["dummy", "proj2"], ""));
+    let stderr = String::from_utf8(cmd.output()?.stderr)?;
+    assert!(
+        stderr.contains("requires at least Python 3.10"),
+        "{}",
+        stderr
+    );
+
+    let mut cmd = Command::main_binary()?;
+    cmd.current_dir(resource_path(true, &["dummy", "proj2"], ""));
+    cmd.arg("--python").arg("3.10");
+    assert!(cmd.output()?.status.success());
+    let output_path = resource_path(true, &["dummy", "proj2", "out"], "");
+    assert!(Path::new(&output_path).join("matching.py").exists());
+
+    delete_dir(&output_path)
+}
+
+#[test]
+fn no_annotate_overrides_manifest() -> Result<(), Box<dyn std::error::Error>> {
+    let output_path = resource_path(true, &["dummy", "proj2", "out"], "");
+    let mut outputs = vec![];
+    for args in [vec![], vec!["--no-annotate"], vec!["--no-annotate", "-a"]] {
+        let mut cmd = Command::main_binary()?;
+        cmd.current_dir(resource_path(true, &["dummy", "proj2"], ""));
+        cmd.arg("--python").arg("3.10").args(args);
+        assert!(cmd.output()?.status.success());
+        outputs.push(std::fs::read_to_string(
+            Path::new(&output_path).join("matching.py"),
+        )?);
+    }
+
+    delete_dir(&output_path)?;
+    assert!(outputs[0].contains("x: int = 10"), "{}", outputs[0]);
+    assert!(outputs[1].contains("x = 10"), "{}", outputs[1]);
+    assert!(outputs[2].contains("x: int = 10"), "{}", outputs[2]);
+    Ok(())
+}
+
+#[test]
+fn run_rewrites_traceback_and_forwards_exit_code() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::main_binary()?;
+    cmd.current_dir(resource_path(true, &["dummy", "proj3"], ""));
+    cmd.arg("run").arg("main").arg("--name").arg("world");
+    let output = cmd.output()?;
+
+    let (stdout, stderr) = (
+        String::from_utf8(output.stdout)?,
+        String::from_utf8(output.stderr)?,
+    );
+    assert_eq!(output.status.code(), Some(1), "{}", stderr);
+    assert_eq!(stdout, "Hello, world!\n");
+    assert!(
+        stderr.contains("main.mamba\", line 4, in fail"),
+        "{}",
+        stderr
+    );
+    assert!(
+        stderr.contains("    raise Exception(\"Failed\")"),
+        "{}",
+        stderr
+    );
+    Ok(())
+}
+
+#[test]
+fn transpile_jobs_same_errors() -> Result<(), Box<dyn std::error::Error>> {
+    let (output_path, _) = resource_content_randomize(false, &["type"], "");
+    let mut errors = vec![];
+    for jobs in ["1", "4"] {
+        let mut cmd = Command::main_binary()?;
+        cmd.current_dir(resource_path(false, &["type"], ""));
+        cmd.arg("-i")
+            .arg("operation")
+            .arg("-o")
+            .arg(&output_path)
+            .arg("-j")
+            .arg(jobs);
+        errors.push(String::from_utf8(cmd.output()?.stderr)?);
+    }
+
+    let del_res = delete_dir(&output_path);
+    assert!(errors[0].contains("─→"), "{}", errors[0]);
+    assert_eq!(errors[0], errors[1]);
+    del_res
+}
+
 #[test]
 fn transpile_file_not_src() -> Result<(), Box<dyn std::error::Error>> {
     let mut cmd = Command::main_binary()?;
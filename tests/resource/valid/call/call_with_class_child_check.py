@@ -1,12 +1,15 @@
 class MyClass:
     pass
 
+
 class MyChildClass(MyClass):
-    def __init__(self):
+    def __init__(self) -> None:
         MyClass.__init__(self)
 
-def my_fun(arg: MyClass):
+
+def my_fun(arg: MyClass) -> None:
     pass
 
+
 my_fun(MyClass())
 my_fun(MyChildClass())
@@ -1,7 +1,8 @@
 from typing import Optional
 
+
 class MyServer:
     _message: Optional[str] = None
 
-    def send(self, x: str):
+    def send(self, x: str) -> None:
         self._message = x
@@ -1,23 +1,21 @@
+from dataclasses import dataclass
+@dataclass
 class Y:
-    def __init__(self, a: float):
-        self.a = a
+    a: float
 
 
 class X:
     y: Y = None
 
-    def __init__(self, a: float):
+    def __init__(self, a: float) -> None:
         self.y = Y(a)
 
 
 x: X = X(10)
-
 x.y.a = x.y.a + 2
 x.y.a = x.y.a - 3
 x.y.a = x.y.a * 6
-
 x.y.a = x.y.a / 7
 x.y.a = x.y.a ** 2
-
 x.y.a = x.y.a << 10
 x.y.a = x.y.a >> 5
@@ -1,15 +1,14 @@
+from dataclasses import dataclass
+@dataclass
 class X:
-    def __init__(self, a: float):
-        self.a = a
+    a: float
 
-x: X = X(10)
 
+x: X = X(10)
 x.a = x.a + 2
 x.a = x.a - 3
 x.a = x.a * 6
-
 x.a = x.a / 7
 x.a = x.a ** 2
-
 x.a = x.a << 10
 x.a = x.a >> 5
@@ -0,0 +1,25 @@
+from dataclasses import dataclass
+@dataclass(frozen = True)
+class Point: 
+    x: int
+    y: int
+    def norm(self) -> int: 
+        return self.x * self.x + self.y * self.y
+
+
+
+@dataclass
+class Counter: 
+    count: int = 0
+
+
+a: Point = Point(1, 2)
+print(a == Point(1, 2))
+print(a != Point(2, 1))
+print(a.norm())
+print(a)
+c: Counter = Counter()
+c.count = c.count + 1
+print(c)
+print({a, Point(1, 2)})
+
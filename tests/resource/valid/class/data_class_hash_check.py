@@ -0,0 +1,16 @@
+from dataclasses import dataclass
+@dataclass
+class B: 
+    x: int
+
+
+@dataclass(frozen = True)
+class P: 
+    x: int
+
+
+b: B = B(1)
+print(b == B(1))
+print({P(1), P(1)})
+d: dict[P, int] = {P(1): 1}
+print(d[P(1)])
@@ -0,0 +1,23 @@
+def make(start: int) -> "Counter":
+    return Counter(start)
+
+
+class Counter:
+    count: int = 0
+
+    def __init__(self, start: int) -> None:
+        self.start: int = start
+
+    def bump(self) -> "Counter":
+        self.count = self.count + 1
+        return self
+
+    def copy(self) -> "Counter":
+        return Counter(self.start)
+
+
+low: Counter
+high: Counter
+low, high = (make(0), make(10))
+counter: Counter = make(5).bump()
+print(counter.count)
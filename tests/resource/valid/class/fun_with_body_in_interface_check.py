@@ -1,9 +1,11 @@
 from abc import ABC, abstractmethod
 
 class MyType(ABC):
+    @staticmethod
     @abstractmethod
     def abstract_fun(my_arg: int) -> str:
         pass
 
+    @staticmethod
     def concrete_fun(x: int) -> int:
         return x + 10
@@ -1,3 +1,9 @@
-class MyClass:
+from typing import Generic, TypeVar
+
+A = TypeVar("A")
+
+
+class MyClass(Generic[A]):
+    @staticmethod
     def f() -> int:
         return 10
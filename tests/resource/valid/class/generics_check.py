@@ -1,41 +1,42 @@
-from typing import Callable, Optional
+from dataclasses import dataclass
+from typing import Callable, Generic, Optional, TypeVar
+
+A = TypeVar("A")
+C = TypeVar("C")
 
 
 class Err1(Exception):
-    def __init__(self, msg: str):
+    def __init__(self, msg: str) -> None:
         Exception.__init__(self, msg)
 
 
 class Err2(Exception):
-    def __init__(self, msg: str):
+    def __init__(self, msg: str) -> None:
         Exception.__init__(self, msg)
 
 
-class MyType:
-    def __init__(self, super_field: str):
-        self.super_field = super_field
+@dataclass
+class MyType(Generic[A, C]):
+    super_field: str
 
 
-class MyClass2(MyType):
+class MyClass2(MyType[A, C], Generic[C, A]):
     z_modified: str = "asdf"
     other_field: int = 10
 
-    def __init__(self, other_field: int, z: int):
+    def __init__(self, other_field: int, z: int) -> None:
         MyType.__init__(self, "the quick brown fox jumped over the slow donkey")
-
         if z > 10:
             raise Err1("Something is wrong!")
         self.z_modified = "fdsa"
-
+        a: int
+        b: int
         a, b = (10, 20)
         a, b = (30, 40)
         (a, b) = (0, 10)
-
         my_bool: bool = True
-
-        a: int = None
         try:
-            a: int = self.error_function()
+            a = self.error_function()
         except Err1 as err1:
             print(err1)
             a = -1
@@ -46,10 +47,10 @@ class MyClass2(MyType):
     def error_function(self) -> int:
         return 200
 
-    def connect(self):
+    def connect(self) -> None:
         self.other_field = 200
 
-    def _fun_b(self):
+    def _fun_b(self) -> None:
         print("this function is private!")
 
     def factorial(self, x: int = 0) -> int:
@@ -1,16 +1,16 @@
 class MyType:
-    def __init__(self, a: str):
-        self.a = a
+    def __init__(self, a: str) -> None:
+        self.a: str = a
 
 
 class MyType2:
-    def __init__(self, b: str):
-        self.b = b
+    def __init__(self, b: str) -> None:
+        self.b: str = b
 
 
 class MyClass1(MyType, MyType2):
     other: int = None
 
-    def __init__(self):
+    def __init__(self) -> None:
         MyType.__init__(self, "asdf")
         MyType2.__init__(self, "qwerty")
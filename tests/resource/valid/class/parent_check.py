@@ -3,14 +3,16 @@ from abc import ABC, abstractmethod
 
 class MyType(ABC):
     @abstractmethod
-    def fun_a(self): pass
+    def fun_a(self) -> None:
+        pass
 
     @abstractmethod
-    def factorial(self, x: int) -> int: pass
+    def factorial(self, x: int) -> int:
+        pass
 
 
 class MyClass1(MyType):
     other: int = None
 
-    def __init__(self):
+    def __init__(self) -> None:
         MyType.__init__(self, "asdf")
@@ -1,12 +1,13 @@
+from dataclasses import dataclass
+@dataclass
 class Y:
-    def __init__(self, a: float):
-        self.a = a
+    a: float
 
 
 class X:
     y: Y = None
 
-    def __init__(self, a: float):
+    def __init__(self, a: float) -> None:
         self.y = Y(a)
 
 
@@ -0,0 +1,26 @@
+from dataclasses import dataclass
+@dataclass
+class Temperature:
+    celsius: float
+
+
+    @property
+    def kelvin(self) -> float: 
+        return self.celsius + 273.15
+
+    @kelvin.setter
+    def kelvin(self, value: float) -> None: 
+        self.celsius = value - 273.15
+
+    @property
+    def fahrenheit(self) -> float: 
+        return self.celsius * 1.8 + 32.0
+
+
+
+temperature: Temperature = Temperature(20.0)
+temperature.kelvin = 300.0
+print(temperature.celsius)
+print(temperature.kelvin)
+print(temperature.fahrenheit)
+
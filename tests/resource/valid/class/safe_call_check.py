@@ -0,0 +1,31 @@
+from dataclasses import dataclass
+from typing import Optional
+
+@dataclass
+class Address:
+    city: str
+
+
+
+
+@dataclass
+class User:
+    name: str
+    address: Optional[Address]
+
+
+    def get_address(self) -> Optional[Address]:
+        return self.address
+
+
+
+user: Optional[User] = User("Alice", Address("Delft"))
+city: Optional[str] = (None if (_safe_call0 := (None if user is None else user.address)) is None else _safe_call0.city)
+other_city: Optional[str] = (None if (_safe_call1 := (None if user is None else user.get_address())) is None else _safe_call1.city)
+name: Optional[str] = (None if user is None else user.name)
+print(city or "nowhere")
+print(other_city or "nowhere")
+print(name or "nobody")
+nobody: Optional[User] = None
+print((None if nobody is None else nobody.name) or "nobody")
+
@@ -1,30 +1,38 @@
+from typing import Union
+
 class MyClass1:
-    def f1(self):
+    def f1(self) -> None:
         print("1")
 
+
 class MyClass2:
-    def f2(self):
+    def f2(self) -> None:
         print("2")
 
+
 class MyClass3:
-    def f3(self):
+    def f3(self) -> None:
         print("3")
 
+
 class MyClass4:
-    def f4(self):
+    def f4(self) -> None:
         print("4")
 
-x: MyClass1 = MyClass1()
-x.f1()
 
-x: MyClass2 = MyClass2()
+x: Union[MyClass1, MyClass2] = MyClass1()
+x.f1()
+x = MyClass2()
 x.f2()
 
+
 class MyClass:
     x: MyClass3 = MyClass3()
 
-    def g():
+    @staticmethod
+    def g() -> None:
         x.f3()
 
-    def f(x: MyClass4):
+    @staticmethod
+    def f(x: MyClass4) -> None:
         x.f4()
@@ -0,0 +1,24 @@
+from dataclasses import dataclass
+@dataclass
+class Point:
+    x: int
+    y: int
+
+
+    @classmethod
+    def origin(cls) -> "Point": 
+        return cls(0, 0)
+
+    @staticmethod
+    def distance(a: "Point", b: "Point") -> int: 
+        return a.x * b.x + a.y * b.y
+
+    def moved(self, dx: int) -> "Point": 
+        return Point(self.x + dx, self.y)
+
+
+
+p: Point = Point.origin().moved(3)
+print(Point.distance(p, Point(1, 1)))
+print(p.distance(p, Point.origin()))
+
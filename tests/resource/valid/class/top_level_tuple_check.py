@@ -1,4 +1,6 @@
 class A:
+    a: int
+    b: int
     a, b = (10, 100)
 
 
@@ -4,5 +4,5 @@ from typing import Optional
 class X:
     y: Optional[int] = None
 
-    def __init__(self):
+    def __init__(self) -> None:
         print("No assignments here!")
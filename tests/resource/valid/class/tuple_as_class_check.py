@@ -2,5 +2,5 @@ from typing import Tuple
 
 
 class MyTuple(Tuple[int, int, str]):
-    def __init__(self):
+    def __init__(self) -> None:
         Tuple.__init__(self)
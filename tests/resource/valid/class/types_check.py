@@ -1,15 +1,16 @@
 from abc import ABC, abstractmethod
+from dataclasses import dataclass
 from typing import Callable, NewType
 
 
 class MyGeneric(str):
-    def __init__(self):
+    def __init__(self) -> None:
         str.__init__(self)
 
 
+@dataclass
 class MyType:
-    def __init__(self, some_field: str):
-        self.some_field = some_field
+    some_field: str
 
 
 SomeState = NewType("SomeState", MyClass)
@@ -23,7 +24,7 @@ class SuperInterface(ABC):
 class MyInterface(SuperInterface):
     required_field: int = None
 
-    def __init__(self):
+    def __init__(self) -> None:
         SuperInterface.__init__(self)
 
     @abstractmethod
@@ -35,15 +36,19 @@ class MyClass(MyType, MyInterface):
     required_field: int = 100
     private_field: int = 20
 
-    def __init__(self, my_field: int, other_field: str = "Hello"):
+    def __init__(self, my_field: int, other_field: str = "Hello") -> None:
         MyType.__init__(self, other_field)
         MyInterface.__init__(self)
-        self.my_field = my_field
+        self.my_field: int = my_field
 
-    def fun_a(self): self.some_field = f"my field is {self.required_field}"
+    def fun_a(self) -> None:
+        self.some_field = f"my field is {self.required_field}"
 
-    def fun_b(self): print(f"this function is private: {self.private_field}!")
+    def fun_b(self) -> None:
+        print(f"this function is private: {self.private_field}!")
 
-    def some_higher_order(self, fun: Callable[[int], int]) -> int: return fun(self.my_field)
+    def some_higher_order(self, fun: Callable[[int], int]) -> int:
+        return fun(self.my_field)
 
-    def higher_order(self) -> int: return self.some_higher_order(lambda x: x * 2)
+    def higher_order(self) -> int:
+        return self.some_higher_order(lambda x: x * 2)
@@ -1,5 +1,10 @@
+from typing import Optional
+
+
 class X:
+    y: int
+    z: Optional[int]
     y, z = (None, None)
 
-    def __init__(self):
+    def __init__(self) -> None:
         self.y = 10
@@ -1,5 +1,6 @@
 message: str = "may be mutable, for now"
 
+
 class MyClass:
-    def f(self):
+    def f(self) -> None:
         print(message)
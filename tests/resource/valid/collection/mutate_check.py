@@ -0,0 +1,18 @@
+xs: list[int] = [1, 2, 3]
+xs.append(4)
+xs.extend([5, 6])
+xs.insert(0, 0)
+last: int = xs.pop()
+print(xs)
+s: set[int] = {1, 2}
+s.add(3)
+s.discard(1)
+print(s)
+d: dict[str, int] = {"a": 1}
+d.update({"b": 2})
+a: int = d.pop("a")
+ys: list[int] = [1, 2]
+print(ys[0] + last + a)
+for y in ys:
+    print(y)
+
@@ -1,6 +1,9 @@
-b = ()
-c = 1
-a = (c, b)
+from typing import Tuple, Union
+b: Tuple[()] = ()
+c: int = 1
+a: Union[Tuple[int, Tuple[()]], Tuple[Tuple[int, Tuple[()]], Tuple[()]]] = (c, b)
+e: int
+f: bool
 e, f = (2, False)
 
 a = (a, b)
@@ -1,6 +1,7 @@
+my_var: int
 if True:
     print("then")
-    my_var: int = 10
+    my_var = 10
 else:
     print("else")
-    my_var: int = 20
+    my_var = 20
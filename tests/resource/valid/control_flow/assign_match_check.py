@@ -1,4 +1,5 @@
 c: bool = True
+my_var: int
 match c:
     case True:
         my_var = 10
@@ -0,0 +1,24 @@
+def f(b: bool, n: int) -> int:
+    x: int = None
+    if b:
+        x = 10
+
+    else:
+        return 20
+
+    y: int = None
+    match n:
+        case 0:
+            y = 1
+        case _:
+            y = 2
+
+    z: int = 0
+    for i in range(0, n, 1):
+        z = z + i
+
+    return x + y + z
+
+
+print(f(True, 3))
+
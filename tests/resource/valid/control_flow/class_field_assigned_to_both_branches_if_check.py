@@ -1,7 +1,7 @@
 class MyClass:
     x: int = None
 
-    def __init__(self):
+    def __init__(self) -> None:
         if False:
             self.x = 10
         else:
@@ -1,7 +1,7 @@
 class MyClass:
     x: int = None
 
-    def __init__(self):
+    def __init__(self) -> None:
         match 10:
             case 2:
                 self.x = 2
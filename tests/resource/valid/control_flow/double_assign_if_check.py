@@ -1,6 +1,7 @@
 other: int = 20
+my_variable: int
 if True:
     other = 30
-    my_variable: int = 20
+    my_variable = 20
 else:
-    my_variable: int = 10
+    my_variable = 10
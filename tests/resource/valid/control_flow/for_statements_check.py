@@ -1,28 +1,24 @@
-b: set[int] = {1,2}
+from typing import Union
+
+b: Union[int, set[int]] = {1, 2}
 for b in b:
     print(b + 5)
     new: int = b + 1
     new = 30
     print(new)
-
 e: set[int] = {1, 2, 3, 4, 5, 6, 7, 8, 9, 10}
 for d in e:
     print(d)
     print(d - 1)
-
     print(d + 1)
-
 for i in range(0, 34, 1):
     print(i)
-
 for i in range(0, 345 + 1, 1):
     print(i)
-
 a: int = 1
-b: int = 112
+b = 112
 for i in range(a, b, 1):
     print("hello")
-
 c: int = 2451
 for i in range(a, c + 1, 20):
     print("world")
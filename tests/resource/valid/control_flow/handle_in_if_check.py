@@ -1,13 +1,15 @@
 def f() -> int:
     return 10
 
+
+x: int
 if True:
     try:
-        x: int = f()
+        x = f()
     except Exception as err:
-        x: int = 3
+        x = 3
 else:
     try:
-        x: int = f()
+        x = f()
     except Exception as err:
-        x: int = 3
+        x = 3
@@ -0,0 +1,52 @@
+from dataclasses import dataclass
+from typing import Tuple
+
+
+@dataclass
+class Point:
+    x: int
+    y: int
+
+
+pair: Tuple[int, str] = (1, "one")
+match pair:
+    case [0, _]:
+        print("zero")
+    case [n, name] if n > 0:
+        print(f"{name}: {n + 1}")
+    case _:
+        print("negative")
+numbers: list[int] = [1, 2, 3]
+match numbers:
+    case []:
+        print("empty")
+    case [first, *rest]:
+        print(f"{first} and {rest}")
+point: Point = Point(0, 2)
+match point:
+    case Point(x=0, y=py):
+        print(py + 1)
+    case Point(x=px, y=0):
+        print(px - 1)
+    case _:
+        print("elsewhere")
+match 3:
+    case 1 | 2:
+        print("small")
+    case -1:
+        print("negative")
+    case _:
+        print("large")
+
+match (0, ("a", 5)):
+    case [0, [s, n]]:
+        print(f"{s}: {n + 1}")
+    case _:
+        print("other")
+
+origin: Point = Point(0, 2)
+match 2:
+    case origin.y:
+        print("same as origin")
+    case _:
+        print("different")
@@ -1,13 +1,12 @@
 a: str = "d"
-
+b: int
+bb: int
+bbb: int
 b, bb, bbb = (0, 1, 2)
-
 match (b, bb, bbb):
-    case (0, 1, 2):
+    case [0, 1, 2]:
         print("hello world")
-
 nested: str = "other"
-
 match nested:
     case "a":
         "b"
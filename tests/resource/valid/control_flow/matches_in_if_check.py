@@ -1,3 +1,4 @@
+x: int
 if True:
     match 10:
         case 2:
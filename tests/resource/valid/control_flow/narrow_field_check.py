@@ -0,0 +1,21 @@
+from dataclasses import dataclass
+from typing import Optional
+@dataclass
+class Node: 
+    value: Optional[int] = None
+    def inc(self) -> int: 
+        return self.value + 1 if self.value != None else 0
+
+    def or_default(self) -> int: 
+        return self.value if isinstance(self.value,int) else -1
+
+
+
+
+node: Node = Node(3)
+empty: Node = Node()
+doubled: int = empty.value * 2 if empty.value != None else 0
+print(doubled)
+print(node.inc())
+print(node.or_default())
+
@@ -0,0 +1,26 @@
+from typing import Optional, Union
+
+def inc(x: Optional[int]) -> int:
+    return x + 1 if x != None else 0
+
+def add(x: Optional[int], y: Optional[int]) -> int:
+    return x + y if x is not None and y is not None else 0
+
+def double(x: Optional[int]) -> int:
+    if x == None:
+        return 0
+    return x * 2
+
+
+def describe(x: Union[int, str]) -> str:
+    return f"int {x + 1}" if isinstance(x,int) else "str"
+
+def or_default(x: Optional[int]) -> int:
+    return x or 10
+
+print(inc(1))
+print(add(1, 2))
+print(double(2))
+print(describe(3))
+print(or_default(3))
+
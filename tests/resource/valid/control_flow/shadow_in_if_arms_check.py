@@ -1,14 +1,18 @@
+from typing import Union
+
 class MyClass1:
-    def f1(self):
+    def f1(self) -> None:
         print("1")
 
+
 class MyClass2:
-    def f2(self):
+    def f2(self) -> None:
         print("2")
 
+
 if True:
-    x: MyClass1 = MyClass1()
+    x: Union[MyClass1, MyClass2] = MyClass1()
     x.f1()
 else:
-    x: MyClass2 = MyClass2()
+    x = MyClass2()
     x.f2()
@@ -1,14 +1,18 @@
+from typing import Union
+
 class MyClass1:
-    def f1(self):
+    def f1(self) -> None:
         pass
 
+
 class MyClass2:
-    def f2(self):
+    def f2(self) -> None:
         pass
 
-x: MyClass2 = MyClass2()
+
+x: Union[MyClass1, MyClass2] = MyClass2()
 if True:
-    x: MyClass1 = MyClass1()
+    x = MyClass1()
     x.f1()
 else:
     x.f2()
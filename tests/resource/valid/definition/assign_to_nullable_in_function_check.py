@@ -1,5 +1,6 @@
 from typing import Optional
 
-def send(message: str):
+
+def send(message: str) -> None:
     _message: Optional[str] = None
     _message = message
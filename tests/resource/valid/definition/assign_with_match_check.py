@@ -1,3 +1,4 @@
+a: int
 match 40:
     case 2:
         a = 3
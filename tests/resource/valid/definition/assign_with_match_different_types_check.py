@@ -1,17 +1,23 @@
 from typing import Union
+
+
 class MyClass:
     pass
 
+
 class MyClass1:
     pass
 
+
 class MyClass2:
     pass
 
+
+a: Union[MyClass, MyClass1, MyClass2]
 match 40:
     case 2:
-        a: Union[MyClass, MyClass1, MyClass2] = MyClass()
+        a = MyClass()
     case 4:
-        a: Union[MyClass, MyClass1, MyClass2] = MyClass1()
+        a = MyClass1()
     case _:
-        a: Union[MyClass, MyClass1, MyClass2] = MyClass2()
+        a = MyClass2()
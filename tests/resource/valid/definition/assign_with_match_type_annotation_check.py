@@ -1,7 +1,8 @@
+a: int
 match 40:
     case 2:
-        a: int = 3
+        a = 3
     case 4:
-        a: int = 30
+        a = 30
     case _:
-        a: int = 300
+        a = 300
@@ -1,9 +1,10 @@
 x: int = 20
+a: int
 if x > 10:
     if x > 30:
         print("string")
-        a: int = x + 1
+        a = x + 1
     else:
-        a: int = x - 1
+        a = x - 1
 else:
-    a: int = x
+    a = x
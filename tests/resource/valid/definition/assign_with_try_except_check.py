@@ -2,8 +2,8 @@ def g() -> int:
     raise Exception("A")
 
 
-a: int = None
+a: int
 try:
-    a: int = g()
+    a = g()
 except Exception as err:
     a = 10
@@ -1,4 +1,4 @@
-def f(x: int):
+def f(x: int) -> None:
     if x > 10:
         20
     else:
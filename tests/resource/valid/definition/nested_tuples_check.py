@@ -0,0 +1,31 @@
+from typing import Callable, Tuple
+a: int
+b: int
+c: str
+a, (b, c) = (1, (2, "three"))
+d: int
+e: int
+f: str
+d, (e, f) = (4, (5, "six"))
+print(a + b + d + e)
+print(c + f)
+(a, (b, c)) = (7, (8, "nine"))
+print(a + b)
+pairs: list[Tuple[int, Tuple[int, str]]] = [(1, (2, "x")), (3, (4, "y"))]
+for (i, (j, s)) in pairs:
+    total: int = i + j
+    label: str = s
+    print(total)
+    print(label)
+
+scores: dict[str, int] = {"a": 1, "b": 2}
+for (name, score) in scores.items():
+    key: str = name
+    value: int = score
+    print(value)
+
+def apply(g: Callable[[Tuple[int, Tuple[int, str]]], int], x: Tuple[int, Tuple[int, str]]) -> int: 
+    return g(x)
+
+print(apply(lambda _tuple_arg0: (lambda x, y, z: x + y)(_tuple_arg0[0], _tuple_arg0[1][0], _tuple_arg0[1][1]), (1, (2, "z"))))
+
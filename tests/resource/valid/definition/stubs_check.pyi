@@ -0,0 +1,22 @@
+from typing import Any, Callable, Optional, TypeVar, Union
+T = TypeVar("T", bound="Point")
+import math
+
+class MyErr(Exception):
+    def __init__(self, msg: str) -> None: ...
+
+class Point:
+    x: int
+    y: int
+    w: Optional[int]
+    def __init__(self, x: int, y: int) -> None: ...
+    def norm(self) -> int: ...
+    # raises [MyErr]
+    def check(self, z: int = ...) -> int: ...
+
+def first(a: T, f: Callable[[int], str]) -> T: ...
+
+def maybe(x: Optional[int]) -> Union[int, str]: ...
+
+c: int
+d: Any
@@ -1,5 +1,8 @@
 class MyClass:
     my_field: int = 10
 
+
+a: MyClass
+b: int
 a, b = (MyClass(), 10)
 a.my_field = 20
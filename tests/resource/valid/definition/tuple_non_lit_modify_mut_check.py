@@ -1,9 +1,15 @@
 from typing import Tuple
+
+
 class MyClass:
     my_field: int = 10
 
+
 def f() -> Tuple[MyClass, int]:
     return (MyClass(), 10)
 
+
+a: MyClass
+b: int
 a, b = f()
 a.my_field = 20
@@ -1,10 +1,12 @@
 class MyException(Exception):
-    def __init__(self, msg: str):
+    def __init__(self, msg: str) -> None:
         Exception.__init__(self, msg)
 
+
 def g() -> int:
     raise MyException("A")
 
+
 def f(x: int) -> int:
     try:
         return g()
@@ -1,10 +1,10 @@
 class MyErr1(Exception):
-    def __init__(self):
+    def __init__(self) -> None:
         Exception.__init__(self, "Something went wrong")
 
 
 class MyErr2(Exception):
-    def __init__(self, msg: str):
+    def __init__(self, msg: str) -> None:
         Exception.__init__(self, msg)
 
 
@@ -12,9 +12,9 @@ def f(x: int) -> int:
     return x
 
 
-a: int = None
+a: int
 try:
-    a: int = f(10)
+    a = f(10)
 except MyErr1:
     print("Something went wrong")
     a = -1
@@ -1,18 +1,17 @@
 class MyErr(Exception):
-    def __init__(self, message: str):
+    def __init__(self, message: str) -> None:
         Exception.__init__(self)
-        self.message = message
+        self.message: str = message
 
 
 def function_may_throw_err() -> int:
     return 10
 
 
-a: int = None
+a: int
 try:
-    a: int = function_may_throw_err()
+    a = function_may_throw_err()
 except MyErr as err:
     print(f"We have a problem: {err.message}.")
     a = 20
-
 print(f"a has value {a}.")
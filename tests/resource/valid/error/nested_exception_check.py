@@ -1,11 +1,13 @@
 class MyException1(Exception):
-    def __init__(self, msg: str):
+    def __init__(self, msg: str) -> None:
         Exception.__init__(self, msg)
 
+
 class MyException2(Exception):
-    def __init__(self, msg: str):
+    def __init__(self, msg: str) -> None:
         Exception.__init__(self, msg)
 
+
 def f(x: int) -> int:
     match x:
         case 0:
@@ -15,10 +17,12 @@ def f(x: int) -> int:
         case 2:
             raise MyException2()
 
+
 def g() -> int:
     try:
         return f(2)
-    except Exception as err:
+    except MyException1 as err:
         return 10
 
+
 g()
@@ -1,13 +1,31 @@
-def do_something(x: int):
+from dataclasses import dataclass
+from typing import Optional
+def do_something(x: int) -> None: 
     print(f"hello world {x}")
 
-my_resource: int = 10
+@dataclass
+class MyResource: 
+    value: int
+    def __enter__(self) -> int: 
+        return self.value
 
-with my_resource as other:
+    def __exit__(self, exc_type: Optional[int], exc_value: Optional[int], traceback: Optional[int]) -> None: 
+        pass
+
+
+
+my_resource: MyResource = MyResource(10)
+with my_resource as other: 
     do_something(other)
 
-with my_resource as yet_another:
+with my_resource as yet_another: 
     do_something(yet_another)
 
-with my_resource:
-    do_something(my_resource)
+with my_resource: 
+    do_something(my_resource.value)
+
+with my_resource as first: 
+    with MyResource(20) as second: 
+        do_something(first + second)
+
+
@@ -0,0 +1,10 @@
+from typing import Callable, Tuple
+def apply(g: Callable[[Tuple[int, Tuple[int, int]]], int], x: Tuple[int, Tuple[int, int]]) -> int: 
+    return g(x)
+
+def inc(g: Callable[[int], int], x: int) -> int: 
+    return g(x)
+
+print(apply(lambda _tuple_arg0: (lambda a, b, c: a + b + c)(_tuple_arg0[0], _tuple_arg0[1][0], _tuple_arg0[1][1]), (1, (2, 3))))
+print(inc(lambda a: a + 1, 2))
+
@@ -0,0 +1,38 @@
+from typing import Optional
+
+
+class Counter:
+    current: int = 0
+
+    def __init__(self, limit: int) -> None:
+        self.limit: int = limit
+
+    def __aiter__(self) -> "Counter":
+        return self
+
+    async def __anext__(self) -> int:
+        self.current = self.current + 1
+        return self.current
+
+
+class Lock:
+    async def __aenter__(self) -> "Lock":
+        return self
+
+    async def __aexit__(self, a: Optional[int], b: Optional[int], c: Optional[int]) -> None:
+        pass
+
+
+async def fetch(x: int) -> int:
+    return x + 1
+
+
+async def total(counter: Counter, lock: Lock) -> int:
+    sum: int = await fetch(1)
+    async for i in counter:
+        sum = sum + i
+
+    async with lock as l:
+        sum = sum + 1
+
+    return sum
@@ -1,5 +1,10 @@
-def fun_a(): print("hello world")
-def fun_b(x: int): print(f"hello {x}")
+def fun_a() -> None:
+    print("hello world")
+
+
+def fun_b(x: int) -> None:
+    print(f"hello {x}")
+
 
 fun_a()
 fun_b(123)
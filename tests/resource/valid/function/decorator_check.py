@@ -0,0 +1,34 @@
+from dataclasses import dataclass
+from typing import Callable
+
+
+def logged(f: Callable[[int], int]) -> Callable[[int], int]:
+    print("decorating")
+    return f
+
+
+def twice(f: Callable[[int], int]) -> Callable[[int], int]:
+    return lambda x: f(f(x))
+
+
+@logged
+@twice
+def inc(x: int) -> int:
+    return x + 1
+
+
+def tagged(cls: "Callable[[str], Named]") -> "Callable[[str], Named]":
+    return cls
+
+
+@tagged
+@dataclass
+class Named:
+    name: str
+
+    def __str__(self) -> str:
+        return self.name
+
+
+print(inc(1))
+print(Named("a"))
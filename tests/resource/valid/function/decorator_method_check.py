@@ -0,0 +1,17 @@
+from typing import Callable
+
+
+def logged(f: "Callable[[Counter, int], int]") -> "Callable[[Counter, int], int]":
+    print("decorating")
+    return f
+
+
+class Counter:
+    start: int = 10
+
+    @logged
+    def add(self, x: int) -> int:
+        return self.start + x
+
+
+print(Counter().add(1))
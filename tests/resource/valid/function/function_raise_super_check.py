@@ -1,14 +1,19 @@
 from typing import Callable
 
+
 class MyErr(Exception):
-    def __init__(self):
+    def __init__(self) -> None:
         Exception.__init__(self)
 
+
 class MyErr2(Exception):
-    def __init__(self):
+    def __init__(self) -> None:
         Exception.__init__(self)
 
-def f(fun: Callable[[int], int]):
+
+def f(fun: Callable[[int], int]) -> None:
     g()
-def g():
+
+
+def g() -> None:
     pass
@@ -0,0 +1,21 @@
+from typing import Iterator
+
+
+def count_up(n: int) -> Iterator[int]:
+    i: int = 0
+    while i < n:
+        yield i
+        i = i + 1
+
+
+def numbers(n: int) -> Iterator[int]:
+    yield from [10, 20]
+    yield from count_up(n)
+
+
+total: int = 0
+for x in numbers(3):
+    total = total + x
+
+print(total)
+doubled: list[int] = [x * 2 for x in count_up(4) if x > 0]
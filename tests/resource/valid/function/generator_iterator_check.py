@@ -0,0 +1,20 @@
+from typing import Iterator
+
+
+def count_up(n: int) -> Iterator[int]:
+    i: int = 0
+    while i < n:
+        yield i
+        i = i + 1
+
+
+def twice(n: int) -> Iterator[int]:
+    yield from count_up(n)
+    yield from count_up(n)
+
+
+total: int = 0
+for x in twice(3):
+    total = total + x
+
+print(total)
@@ -0,0 +1,35 @@
+from dataclasses import dataclass
+from typing import Generic, TypeVar
+
+T = TypeVar("T")
+T1 = TypeVar("T1", bound="Animal")
+
+
+class Animal:
+    def name(self) -> str:
+        return "animal"
+
+
+@dataclass
+class Box(Generic[T]):
+    item: T
+
+
+def loudest(xs: list[T1]) -> T1:
+    best: T1 = xs[0]
+    return best
+
+
+print(loudest([Animal(), Animal()]).name())
+
+
+def first(xs: list[T]) -> T:
+    return xs[0]
+
+
+def quietest(xs: list[T1]) -> T1:
+    return xs[1]
+
+
+print(first([1, 2]))
+print(quietest([Animal(), Animal()]).name())
@@ -0,0 +1,47 @@
+from dataclasses import dataclass
+from typing import Optional, TypeVar
+
+A = TypeVar("A", bound="Animal")
+T = TypeVar("T")
+
+
+@dataclass
+class Animal:
+    name: str
+
+
+class Dog(Animal):
+    def __init__(self, name: str) -> None:
+        Animal.__init__(self, name)
+
+
+def first(xs: list[T]) -> Optional[T]:
+    for x in xs:
+        return x
+    return None
+
+
+def pick(a: T, b: T, left: bool) -> T:
+    return a if left else b
+
+
+def name_of(animal: A) -> str:
+    return animal.name
+
+
+a: Optional[int] = first([1, 2, 3])
+b: Optional[str] = first(["a", "b"])
+c: str = pick("left", "right", False)
+print(a or 0)
+print(b or "")
+print(c)
+print(name_of(Dog("rex")))
+
+
+class Shelter:
+    def adopt(self, animal: T) -> T:
+        return animal
+
+
+dog: Dog = Shelter().adopt(Dog("fido"))
+print(dog.name)
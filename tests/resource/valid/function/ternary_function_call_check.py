@@ -1,4 +1,5 @@
-def my_fun(x: int):
+def my_fun(x: int) -> None:
     print(x)
 
+
 my_fun(1 if True else 2)
@@ -11,7 +11,7 @@ j: int = 100 // 10
 l: int = 100 % 2
 n: float = math.sqrt(l)
 m: float = n ** 100
-o: int = (6 * 10 ** 4)
+o: float = 6E4
 
 x = +10
 y = -30
@@ -1,8 +1,8 @@
 class MyClass:
-    def __eq__(self, other: MyOtherClass) -> bool:
+    def __eq__(self, other: "MyOtherClass") -> bool:
         return True
 
-    def __ne__(self, other: MyOtherClass) -> bool:
+    def __ne__(self, other: "MyOtherClass") -> bool:
         return False
 
 
@@ -12,6 +12,5 @@ class MyOtherClass:
 
 a: MyClass = MyClass()
 b: MyOtherClass = MyOtherClass()
-
 a == b
 a != b
@@ -1,10 +1,11 @@
+from dataclasses import dataclass
+@dataclass
 class MyClass:
-    def __init__(self, a: int):
-        self.a = a
+    a: int
 
     def f(self) -> bool:
         return self.a > 10
 
-a: MyClass = MyClass(10)
 
+a: MyClass = MyClass(10)
 a.f()
@@ -1,11 +1,12 @@
+from dataclasses import dataclass
+@dataclass
 class MyClass:
-    def __init__(self, a: int):
-        self.a = a
+    a: int
 
-    def f(self, other: MyClass) -> int:
+    def f(self, other: "MyClass") -> int:
         return self.a * other.a
 
+
 a: MyClass = MyClass(10)
 b: MyClass = MyClass(20)
-
 a.f(b)
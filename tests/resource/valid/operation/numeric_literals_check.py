@@ -0,0 +1,15 @@
+hex: int = 0xFF
+octal: int = 0o17
+binary: int = 0b1010_1010
+million: int = 1_000_000
+ints: int = hex + octal + binary + million
+small: float = 1.5e-3
+large: float = 6E4
+signed: float = 2.5E+2
+floats: float = small + large + signed
+imaginary: complex = 3j
+complex_sum: complex = imaginary + 1.5e2J
+print(ints)
+print(floats)
+print(complex_sum)
+
@@ -1,30 +1,29 @@
 class MyErr(Exception):
-    def __init__(self, message: str):
+    def __init__(self, message: str) -> None:
         Exception.__init__(self)
-        self.message = message
+        self.message: str = message
 
 
 class MyOtherErr(Exception):
-    def __init__(self, message: str):
+    def __init__(self, message: str) -> None:
         Exception.__init__(self)
-        self.message = message
+        self.message: str = message
 
 
 def function_may_throw_err() -> int:
     return 10
 
 
-def g():
-    a: int = None
+def g() -> None:
+    a: int
     try:
-        a: int = function_may_throw_err()
+        a = function_may_throw_err()
     except MyErr as err:
         print(f"We have a problem: {err.message}.")
         return None
     except MyOtherErr as err:
         print(f"We have another problem: {err.message}.")
         a = 0
-
     print(f"a has value {a}.")
 
 
@@ -1,30 +1,33 @@
 from ipaddress import IPv4Address
 
+
 class ServerError(Exception):
-    def __init__(self, message:str):
+    def __init__(self, message: str) -> None:
         Exception.__init__(self, message)
 
+
 always_the_same_message: str = "Connected!"
 
+
 class MyServer:
     is_connected: bool = False
     _last_message: str = "temp"
 
-    def __init__(self, ip_address: IPv4Address):
-        self.ip_address = ip_address
+    def __init__(self, ip_address: IPv4Address) -> None:
+        self.ip_address: IPv4Address = ip_address
 
     def last_sent(self) -> str:
         return self._last_message
 
-    def connect(self):
+    def connect(self) -> None:
         self.is_connected = True
         print(always_the_same_message)
 
-    def send(self, message: str):
+    def send(self, message: str) -> None:
         if self.is_connected:
             self._last_message = message
         else:
             raise ServerError("Not connected!")
 
-    def disconnect(self):
+    def disconnect(self) -> None:
         self.is_connected = False
@@ -37,10 +37,57 @@ impl Debug for OutTestErr {
 
 /// Test directory with default set to annotate output.
 fn test_directory(valid: bool, input: &[&str], output: &[&str], file_name: &str) -> OutTestRet {
-    let args = Arguments { annotate: true };
+    let args = Arguments {
+        annotate: true,
+        ..Default::default()
+    };
     test_directory_args(valid, input, output, file_name, &args)
 }
 
+/// Test the stub written next to the output, which is compared to `<file_name>_check.pyi`.
+fn test_stub(input: &[&str], file_name: &str) -> OutTestRet {
+    let (output_path, output_file) =
+        resource_content_randomize(true, input, &format!("{}.pyi", file_name));
+    let current_dir_string = resource_path(true, input, "");
+    let args = Arguments {
+        stubs: true,
+        ..Default::default()
+    };
+
+    let res = transpile_dir(
+        Path::new(&current_dir_string),
+        Some(&format!("{}.mamba", file_name)),
+        Some(&output_path),
+        &args,
+    )
+    .and_then(|_| resource_content_path(&output_file));
+    delete_dir(&output_path).map_err(|_| OutTestErr(vec![]))?;
+    let out_src = res?;
+
+    let check_path = resource_path(true, input, &format!("{}_check.pyi", file_name));
+    let check_src = resource_content_path(&check_path)?;
+    let check_string = python_ast_dump(&check_src)?;
+    let out_string = python_ast_dump(&out_src)?;
+    assert_eq!(out_string, check_string, "Was stub:\n{}", out_src);
+    Ok(())
+}
+
+/// Dump of the AST as parsed by Python itself, as stubs use syntax the parser does not support.
+fn python_ast_dump(python_src: &str) -> OutTestRet<String> {
+    let cmd = Command::new(PYTHON)
+        .arg("-c")
+        .arg("import ast, sys; print(ast.dump(ast.parse(sys.argv[1]), indent=1))")
+        .arg(python_src)
+        .output()
+        .expect("Could not run Python command.");
+
+    if cmd.status.code().unwrap() != 0 {
+        let msg = String::from_utf8(cmd.stderr).unwrap().trim().to_string();
+        return Err(OutTestErr(vec![msg]));
+    }
+    Ok(String::from_utf8(cmd.stdout).unwrap())
+}
+
 fn test_directory_args(
     valid: bool,
     input: &[&str],
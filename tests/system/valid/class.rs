@@ -70,6 +70,11 @@ fn generic_unknown_type_unused() -> OutTestRet {
     )
 }
 
+#[test]
+fn safe_call() -> OutTestRet {
+    test_directory(true, &["class"], &["class", "target"], "safe_call")
+}
+
 #[test]
 fn same_var_different_type() -> OutTestRet {
     test_directory(
@@ -154,3 +159,28 @@ fn var_from_outside_class() -> OutTestRet {
         "var_from_outside_class",
     )
 }
+
+#[test]
+fn forward_reference() -> OutTestRet {
+    test_directory(true, &["class"], &["class", "target"], "forward_reference")
+}
+
+#[test]
+fn property() -> OutTestRet {
+    test_directory(true, &["class"], &["class", "target"], "property")
+}
+
+#[test]
+fn static_method() -> OutTestRet {
+    test_directory(true, &["class"], &["class", "target"], "static_method")
+}
+
+#[test]
+fn data_class() -> OutTestRet {
+    test_directory(true, &["class"], &["class", "target"], "data_class")
+}
+
+#[test]
+fn data_class_hash() -> OutTestRet {
+    test_directory(true, &["class"], &["class", "target"], "data_class_hash")
+}
@@ -1,6 +1,4 @@
-use mamba::Arguments;
-
-use crate::system::{test_directory, test_directory_args, OutTestRet};
+use crate::system::{test_directory, OutTestRet};
 
 #[test]
 fn collection_type() -> OutTestRet {
@@ -104,12 +102,10 @@ fn set_union() -> OutTestRet {
 
 #[test]
 fn tuple_verify() -> OutTestRet {
-    let args = Arguments { annotate: false }; // Type annotations in output wrong
-    test_directory_args(
-        true,
-        &["collection"],
-        &["collection", "target"],
-        "tuple",
-        &args,
-    )
+    test_directory(true, &["collection"], &["collection", "target"], "tuple")
+}
+
+#[test]
+fn mutate() -> OutTestRet {
+    test_directory(true, &["collection"], &["collection", "target"], "mutate")
 }
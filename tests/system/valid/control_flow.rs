@@ -137,6 +137,26 @@ fn if_two_types() -> OutTestRet {
     )
 }
 
+#[test]
+fn assigned_in_all_branches() -> OutTestRet {
+    test_directory(
+        true,
+        &["control_flow"],
+        &["control_flow", "target"],
+        "assigned_in_all_branches",
+    )
+}
+
+#[test]
+fn match_destructure() -> OutTestRet {
+    test_directory(
+        true,
+        &["control_flow"],
+        &["control_flow", "target"],
+        "match_destructure",
+    )
+}
+
 #[test]
 fn match_dont_remove_shadowed() -> OutTestRet {
     test_directory(
@@ -157,6 +177,26 @@ fn matches_in_if() -> OutTestRet {
     )
 }
 
+#[test]
+fn narrow_nullable() -> OutTestRet {
+    test_directory(
+        true,
+        &["control_flow"],
+        &["control_flow", "target"],
+        "narrow_nullable",
+    )
+}
+
+#[test]
+fn narrow_field() -> OutTestRet {
+    test_directory(
+        true,
+        &["control_flow"],
+        &["control_flow", "target"],
+        "narrow_field",
+    )
+}
+
 #[test]
 fn shadow_in_if_arms() -> OutTestRet {
     test_directory(
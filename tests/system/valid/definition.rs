@@ -1,4 +1,4 @@
-use crate::system::{test_directory, OutTestRet};
+use crate::system::{test_directory, test_stub, OutTestRet};
 
 #[test]
 fn long_f_string() -> OutTestRet {
@@ -20,6 +20,16 @@ fn assign_tuples() -> OutTestRet {
     )
 }
 
+#[test]
+fn nested_tuples() -> OutTestRet {
+    test_directory(
+        true,
+        &["definition"],
+        &["definition", "target"],
+        "nested_tuples",
+    )
+}
+
 #[test]
 fn assign_to_nullable_in_function() -> OutTestRet {
     test_directory(
@@ -195,3 +205,8 @@ fn tuple_non_lit_modify_mut() -> OutTestRet {
         "tuple_non_lit_modify_mut",
     )
 }
+
+#[test]
+fn stubs() -> OutTestRet {
+    test_stub(&["definition"], "stubs")
+}
@@ -1,5 +1,25 @@
 use crate::system::{test_directory, OutTestRet};
 
+#[test]
+fn async_function() -> OutTestRet {
+    test_directory(
+        true,
+        &["function"],
+        &["function", "target"],
+        "async_function",
+    )
+}
+
+#[test]
+fn anon_fun_infer_args() -> OutTestRet {
+    test_directory(
+        true,
+        &["function"],
+        &["function", "target"],
+        "anon_fun_infer_args",
+    )
+}
+
 #[test]
 fn callable_fun_arg() -> OutTestRet {
     test_directory(
@@ -15,6 +35,31 @@ fn call_ast_verify() -> OutTestRet {
     test_directory(true, &["function"], &["function", "target"], "calls")
 }
 
+#[test]
+fn decorator() -> OutTestRet {
+    test_directory(true, &["function"], &["function", "target"], "decorator")
+}
+
+#[test]
+fn decorator_import() -> OutTestRet {
+    test_directory(
+        true,
+        &["function"],
+        &["function", "target"],
+        "decorator_import",
+    )
+}
+
+#[test]
+fn decorator_method() -> OutTestRet {
+    test_directory(
+        true,
+        &["function"],
+        &["function", "target"],
+        "decorator_method",
+    )
+}
+
 #[test]
 fn definition_ast_verify() -> OutTestRet {
     test_directory(true, &["function"], &["function", "target"], "definition")
@@ -40,6 +85,41 @@ fn function_raise_super() -> OutTestRet {
     )
 }
 
+#[test]
+fn generator() -> OutTestRet {
+    test_directory(true, &["function"], &["function", "target"], "generator")
+}
+
+#[test]
+fn generator_iterator() -> OutTestRet {
+    test_directory(
+        true,
+        &["function"],
+        &["function", "target"],
+        "generator_iterator",
+    )
+}
+
+#[test]
+fn generic_bound_renamed() -> OutTestRet {
+    test_directory(
+        true,
+        &["function"],
+        &["function", "target"],
+        "generic_bound_renamed",
+    )
+}
+
+#[test]
+fn generic_function() -> OutTestRet {
+    test_directory(
+        true,
+        &["function"],
+        &["function", "target"],
+        "generic_function",
+    )
+}
+
 #[test]
 fn match_function() -> OutTestRet {
     test_directory(
@@ -5,6 +5,16 @@ fn arithmetic() -> OutTestRet {
     test_directory(true, &["operation"], &["operation", "target"], "arithmetic")
 }
 
+#[test]
+fn numeric_literals() -> OutTestRet {
+    test_directory(
+        true,
+        &["operation"],
+        &["operation", "target"],
+        "numeric_literals",
+    )
+}
+
 #[test]
 fn assign_types() -> OutTestRet {
     test_directory(